@@ -1,27 +1,21 @@
-use slack_serverless::{App, Context, Say, Ack, Result};
-use std::env;
+use slack_serverless::response::SlackResponse;
+use slack_serverless::{App, Context, Result};
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize tracing
-    tracing_subscriber::init();
+    tracing_subscriber::fmt::init();
 
     // Create app with bot token and signing secret
-    let app = App::builder()
+    let mut app = App::builder()
         .token_from_env("SLACK_BOT_TOKEN")?
         .signing_secret_from_env("SLACK_SIGNING_SECRET")?
         .build()?;
 
-    // Handle app mentions
-    // In a full implementation, this would use procedural macros
-    // app.event("app_mention", handle_app_mention);
-
-    // Handle slash commands
-    // app.command("/hello", handle_hello_command);
-
-    // Handle button clicks
-    // app.action("button_click", handle_button_click);
+    app.event("app_mention", handle_app_mention)
+        .command("/hello", handle_hello_command)
+        .action("button_click", handle_button_click);
 
     info!("Starting Slack app...");
 
@@ -41,20 +35,15 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-// Example event handler (would be registered via app.event in full implementation)
-async fn handle_app_mention(context: Context) -> Result<()> {
+async fn handle_app_mention(context: Context) -> Result<SlackResponse> {
     context.say.text("Hello! You mentioned me!").await?;
-    Ok(())
+    Ok(SlackResponse::empty())
 }
 
-// Example command handler (would be registered via app.command in full implementation)
-async fn handle_hello_command(context: Context, ack: Ack) -> Result<()> {
-    ack.text("Hello from slash command!").await?;
-    Ok(())
+async fn handle_hello_command(context: Context) -> Result<SlackResponse> {
+    context.ack.text("Hello from slash command!").await
 }
 
-// Example action handler (would be registered via app.action in full implementation)
-async fn handle_button_click(context: Context, ack: Ack) -> Result<()> {
-    ack.text("Button clicked!").await?;
-    Ok(())
-}
\ No newline at end of file
+async fn handle_button_click(context: Context) -> Result<SlackResponse> {
+    context.ack.text("Button clicked!").await
+}
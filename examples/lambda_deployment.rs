@@ -1,11 +1,11 @@
-use slack_serverless::{App, Context, Say, Ack, Result};
+use slack_serverless::response::SlackResponse;
+use slack_serverless::{App, Context, Result};
 use lambda_runtime::Error as LambdaError;
 use serde_json::json;
-use std::env;
 use tracing::{info, error};
 
 #[tokio::main]
-async fn main() -> Result<(), LambdaError> {
+async fn main() -> std::result::Result<(), LambdaError> {
     // Initialize tracing with JSON formatter for CloudWatch
     tracing_subscriber::fmt()
         .with_target(false)
@@ -16,7 +16,7 @@ async fn main() -> Result<(), LambdaError> {
     info!("Lambda function starting");
 
     // Create app optimized for Lambda
-    let app = App::builder()
+    let mut app = App::builder()
         .token_from_env("SLACK_BOT_TOKEN")
         .map_err(|e| {
             error!("Failed to get bot token: {}", e);
@@ -34,12 +34,14 @@ async fn main() -> Result<(), LambdaError> {
             LambdaError::from(e.to_string())
         })?;
 
-    // Register event handlers
-    // In a full implementation, these would be properly registered
-    info!("App configured with handlers for:");
-    info!("- app_mention events");
-    info!("- /status, /deploy, /help commands");
-    info!("- button interactions");
+    app.event("app_mention", handle_app_mention)
+        .command("/status", handle_status_command)
+        .command("/deploy", handle_deploy_command)
+        .command("/help", handle_help_command)
+        .action("get_status", handle_get_status_button)
+        .action("confirm_deploy", handle_confirm_deploy_button)
+        .action("cancel_deploy", handle_cancel_deploy_button)
+        .action("show_help", handle_show_help_button);
 
     // Start the Lambda handler
     info!("Starting Lambda handler");
@@ -47,7 +49,7 @@ async fn main() -> Result<(), LambdaError> {
 }
 
 // Example: Handle app mentions with rich responses
-async fn handle_app_mention(context: Context) -> Result<()> {
+async fn handle_app_mention(context: Context) -> Result<SlackResponse> {
     let blocks = json!([
         {
             "type": "section",
@@ -81,11 +83,11 @@ async fn handle_app_mention(context: Context) -> Result<()> {
     ]);
 
     context.say.blocks(vec![blocks]).await?;
-    Ok(())
+    Ok(SlackResponse::empty())
 }
 
 // Example: Status command
-async fn handle_status_command(context: Context, ack: Ack) -> Result<()> {
+async fn handle_status_command(context: Context) -> Result<SlackResponse> {
     let status_message = json!({
         "response_type": "ephemeral",
         "blocks": [
@@ -99,12 +101,11 @@ async fn handle_status_command(context: Context, ack: Ack) -> Result<()> {
         ]
     });
 
-    ack.text("System is running normally! ✅").await?;
-    Ok(())
+    context.ack.json(status_message).await
 }
 
 // Example: Deploy command with confirmation
-async fn handle_deploy_command(context: Context, ack: Ack) -> Result<()> {
+async fn handle_deploy_command(context: Context) -> Result<SlackResponse> {
     let confirmation_blocks = json!([
         {
             "type": "section",
@@ -138,12 +139,11 @@ async fn handle_deploy_command(context: Context, ack: Ack) -> Result<()> {
         }
     ]);
 
-    ack.blocks(vec![confirmation_blocks]).await?;
-    Ok(())
+    context.ack.blocks(vec![confirmation_blocks]).await
 }
 
 // Example: Help command
-async fn handle_help_command(context: Context, ack: Ack) -> Result<()> {
+async fn handle_help_command(context: Context) -> Result<SlackResponse> {
     let help_text = r#"
 *Available Commands:*
 
@@ -162,34 +162,27 @@ async fn handle_help_command(context: Context, ack: Ack) -> Result<()> {
 • ✅ Event handling
     "#;
 
-    ack.ephemeral(help_text).await?;
-    Ok(())
+    context.ack.ephemeral(help_text).await
 }
 
 // Example: Button interaction handlers
-async fn handle_get_status_button(context: Context, ack: Ack) -> Result<()> {
-    ack.text("Status: All systems operational! 🟢").await?;
-    Ok(())
+async fn handle_get_status_button(context: Context) -> Result<SlackResponse> {
+    context.ack.text("Status: All systems operational! 🟢").await
 }
 
-async fn handle_confirm_deploy_button(context: Context, ack: Ack) -> Result<()> {
-    ack.text("🚀 Deployment started! Check #deployments for updates.").await?;
-    
+async fn handle_confirm_deploy_button(context: Context) -> Result<SlackResponse> {
     // In a real implementation, you might:
     // - Trigger AWS CodePipeline
     // - Call GitHub Actions API
     // - Send SNS message to start deployment process
     // - Update deployment status in DynamoDB
-    
-    Ok(())
+    context.ack.text("🚀 Deployment started! Check #deployments for updates.").await
 }
 
-async fn handle_cancel_deploy_button(context: Context, ack: Ack) -> Result<()> {
-    ack.text("❌ Deployment cancelled.").await?;
-    Ok(())
+async fn handle_cancel_deploy_button(context: Context) -> Result<SlackResponse> {
+    context.ack.text("❌ Deployment cancelled.").await
 }
 
-async fn handle_show_help_button(context: Context, ack: Ack) -> Result<()> {
-    ack.ephemeral("Use `/help` command for detailed help information.").await?;
-    Ok(())
+async fn handle_show_help_button(context: Context) -> Result<SlackResponse> {
+    context.ack.ephemeral("Use `/help` command for detailed help information.").await
 }
\ No newline at end of file
@@ -1,21 +1,19 @@
-use slack_serverless::{App, Context, Say, Ack, Result};
+use slack_serverless::response::SlackResponse;
+use slack_serverless::{App, Context, Result};
 #[cfg(feature = "oauth")]
 use slack_serverless::oauth::dynamodb_store::{DynamoDbInstallationStore, DynamoDbStateStore};
-use aws_config;
-use aws_sdk_dynamodb::Client as DynamoDbClient;
 use std::env;
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize tracing
-    tracing_subscriber::init();
+    tracing_subscriber::fmt::init();
 
     #[cfg(feature = "oauth")]
     {
-        // Initialize AWS configuration
-        let aws_config = aws_config::load_from_env().await;
-        let dynamodb_client = DynamoDbClient::new(&aws_config);
+        // Shared across warm invocations of the same execution environment
+        let dynamodb_client = slack_serverless::shared::dynamodb_client().await;
 
         // Create DynamoDB stores
         let installation_store = DynamoDbInstallationStore::new(
@@ -29,7 +27,7 @@ async fn main() -> Result<()> {
         );
 
         // Create app with OAuth configuration
-        let app = App::builder()
+        let mut app = App::builder()
             .client_id_from_env("SLACK_CLIENT_ID")?
             .client_secret_from_env("SLACK_CLIENT_SECRET")?
             .signing_secret_from_env("SLACK_SIGNING_SECRET")?
@@ -42,13 +40,18 @@ async fn main() -> Result<()> {
                     .installation_store(installation_store)
                     .state_store(state_store)
             })
+            .on_install(|installation| async move {
+                info!("Installed to team {}", installation.team_id);
+                Ok(())
+            })
+            .on_uninstall(|uninstall| async move {
+                info!("Uninstalled from team {}: {:?}", uninstall.team_id, uninstall.kind);
+                Ok(())
+            })
             .build()?;
 
-        // Handle app mentions
-        // app.event("app_mention", handle_app_mention);
-
-        // Handle slash commands
-        // app.command("/deploy", handle_deploy_command);
+        app.event("app_mention", handle_app_mention)
+            .command("/deploy", handle_deploy_command);
 
         info!("Starting OAuth-enabled Slack app...");
 
@@ -67,17 +70,13 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-// Example handlers
-async fn handle_app_mention(context: Context) -> Result<()> {
+async fn handle_app_mention(context: Context) -> Result<SlackResponse> {
     context.say.text("Hello! I'm an OAuth-enabled bot!").await?;
-    Ok(())
+    Ok(SlackResponse::empty())
 }
 
-async fn handle_deploy_command(context: Context, ack: Ack) -> Result<()> {
-    ack.text("🚀 Deployment started! This is a demo command.").await?;
-    
+async fn handle_deploy_command(context: Context) -> Result<SlackResponse> {
     // You could add actual deployment logic here
     // For example, trigger AWS CodePipeline, call GitHub Actions API, etc.
-    
-    Ok(())
-}
\ No newline at end of file
+    context.ack.text("🚀 Deployment started! This is a demo command.").await
+}
@@ -1,16 +1,41 @@
 #[cfg(feature = "lambda")]
+use crate::app::config::{HEALTH_PATH, OAUTH_INSTALL_PATH};
 use crate::app::App;
 use crate::error::{Result, SlackError};
-use crate::request::{SlackRequest, SlackRequestBody, EventRequest, CommandRequest, InteractiveRequest, OAuthRequest};
-use crate::response::SlackResponse;
-use crate::context::Context;
-use crate::client::SlackClient;
+#[cfg(feature = "oauth")]
+use crate::i18n;
+#[cfg(feature = "oauth")]
+use crate::oauth::state_cookie;
+use crate::request::gateway::strip_base_path;
+use crate::request::signature;
+use crate::request::{GatewayContext, SlackRequest, SlackRequestBody, OAuthRequest};
+#[cfg(feature = "streaming")]
+use crate::response::stream::BodyStream;
+#[cfg(feature = "oauth")]
+use crate::response::escape_html;
+use crate::response::{SlackResponse, SlackResponseBody};
+use crate::adapter::warmup::is_warmup_event;
+use crate::app::lifecycle::{self, is_snapstart};
+use crate::context::correlation::{self, CORRELATION_ID_HEADER};
+use crate::context::invocation::{self, Invocation};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use bytes::Bytes;
+use http::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, COOKIE};
 use lambda_runtime::{service_fn, Error as LambdaError, LambdaEvent};
+#[cfg(feature = "streaming")]
+use lambda_runtime::{FunctionResponse, MetadataPrelude, StreamResponse};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use urlencoding::decode;
-use tracing::{info, error, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use tokio::sync::OnceCell;
+use tracing::{debug, info_span, Instrument};
+#[cfg(feature = "oauth")]
+use tracing::{error, info};
+
+static COLD_START: AtomicBool = AtomicBool::new(true);
+static RESTORED: OnceCell<()> = OnceCell::const_new();
 
 #[derive(Clone)]
 pub struct LambdaHandler {
@@ -23,6 +48,15 @@ impl LambdaHandler {
     }
 
     pub async fn run(self) -> std::result::Result<(), LambdaError> {
+        // Still inside the init phase here, so this work is covered by the snapshot
+        // under SnapStart and never billed against the first request otherwise
+        self.app.run_init_hooks().await?;
+        lifecycle::spawn_shutdown_listener(self.app.clone());
+        self.serve().await
+    }
+
+    // The runtime loop alone; `App::run` uses this directly so init hooks run once
+    pub(crate) async fn serve(self) -> std::result::Result<(), LambdaError> {
         lambda_runtime::run(service_fn(move |event| {
             let handler = self.clone();
             async move { handler.handle_request(event).await }
@@ -30,270 +64,481 @@ impl LambdaHandler {
         .await
     }
 
-    async fn handle_request(&self, event: LambdaEvent<ApiGatewayProxyRequest>) -> std::result::Result<ApiGatewayProxyResponse, LambdaError> {
-        let (request, _context) = event.into_parts();
-        
-        match self.process_request(request).await {
-            Ok(response) => Ok(self.to_api_gateway_response(response)),
-            Err(e) => {
-                error!("Error processing request: {}", e);
-                Ok(ApiGatewayProxyResponse {
-                    status_code: 500,
-                    headers: HashMap::new(),
-                    body: Some("Internal Server Error".to_string()),
-                    is_base64_encoded: false,
-                })
+    // For functions behind a Function URL in RESPONSE_STREAM invoke mode. Streamed bodies from
+    // custom routes go out chunk by chunk; everything else, Slack endpoints included, is sent
+    // as a single buffered chunk.
+    #[cfg(feature = "streaming")]
+    pub async fn run_streaming(self) -> std::result::Result<(), LambdaError> {
+        self.app.run_init_hooks().await?;
+        lifecycle::spawn_shutdown_listener(self.app.clone());
+
+        lambda_runtime::run(service_fn(move |event| {
+            let handler = self.clone();
+            async move { handler.handle_streaming_request(event).await }
+        }))
+        .await
+    }
+
+    async fn handle_request(&self, event: LambdaEvent<Value>) -> std::result::Result<ApiGatewayProxyResponse, LambdaError> {
+        let response = self.respond(event).await?;
+        Ok(self.to_api_gateway_response(buffer_stream(response).await))
+    }
+
+    #[cfg(feature = "streaming")]
+    async fn handle_streaming_request(
+        &self,
+        event: LambdaEvent<Value>,
+    ) -> std::result::Result<FunctionResponse<(), BodyStream>, LambdaError> {
+        let response = self.with_response_headers(self.respond(event).await?);
+        let metadata_prelude = MetadataPrelude {
+            status_code: http::StatusCode::from_u16(response.status_code).unwrap_or(http::StatusCode::OK),
+            headers: response.headers,
+            cookies: Vec::new(),
+        };
+
+        let stream: BodyStream = match response.body {
+            SlackResponseBody::Stream(stream) => stream.take().unwrap_or_else(|| Box::pin(futures::stream::empty())),
+            body => {
+                let bytes = match body {
+                    SlackResponseBody::Empty => Bytes::new(),
+                    SlackResponseBody::Content(content) => Bytes::from(content),
+                    SlackResponseBody::Binary(bytes) => Bytes::from(bytes),
+                    body => Bytes::from(serde_json::to_vec(&body).unwrap_or_default()),
+                };
+                Box::pin(futures::stream::once(async move { Ok(bytes) }))
             }
-        }
+        };
+        Ok(FunctionResponse::StreamingResponse(StreamResponse { metadata_prelude, stream }))
     }
 
-    async fn process_request(&self, request: ApiGatewayProxyRequest) -> Result<SlackResponse> {
-        let slack_request = self.to_slack_request(request)?;
-        
-        // Verify request signature
-        if let Err(e) = self.verify_signature(&slack_request) {
-            warn!("Invalid request signature: {}", e);
-            return Ok(SlackResponse {
-                status_code: 401,
-                headers: HashMap::new(),
-                body: crate::response::SlackResponseBody::Empty,
-            });
+    async fn respond(&self, event: LambdaEvent<Value>) -> std::result::Result<SlackResponse, LambdaError> {
+        let (payload, lambda_context) = event.into_parts();
+
+        if is_snapstart() {
+            // Snapshots are taken before any invocation, so the first one after restore is the hook point
+            RESTORED.get_or_try_init(|| self.app.run_restore_hooks()).await?;
         }
 
-        // Handle different request types
-        match &slack_request.body {
-            SlackRequestBody::Event(event_req) => {
-                // Handle URL verification challenge
-                if let Some(challenge) = &event_req.challenge {
-                    return Ok(SlackResponse::challenge(challenge));
-                }
-                
-                self.handle_event_request(slack_request).await
-            }
-            SlackRequestBody::Command(_) => {
-                self.handle_command_request(slack_request).await
-            }
-            SlackRequestBody::Interactive(_) => {
-                self.handle_interactive_request(slack_request).await
-            }
-            SlackRequestBody::OAuth(oauth_req) => {
-                self.handle_oauth_request(slack_request.clone(), oauth_req).await
-            }
-            SlackRequestBody::Raw(_) => {
-                Ok(SlackResponse::empty())
-            }
+        if is_warmup_event(&payload, self.app.config().warmup_header.as_deref()) {
+            debug!("Warmup ping received, skipping request processing");
+            return Ok(SlackResponse::empty());
         }
-    }
 
-    fn to_slack_request(&self, request: ApiGatewayProxyRequest) -> Result<SlackRequest> {
-        let method = request.http_method;
-        let path = request.path.unwrap_or_default();
-        let headers = request.headers.unwrap_or_default();
-        let query_params = request.query_string_parameters.unwrap_or_default();
-        let body = request.body.unwrap_or_default();
+        let request: ApiGatewayProxyRequest = serde_json::from_value(payload)?;
+        let span = info_span!("lambda_invocation", aws_request_id = %lambda_context.request_id);
 
-        let slack_body = self.parse_body(&body, &headers)?;
+        let mut invocation = Invocation::new(lambda_context.request_id)
+            .with_function_arn(lambda_context.invoked_function_arn)
+            .with_deadline_ms(lambda_context.deadline);
+        if let Some(xray_trace_id) = lambda_context.xray_trace_id {
+            invocation = invocation.with_xray_trace_id(xray_trace_id);
+        }
 
-        Ok(SlackRequest {
-            method,
-            path,
-            headers,
-            query_params,
-            body: slack_body,
-        })
+        Ok(invocation::CURRENT.scope(invocation, self.handle_invocation(request).instrument(span)).await)
     }
 
-    fn parse_body(&self, body: &str, headers: &HashMap<String, String>) -> Result<SlackRequestBody> {
-        let content_type = headers.get("content-type")
-            .or_else(|| headers.get("Content-Type"))
-            .unwrap_or(&"".to_string())
-            .to_lowercase();
-
-        if content_type.contains("application/json") {
-            // Event API request
-            let event_req: EventRequest = serde_json::from_str(body)?;
-            Ok(SlackRequestBody::Event(event_req))
-        } else if content_type.contains("application/x-www-form-urlencoded") {
-            // Parse form data
-            let form_data = self.parse_form_data(body)?;
-            
-            if let Some(payload) = form_data.get("payload") {
-                // Interactive component request
-                let interactive_req: InteractiveRequest = serde_json::from_str(payload)?;
-                Ok(SlackRequestBody::Interactive(interactive_req))
-            } else if form_data.contains_key("command") {
-                // Slash command request
-                let command_req = CommandRequest {
-                    token: form_data.get("token").unwrap_or(&"".to_string()).clone(),
-                    team_id: form_data.get("team_id").unwrap_or(&"".to_string()).clone(),
-                    team_domain: form_data.get("team_domain").unwrap_or(&"".to_string()).clone(),
-                    channel_id: form_data.get("channel_id").unwrap_or(&"".to_string()).clone(),
-                    channel_name: form_data.get("channel_name").unwrap_or(&"".to_string()).clone(),
-                    user_id: form_data.get("user_id").unwrap_or(&"".to_string()).clone(),
-                    user_name: form_data.get("user_name").unwrap_or(&"".to_string()).clone(),
-                    command: form_data.get("command").unwrap_or(&"".to_string()).clone(),
-                    text: form_data.get("text").unwrap_or(&"".to_string()).clone(),
-                    response_url: form_data.get("response_url").unwrap_or(&"".to_string()).clone(),
-                    trigger_id: form_data.get("trigger_id").unwrap_or(&"".to_string()).clone(),
-                };
-                Ok(SlackRequestBody::Command(command_req))
-            } else if form_data.contains_key("code") || form_data.contains_key("error") {
-                // OAuth callback
-                let oauth_req = OAuthRequest {
-                    code: form_data.get("code").cloned(),
-                    state: form_data.get("state").cloned(),
-                    error: form_data.get("error").cloned(),
-                };
-                Ok(SlackRequestBody::OAuth(oauth_req))
-            } else {
-                Ok(SlackRequestBody::Raw(body.to_string()))
-            }
-        } else {
-            Ok(SlackRequestBody::Raw(body.to_string()))
+    async fn handle_invocation(&self, request: ApiGatewayProxyRequest) -> SlackResponse {
+        let cold_start = COLD_START.swap(false, Ordering::Relaxed);
+        let started = Instant::now();
+        let correlation_id = request_correlation_id(&request);
+        let slack_request = self.to_slack_request(request, correlation_id.clone());
+        let request_type = slack_request.as_ref().map(|r| r.body.kind()).unwrap_or("invalid");
+        
+        let result = match slack_request {
+            Ok(slack_request) => self.process_request(slack_request).await,
+            Err(e) => Err(e),
+        };
+
+        let mut response = match result {
+            Ok(response) => response,
+            Err(e) => self.app.error_policy().response(&e),
+        };
+        // Lets whoever sees a failure quote an ID that finds the matching logs
+        if let Ok(value) = HeaderValue::from_str(&correlation_id) {
+            response.headers.insert(CORRELATION_ID_HEADER, value);
+        }
+
+        let metrics = self.app.metrics();
+        let tags = [("RequestType", request_type)];
+        metrics.counter("RequestCount", 1, &tags);
+        metrics.histogram("HandlerLatency", started.elapsed().as_secs_f64() * 1000.0, &tags);
+        if response.status_code >= 500 {
+            metrics.counter("Errors", 1, &tags);
+        }
+        if cold_start {
+            metrics.counter("ColdStart", 1, &tags);
         }
+        self.app.flush().await;
+
+        response
     }
 
-    fn parse_form_data(&self, body: &str) -> Result<HashMap<String, String>> {
-        let mut form_data = HashMap::new();
-        
-        for pair in body.split('&') {
-            if let Some((key, value)) = pair.split_once('=') {
-                let decoded_key = decode(key).map_err(|_| SlackError::Internal("Failed to decode form key".to_string()))?;
-                let decoded_value = decode(value).map_err(|_| SlackError::Internal("Failed to decode form value".to_string()))?;
-                form_data.insert(decoded_key.to_string(), decoded_value.to_string());
+    // Verifies and handles an already parsed request; exposed for adapter-level tests
+    pub async fn process_request(&self, slack_request: SlackRequest) -> Result<SlackResponse> {
+        // Load balancers and uptime monitors cannot sign their requests either
+        if self.is_health_route(&slack_request.method, &slack_request.path) {
+            return Ok(self.app.health().await.response());
+        }
+
+        // Install links and redirects come from a browser and cannot be signed; the state
+        // parameter is what ties a redirect to an install this app started
+        if !matches!(slack_request.body, SlackRequestBody::OAuth(_)) {
+            signature::verify(self.app.config().signing_secret.expose(), &slack_request)?;
+        }
+
+        match &slack_request.body {
+            SlackRequestBody::OAuth(oauth_req) => {
+                self.handle_oauth_request(slack_request.clone(), oauth_req).await
             }
+            _ => self.app.dispatch(slack_request).await,
         }
-        
-        Ok(form_data)
     }
 
-    fn verify_signature(&self, request: &SlackRequest) -> Result<()> {
-        use hmac::{Hmac, Mac};
-        use sha2::Sha256;
+    fn to_slack_request(&self, request: ApiGatewayProxyRequest, correlation_id: String) -> Result<SlackRequest> {
+        // Payload format 2.0 (HTTP APIs) moves the method and path; everything else is 1.0
+        let method = request
+            .http_method
+            .clone()
+            .or_else(|| {
+                request
+                    .request_context
+                    .as_ref()
+                    .and_then(|context| context.pointer("/http/method"))
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+            })
+            .unwrap_or_default();
+        let raw_path = request.path.clone().or_else(|| request.raw_path.clone()).unwrap_or_default();
+        let path = match &self.app.config().base_path {
+            Some(base_path) => strip_base_path(&raw_path, base_path),
+            None => raw_path.clone(),
+        };
+        let gateway = request
+            .request_context
+            .as_ref()
+            .map(|request_context| GatewayContext::from_request_context(request_context).with_raw_path(raw_path));
+        let headers = request_headers(&request);
+        let multi_query_params = request_query(&request);
+        // Single-value view keeps the last occurrence, as API Gateway's own single-value map does
+        let query_params = multi_query_params
+            .iter()
+            .filter_map(|(name, values)| values.last().map(|value| (name.clone(), value.clone())))
+            .collect();
+        // API Gateway base64-encodes bodies it doesn't treat as text; Slack signed the decoded bytes
+        let raw_body = match request.body {
+            Some(body) if request.is_base64_encoded == Some(true) => Bytes::from(
+                BASE64_STANDARD
+                    .decode(body)
+                    .map_err(|e| SlackError::InvalidForm(format!("body is not valid base64: {}", e)))?,
+            ),
+            body => Bytes::from(body.unwrap_or_default()),
+        };
 
-        let timestamp = request.headers.get("x-slack-request-timestamp")
-            .ok_or(SlackError::InvalidSignature)?;
-        
-        let signature = request.headers.get("x-slack-signature")
-            .ok_or(SlackError::InvalidSignature)?;
+        // Reject oversized bodies before spending any time parsing them
+        let limit = self.app.config().max_body_bytes;
+        if raw_body.len() > limit {
+            return Err(SlackError::PayloadTooLarge { size: raw_body.len(), limit });
+        }
 
-        let body = match &request.body {
-            SlackRequestBody::Raw(raw) => raw.clone(),
-            _ => serde_json::to_string(&request.body)?,
+        let slack_body = if self.is_oauth_route(&method, &path) {
+            SlackRequestBody::OAuth(OAuthRequest {
+                code: query_param(&multi_query_params, "code"),
+                state: query_param(&multi_query_params, "state"),
+                error: query_param(&multi_query_params, "error"),
+            })
+        } else if self.is_health_route(&method, &path) {
+            SlackRequestBody::Raw(String::new())
+        } else {
+            self.parse_body(&raw_body, &headers)?
         };
 
-        let basestring = format!("v0:{}:{}", timestamp, body);
-        
-        type HmacSha256 = Hmac<Sha256>;
-        let mut mac = HmacSha256::new_from_slice(self.app.config().signing_secret.as_bytes())
-            .map_err(|_| SlackError::InvalidSignature)?;
-        
-        mac.update(basestring.as_bytes());
-        let computed_signature = format!("v0={}", hex::encode(mac.finalize().into_bytes()));
+        let slack_request = SlackRequest {
+            method,
+            path,
+            headers,
+            query_params,
+            multi_query_params,
+            body: slack_body,
+            raw_body,
+            gateway,
+            correlation_id: Some(correlation_id),
+            deferred: false,
+        };
 
-        if computed_signature != *signature {
-            return Err(SlackError::InvalidSignature);
+        let limit = self.app.config().max_blocks;
+        let count = slack_request.block_count();
+        if count > limit {
+            return Err(SlackError::TooManyBlocks { count, limit });
         }
 
-        Ok(())
+        Ok(slack_request)
     }
 
-    async fn handle_event_request(&self, request: SlackRequest) -> Result<SlackResponse> {
-        let client = SlackClient::new(self.app.config().get_bot_token().map(|s| s.to_string()));
-        let context = Context::new(request, client);
-        
-        // Route through the app's event router
-        if let Some(response) = self.app.router().route_request(&context.request).await? {
-            Ok(response)
-        } else {
-            Ok(SlackResponse::empty())
-        }
+    fn is_oauth_route(&self, method: &str, path: &str) -> bool {
+        let config = self.app.config();
+        method.eq_ignore_ascii_case("GET")
+            && config.is_oauth_enabled()
+            && (path == OAUTH_INSTALL_PATH || path == config.oauth_redirect_path())
     }
 
-    async fn handle_command_request(&self, request: SlackRequest) -> Result<SlackResponse> {
-        let client = SlackClient::new(self.app.config().get_bot_token().map(|s| s.to_string()));
-        let context = Context::new(request, client);
-        
-        // Route through the app's command router
-        if let Some(response) = self.app.router().route_request(&context.request).await? {
-            Ok(response)
-        } else {
-            Ok(SlackResponse::empty())
-        }
+    fn is_health_route(&self, method: &str, path: &str) -> bool {
+        method.eq_ignore_ascii_case("GET") && self.app.config().health_check && path == HEALTH_PATH
     }
 
-    async fn handle_interactive_request(&self, request: SlackRequest) -> Result<SlackResponse> {
-        let client = SlackClient::new(self.app.config().get_bot_token().map(|s| s.to_string()));
-        let context = Context::new(request, client);
-        
-        // Route through the app's interactive router
-        if let Some(response) = self.app.router().route_request(&context.request).await? {
-            Ok(response)
+    fn parse_body(&self, raw_body: &Bytes, headers: &HeaderMap) -> Result<SlackRequestBody> {
+        let content_type = headers.get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+
+        if self.app.config().lenient_parsing {
+            SlackRequestBody::parse_lenient(raw_body, content_type)
         } else {
-            Ok(SlackResponse::empty())
+            SlackRequestBody::parse(raw_body, content_type)
         }
     }
 
+    #[cfg(feature = "oauth")]
     async fn handle_oauth_request(&self, request: SlackRequest, oauth_req: &OAuthRequest) -> Result<SlackResponse> {
-        if let Some(oauth_settings) = self.app.oauth_settings() {
+        let localizer = self.app.localizer();
+        let translate = |key: &str, fallback: &str| {
+            localizer
+                .localize(request.locale(), key)
+                .unwrap_or_else(|| fallback.to_string())
+        };
+
+        if let Some(_oauth_settings) = self.app.oauth_settings() {
             if let Some(error) = &oauth_req.error {
                 error!("OAuth error: {}", error);
-                return Ok(SlackResponse {
-                    status_code: 400,
-                    headers: HashMap::new(),
-                    body: crate::response::SlackResponseBody::Text(crate::response::TextResponse {
-                        text: format!("OAuth error: {}", error),
-                        response_type: None,
-                        replace_original: None,
-                        delete_original: None,
-                    }),
-                });
+                let message = format!("{}: {}", translate(i18n::OAUTH_ERROR, "OAuth error"), error);
+                return Ok(SlackResponse::html(oauth_page(&message)).with_status(400).no_cache());
             }
 
-            if let (Some(code), Some(state)) = (&oauth_req.code, &oauth_req.state) {
-                // Handle OAuth completion - this would need the OAuth flow implementation
-                info!("OAuth callback received with code and state");
-                // In a real implementation, you'd complete the OAuth flow here
-                Ok(SlackResponse::text("Installation successful!"))
+            if let Some(code) = &oauth_req.code {
+                // Without a state the flow was started from Slack's side; the flow's
+                // state verification policy decides whether that is accepted
+                let state = oauth_req.state.as_deref().unwrap_or_default();
+                info!(has_state = !state.is_empty(), "OAuth callback received");
+                let result = match self.app.oauth_flow()?.verify_state_cookie(request.header("cookie"), state) {
+                    Ok(()) => self.app.complete_install(code, state).await.map(|_| ()),
+                    Err(e) => Err(e),
+                };
+                // The cookie has done its job either way
+                let response = match result {
+                    Ok(()) => {
+                        let message = translate(i18n::OAUTH_SUCCESS, "Installation successful!");
+                        SlackResponse::html(oauth_page(&message))
+                    }
+                    Err(e) => {
+                        error!("OAuth install failed: {}", e);
+                        let message = format!("{}: {}", translate(i18n::OAUTH_ERROR, "OAuth error"), e);
+                        SlackResponse::html(oauth_page(&message)).with_status(400)
+                    }
+                };
+                Ok(response.append_header("set-cookie", state_cookie::clear_cookie()).no_cache())
             } else {
-                // Start OAuth flow
                 info!("Starting OAuth flow");
-                // In a real implementation, you'd redirect to Slack's OAuth URL
-                Ok(SlackResponse::redirect("https://slack.com/oauth/v2/authorize"))
+                let flow = self.app.oauth_flow()?;
+                let (url, state) = flow.start_with_state().await?;
+                let mut response = SlackResponse::redirect(url).no_cache();
+                if let Some(cookie) = flow.state_cookie(&state)? {
+                    response = response.append_header("set-cookie", cookie);
+                }
+                Ok(response)
             }
         } else {
             Ok(SlackResponse {
                 status_code: 404,
-                headers: HashMap::new(),
-                body: crate::response::SlackResponseBody::Empty,
+                headers: HeaderMap::new(),
+                body: SlackResponseBody::Empty,
             })
         }
     }
 
+    // Built without the `oauth` feature there are no install pages to serve
+    #[cfg(not(feature = "oauth"))]
+    async fn handle_oauth_request(&self, _request: SlackRequest, _oauth_req: &OAuthRequest) -> Result<SlackResponse> {
+        Ok(SlackResponse {
+            status_code: 404,
+            headers: HeaderMap::new(),
+            body: SlackResponseBody::Empty,
+        })
+    }
+
+    fn with_response_headers(&self, response: SlackResponse) -> SlackResponse {
+        let response = response.with_default_headers();
+        if self.app.config().security_headers && response.is_html() {
+            return response.with_security_headers();
+        }
+        response
+    }
+
     fn to_api_gateway_response(&self, response: SlackResponse) -> ApiGatewayProxyResponse {
+        let response = self.with_response_headers(response);
+
+        let is_base64_encoded = matches!(response.body, SlackResponseBody::Binary(_));
         let body = match response.body {
-            crate::response::SlackResponseBody::Empty => None,
+            SlackResponseBody::Empty => None,
+            SlackResponseBody::Content(content) => Some(content),
+            SlackResponseBody::Binary(bytes) => Some(BASE64_STANDARD.encode(bytes)),
             _ => Some(serde_json::to_string(&response.body).unwrap_or_default()),
         };
 
+        let mut headers = HashMap::new();
+        let mut multi_value_headers = HashMap::new();
+        for name in response.headers.keys() {
+            let values: Vec<String> = response
+                .headers
+                .get_all(name)
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+                .collect();
+
+            // API Gateway only accepts repeated headers (e.g. Set-Cookie) through multiValueHeaders
+            if values.len() > 1 {
+                multi_value_headers.insert(name.to_string(), values);
+            } else if let Some(value) = values.into_iter().next() {
+                headers.insert(name.to_string(), value);
+            }
+        }
+
         ApiGatewayProxyResponse {
             status_code: response.status_code as i32,
-            headers: response.headers,
+            headers,
+            multi_value_headers,
             body,
-            is_base64_encoded: false,
+            is_base64_encoded,
         }
     }
 }
 
+// The buffered API Gateway response has no way to stream, so collect the body up front
+#[cfg(feature = "streaming")]
+async fn buffer_stream(mut response: SlackResponse) -> SlackResponse {
+    if let SlackResponseBody::Stream(stream) = &response.body {
+        response.body = match stream.collect().await {
+            Ok(bytes) => SlackResponseBody::Binary(bytes),
+            Err(e) => {
+                tracing::error!("Failed to buffer streamed response: {}", e);
+                return SlackResponse::empty().with_status(500);
+            }
+        };
+    }
+    response
+}
+
+#[cfg(not(feature = "streaming"))]
+async fn buffer_stream(response: SlackResponse) -> SlackResponse {
+    response
+}
+
+#[cfg(feature = "oauth")]
+fn oauth_page(message: &str) -> String {
+    let message = escape_html(message);
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{0}</title></head><body><p>{0}</p></body></html>",
+        message
+    )
+}
+
+// multiValueHeaders carries every header including repeated ones, so it wins when present.
+// Payload format 2.0 joins repeats with commas and moves cookies to their own list.
+fn request_headers(request: &ApiGatewayProxyRequest) -> HeaderMap {
+    let mut header_map = HeaderMap::new();
+
+    match &request.multi_value_headers {
+        Some(headers) if !headers.is_empty() => {
+            for (name, values) in headers {
+                for value in values {
+                    append_header(&mut header_map, name, value);
+                }
+            }
+        }
+        _ => {
+            for (name, value) in request.headers.iter().flatten() {
+                append_header(&mut header_map, name, value);
+            }
+        }
+    }
+
+    if let Some(cookies) = request.cookies.as_ref().filter(|cookies| !cookies.is_empty()) {
+        header_map.remove(COOKIE);
+        append_header(&mut header_map, COOKIE.as_str(), &cookies.join("; "));
+    }
+
+    header_map
+}
+
+fn append_header(header_map: &mut HeaderMap, name: &str, value: &str) {
+    match (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+        (Ok(name), Ok(value)) => {
+            header_map.append(name, value);
+        }
+        _ => debug!("Dropping invalid request header {}", name),
+    }
+}
+
+// Adopted from the caller's headers, else API Gateway's own request ID, else a new one
+fn request_correlation_id(request: &ApiGatewayProxyRequest) -> String {
+    correlation::from_headers(&request_headers(request))
+        .or_else(|| {
+            request
+                .request_context
+                .as_ref()
+                .and_then(|context| context.get("requestId"))
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        })
+        .unwrap_or_else(correlation::generate)
+}
+
+// Every value of every query parameter, from whichever representation the event carries
+fn request_query(request: &ApiGatewayProxyRequest) -> HashMap<String, Vec<String>> {
+    if let Some(params) = request.multi_value_query_string_parameters.as_ref().filter(|params| !params.is_empty()) {
+        return params.clone();
+    }
+
+    if let Some(raw_query) = request.raw_query_string.as_deref().filter(|query| !query.is_empty()) {
+        let mut params: HashMap<String, Vec<String>> = HashMap::new();
+        for (name, value) in url::form_urlencoded::parse(raw_query.as_bytes()) {
+            params.entry(name.into_owned()).or_default().push(value.into_owned());
+        }
+        return params;
+    }
+
+    request
+        .query_string_parameters
+        .iter()
+        .flatten()
+        .map(|(name, value)| (name.clone(), vec![value.clone()]))
+        .collect()
+}
+
+// First value of a query parameter, so a repeated `state` cannot replace the one Slack sent
+fn query_param(query: &HashMap<String, Vec<String>>, name: &str) -> Option<String> {
+    query.get(name).and_then(|values| values.first()).cloned()
+}
+
 #[derive(Debug, Deserialize)]
 struct ApiGatewayProxyRequest {
     #[serde(rename = "httpMethod")]
-    http_method: String,
+    http_method: Option<String>,
     path: Option<String>,
+    #[serde(rename = "rawPath")]
+    raw_path: Option<String>,
     #[serde(rename = "queryStringParameters")]
     query_string_parameters: Option<HashMap<String, String>>,
+    #[serde(rename = "multiValueQueryStringParameters")]
+    multi_value_query_string_parameters: Option<HashMap<String, Vec<String>>>,
+    #[serde(rename = "rawQueryString")]
+    raw_query_string: Option<String>,
     headers: Option<HashMap<String, String>>,
+    #[serde(rename = "multiValueHeaders")]
+    multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    cookies: Option<Vec<String>>,
     body: Option<String>,
+    #[serde(rename = "requestContext")]
+    request_context: Option<Value>,
     #[serde(rename = "isBase64Encoded")]
     is_base64_encoded: Option<bool>,
 }
@@ -303,7 +548,63 @@ struct ApiGatewayProxyResponse {
     #[serde(rename = "statusCode")]
     status_code: i32,
     headers: HashMap<String, String>,
+    #[serde(rename = "multiValueHeaders", skip_serializing_if = "HashMap::is_empty")]
+    multi_value_headers: HashMap<String, Vec<String>>,
     body: Option<String>,
     #[serde(rename = "isBase64Encoded")]
     is_base64_encoded: bool,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::signature::{self, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+    use crate::testing::{CommandRequestBuilder, MockSlackClient, TEST_SIGNING_SECRET};
+    use serde_json::json;
+
+    fn handler() -> LambdaHandler {
+        let app = App::builder()
+            .token("xoxb-1")
+            .signing_secret(TEST_SIGNING_SECRET)
+            .client(MockSlackClient::new())
+            .build()
+            .unwrap();
+        LambdaHandler::new(app)
+    }
+
+    // `signed`'s headers on an HTTP API (payload format 2.0) event flagged as base64-encoded
+    fn encoded_event(signed: &SlackRequest, body: String) -> ApiGatewayProxyRequest {
+        serde_json::from_value(json!({
+            "rawPath": "/slack/events",
+            "headers": {
+                "content-type": "application/x-www-form-urlencoded",
+                TIMESTAMP_HEADER: signed.header(TIMESTAMP_HEADER),
+                SIGNATURE_HEADER: signed.header(SIGNATURE_HEADER),
+            },
+            "body": body,
+            "requestContext": { "http": { "method": "POST" } },
+            "isBase64Encoded": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn base64_bodies_are_decoded_before_verification() {
+        let signed = CommandRequestBuilder::new("/deploy").text("prod").build();
+        let event = encoded_event(&signed, BASE64_STANDARD.encode(&signed.raw_body));
+
+        let request = handler().to_slack_request(event, "correlation-1".to_string()).unwrap();
+
+        signature::verify(TEST_SIGNING_SECRET, &request).unwrap();
+        assert!(matches!(request.body, SlackRequestBody::Command(ref command) if command.text == "prod"));
+    }
+
+    #[test]
+    fn invalid_base64_bodies_are_rejected() {
+        let signed = CommandRequestBuilder::new("/deploy").build();
+        let event = encoded_event(&signed, "not base64!".to_string());
+
+        let error = handler().to_slack_request(event, "correlation-1".to_string()).unwrap_err();
+
+        assert!(matches!(error, SlackError::InvalidForm(_)));
+    }
+}
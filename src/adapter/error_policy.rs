@@ -0,0 +1,94 @@
+use crate::error::{ErrorKind, SlackError};
+use crate::response::{SlackResponse, SlackResponseBody};
+use http::HeaderMap;
+use std::sync::Arc;
+use tracing::{error, warn};
+
+pub type StatusMapper = Arc<dyn Fn(&SlackError) -> Option<u16> + Send + Sync>;
+
+#[derive(Clone)]
+pub struct ErrorPolicy {
+    mapper: Option<StatusMapper>,
+    retry_listener_errors: bool,
+}
+
+impl Default for ErrorPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErrorPolicy {
+    pub fn new() -> Self {
+        Self {
+            mapper: None,
+            retry_listener_errors: false,
+        }
+    }
+
+    // The mapper is consulted first; returning None falls back to the default mapping.
+    pub fn map_status<F>(mut self, mapper: F) -> Self
+    where
+        F: Fn(&SlackError) -> Option<u16> + Send + Sync + 'static,
+    {
+        self.mapper = Some(Arc::new(mapper));
+        self
+    }
+
+    // Answers transient listener failures with a 503 so Slack redelivers the request. Only for
+    // listeners that are safe to run twice: the redelivery carries `x-slack-retry-reason:
+    // http_error`, which the duplicate-retry filter lets through, and repeated failures can
+    // get the event subscription disabled.
+    pub fn retry_listener_errors(mut self, enabled: bool) -> Self {
+        self.retry_listener_errors = enabled;
+        self
+    }
+
+    pub fn status_code(&self, error: &SlackError) -> u16 {
+        if let Some(status) = self.mapper.as_ref().and_then(|mapper| mapper(error)) {
+            return status;
+        }
+        match error {
+            SlackError::Listener(inner) if self.retry_listener_errors && inner.is_retriable() => 503,
+            error => Self::default_status_code(error),
+        }
+    }
+
+    pub fn default_status_code(error: &SlackError) -> u16 {
+        match error {
+            // Logged and acknowledged, so Slack doesn't redeliver into the same failure
+            SlackError::Listener(_) => 200,
+            SlackError::InvalidSignature => 401,
+            SlackError::WorkspaceNotAllowed { .. } | SlackError::IpNotAllowed(_) => 403,
+            SlackError::PayloadTooLarge { .. } => 413,
+            _ => match error.kind() {
+                ErrorKind::InvalidRequest => 400,
+                ErrorKind::Fatal => 500,
+                // Raised before any listener ran (conversation lock, installation or conversation
+                // lookup), so Slack can safely redeliver once the dependency recovers
+                ErrorKind::Transient => 503,
+                // Anything else raised while handling a valid request is acknowledged with a 200,
+                // otherwise Slack keeps retrying a delivery that will fail the same way.
+                ErrorKind::Permanent => 200,
+            },
+        }
+    }
+
+    pub fn response(&self, error: &SlackError) -> SlackResponse {
+        let status_code = self.status_code(error);
+
+        if status_code < 400 {
+            error!("Error handling request (responding with {}): {}", status_code, error);
+        } else if status_code < 500 {
+            warn!("Rejecting request with {}: {}", status_code, error);
+        } else {
+            error!("Error processing request: {}", error);
+        }
+
+        SlackResponse {
+            status_code,
+            headers: HeaderMap::new(),
+            body: SlackResponseBody::Empty,
+        }
+    }
+}
@@ -1,2 +1,15 @@
+pub mod error_policy;
+pub mod queue;
+pub mod warmup;
+
 #[cfg(feature = "lambda")]
-pub mod aws_lambda;
\ No newline at end of file
+pub mod aws_lambda;
+#[cfg(feature = "lambda")]
+pub mod schedule;
+#[cfg(feature = "socket-mode")]
+pub mod socket_mode;
+#[cfg(feature = "lambda")]
+pub mod sqs;
+
+pub use error_policy::ErrorPolicy;
+pub use queue::RequestQueue;
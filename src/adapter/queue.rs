@@ -0,0 +1,16 @@
+use crate::error::Result;
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+// Where `Context::defer` hands requests that should finish outside the invocation that acked
+// them, normally the SQS queue an `SqsHandler` consumes. `body` is the JSON-serialized
+// `SlackRequest` that handler expects; send it as the message body unchanged, e.g. with
+// aws-sdk-sqs:
+//
+//     client.send_message().queue_url(&self.queue_url).message_body(body).send().await
+//
+// `testing::MemoryRequestQueue` keeps them in memory instead.
+#[async_trait]
+pub trait RequestQueue: Send + Sync + Debug {
+    async fn send(&self, body: String) -> Result<()>;
+}
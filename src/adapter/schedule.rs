@@ -0,0 +1,51 @@
+// Worker for EventBridge rules and EventBridge Scheduler schedules. Each target names the job
+// to run in its input, `{"job": "daily-digest"}`; rules that forward the event unchanged can
+// put it in the detail instead. Jobs are registered with `App::scheduled`.
+use crate::app::{lifecycle, App};
+use crate::error::{Result, SlackError};
+use lambda_runtime::{service_fn, Error as LambdaError, LambdaEvent};
+use serde_json::Value;
+use tracing::{info_span, Instrument};
+
+#[derive(Clone)]
+pub struct ScheduleHandler {
+    app: App,
+}
+
+impl ScheduleHandler {
+    pub fn new(app: App) -> Self {
+        Self { app }
+    }
+
+    pub async fn run(self) -> std::result::Result<(), LambdaError> {
+        self.app.run_init_hooks().await?;
+        lifecycle::spawn_shutdown_listener(self.app.clone());
+
+        lambda_runtime::run(service_fn(move |event: LambdaEvent<Value>| {
+            let handler = self.clone();
+            async move { handler.handle(event.payload).await.map_err(LambdaError::from) }
+        }))
+        .await
+    }
+
+    // Failures are returned so the invocation fails and EventBridge's retry policy applies
+    pub async fn handle(&self, event: Value) -> Result<()> {
+        let job = job_name(&event).ok_or_else(|| SlackError::UnexpectedPayload {
+            expected: "scheduled event naming a job".to_string(),
+            found: event.to_string(),
+        })?;
+
+        let span = info_span!("scheduled_job", job = %job);
+        let result = self.app.run_scheduled(&job).instrument(span).await;
+        self.app.flush().await;
+        result
+    }
+}
+
+fn job_name(event: &Value) -> Option<String> {
+    event
+        .get("job")
+        .or_else(|| event.pointer("/detail/job"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
@@ -0,0 +1,347 @@
+// Socket Mode: Slack pushes events, slash commands and interactions over a WebSocket opened
+// with the app-level token (xapp-...), so the app needs no public URL. Every envelope is
+// acked over the same socket; for commands and interactions the listener's response rides
+// along as the ack payload, the way it would be the HTTP response body.
+//
+// Requests arrive authenticated by the socket itself and skip signature verification.
+//
+// Connections are replaced as Slack asks: a `warning` or `refresh_requested` disconnect opens
+// a new socket at once while the old one drains, lost sockets come back after a jittered
+// backoff, and `link_disabled` stops the adapter.
+use crate::app::{lifecycle, App};
+use crate::error::{Result, SlackError};
+use crate::request::{
+    CommandRequest, SlackRequest, SlackRequestBody, UnknownRequest, RETRY_NUM_HEADER, RETRY_REASON_HEADER,
+};
+use crate::response::SlackResponseBody;
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use http::{HeaderMap, HeaderValue};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::Instant;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tracing::{debug, error, info, info_span, warn, Instrument};
+
+// Pings keep idle connections alive through proxies; a socket silent for longer than the
+// timeout, pongs included, is treated as dead
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+const PING_TIMEOUT: Duration = Duration::from_secs(30);
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(500);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+// How a connection ended, which decides when the next one opens
+#[derive(Debug)]
+enum Disconnect {
+    // Slack is about to close the socket (`warning`) or asked for a new one
+    // (`refresh_requested`); the replacement opens straight away
+    Refresh,
+    Lost(String),
+    // Socket Mode was turned off in the app settings (`link_disabled`)
+    Disabled,
+}
+
+#[derive(Clone)]
+pub struct SocketModeHandler {
+    app: App,
+}
+
+impl SocketModeHandler {
+    pub fn new(app: App) -> Self {
+        Self { app }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        self.app.run_init_hooks().await?;
+        lifecycle::spawn_shutdown_listener(self.app.clone());
+        self.serve().await
+    }
+
+    // Keeps `socket_mode_connections` connections open until Slack disables Socket Mode or the
+    // app token is rejected; `App::run` uses this directly so init hooks run once
+    pub(crate) async fn serve(self) -> Result<()> {
+        let connections = self.app.config().socket_mode_connections.max(1);
+        let loops = (0..connections).map(|slot| self.clone().connection_loop(slot));
+        futures::future::try_join_all(loops).await?;
+        Ok(())
+    }
+
+    async fn connection_loop(self, slot: usize) -> Result<()> {
+        let mut failures = 0;
+        loop {
+            // Each URL carries a single-use ticket, so every attempt asks for a fresh one
+            let outcome = match self.open_connection().await {
+                Ok(url) => self.connect(&url).await,
+                Err(e) if e.is_retriable() => Err(e),
+                Err(e) => return Err(e),
+            };
+
+            match outcome {
+                Ok(Disconnect::Refresh) => {
+                    failures = 0;
+                    continue;
+                }
+                Ok(Disconnect::Disabled) => {
+                    return Err(SlackError::Config("Socket Mode is disabled for this app".to_string()));
+                }
+                Ok(Disconnect::Lost(reason)) => {
+                    warn!(slot, "Socket Mode connection lost: {}", reason);
+                    failures = 0;
+                }
+                Err(e) => {
+                    warn!(slot, "Socket Mode connection failed: {}", e);
+                    failures += 1;
+                }
+            }
+            tokio::time::sleep(reconnect_delay(failures)).await;
+        }
+    }
+
+    // apps.connections.open hands out a single-use WebSocket URL
+    async fn open_connection(&self) -> Result<String> {
+        let app_token = self
+            .app
+            .config()
+            .app_token
+            .clone()
+            .ok_or_else(|| SlackError::Config("Socket Mode requires an app-level token".to_string()))?;
+        let response = self
+            .app
+            .client()
+            .with_token(app_token.into_exposed())
+            .call("apps.connections.open", json!({}))
+            .await?;
+        response
+            .get("url")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| SlackError::UnexpectedPayload {
+                expected: "apps.connections.open response with a url".to_string(),
+                found: response.to_string(),
+            })
+    }
+
+    async fn connect(&self, url: &str) -> Result<Disconnect> {
+        let (socket, _) = tokio_tungstenite::connect_async(url)
+            .await
+            .map_err(|e| SlackError::Internal(format!("Socket Mode connect failed: {}", e)))?;
+        let (mut sink, mut stream) = socket.split();
+        info!("Socket Mode connected");
+
+        // Envelopes are handled concurrently; acks and pings funnel back through one writer,
+        // which stops once the socket and every handler holding a sender are done
+        let (outgoing, mut pending) = mpsc::unbounded_channel::<Message>();
+        tokio::spawn(async move {
+            while let Some(message) = pending.recv().await {
+                if let Err(e) = sink.send(message).await {
+                    debug!("Socket Mode write failed: {}", e);
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        let disconnect = self.read(&mut stream, &outgoing).await;
+        if let Disconnect::Refresh = disconnect {
+            // A warned socket stays open for a few more seconds; keep serving and acking on it
+            // while the replacement connects
+            let handler = self.clone();
+            tokio::spawn(async move {
+                let disconnect = handler.read(&mut stream, &outgoing).await;
+                debug!("Socket Mode connection retired: {:?}", disconnect);
+            });
+        }
+        Ok(disconnect)
+    }
+
+    async fn read<S>(&self, stream: &mut S, outgoing: &mpsc::UnboundedSender<Message>) -> Disconnect
+    where
+        S: futures::Stream<Item = std::result::Result<Message, WsError>> + Unpin,
+    {
+        let mut ping = tokio::time::interval_at(Instant::now() + PING_INTERVAL, PING_INTERVAL);
+        let mut last_seen = Instant::now();
+
+        loop {
+            let message = tokio::select! {
+                message = stream.next() => message,
+                _ = ping.tick() => {
+                    if last_seen.elapsed() > PING_TIMEOUT {
+                        return Disconnect::Lost(format!("nothing received for {:?}", PING_TIMEOUT));
+                    }
+                    let _ = outgoing.send(Message::Ping(Vec::new()));
+                    continue;
+                }
+            };
+            let message = match message {
+                Some(Ok(message)) => message,
+                Some(Err(e)) => return Disconnect::Lost(e.to_string()),
+                None => return Disconnect::Lost("stream ended".to_string()),
+            };
+            last_seen = Instant::now();
+
+            let text = match message {
+                Message::Text(text) => text,
+                // Answered here rather than left to tungstenite, whose queued pong only goes
+                // out with the next write
+                Message::Ping(data) => {
+                    let _ = outgoing.send(Message::Pong(data));
+                    continue;
+                }
+                Message::Close(frame) => return Disconnect::Lost(format!("closed by Slack: {:?}", frame)),
+                _ => continue,
+            };
+
+            let envelope: Value = match serde_json::from_str(&text) {
+                Ok(envelope) => envelope,
+                Err(e) => {
+                    warn!("Ignoring malformed Socket Mode message: {}", e);
+                    continue;
+                }
+            };
+            match envelope.get("type").and_then(|v| v.as_str()) {
+                Some("hello") => debug!("Socket Mode hello: {}", envelope),
+                Some("disconnect") => {
+                    info!("Socket Mode disconnect requested: {}", envelope);
+                    return match envelope.get("reason").and_then(|v| v.as_str()) {
+                        Some("link_disabled") => Disconnect::Disabled,
+                        Some(reason) => {
+                            if reason != "warning" && reason != "refresh_requested" {
+                                debug!("Unknown Socket Mode disconnect reason {}", reason);
+                            }
+                            Disconnect::Refresh
+                        }
+                        None => Disconnect::Refresh,
+                    };
+                }
+                _ => {
+                    let handler = self.clone();
+                    let outgoing = outgoing.clone();
+                    tokio::spawn(async move {
+                        if let Some(ack) = handler.handle_envelope(envelope).await {
+                            let _ = outgoing.send(Message::Text(ack.to_string()));
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    // The ack for one envelope, or None for messages that take none; exposed for tests
+    pub async fn handle_envelope(&self, envelope: Value) -> Option<Value> {
+        let envelope_id = envelope.get("envelope_id").and_then(|v| v.as_str())?.to_string();
+        let envelope_type = envelope.get("type").and_then(|v| v.as_str()).unwrap_or_default();
+        let payload = envelope.get("payload").cloned().unwrap_or(Value::Null);
+        let span = info_span!("socket_mode_envelope", envelope_id = %envelope_id, envelope_type);
+
+        let request = match request_from_envelope(envelope_type, payload) {
+            Ok(request) => with_retry_headers(request, &envelope),
+            Err(e) => {
+                error!("Unreadable Socket Mode envelope {}: {}", envelope_id, e);
+                return Some(json!({ "envelope_id": envelope_id }));
+            }
+        };
+
+        // Events carry no response, so Slack is answered before the listeners run
+        if envelope_type == "events_api" {
+            let app = self.app.clone();
+            tokio::spawn(
+                async move {
+                    if let Err(e) = app.dispatch(request).await {
+                        error!("Socket Mode event failed: {}", e);
+                    }
+                    app.flush().await;
+                }
+                .instrument(span),
+            );
+            return Some(json!({ "envelope_id": envelope_id }));
+        }
+
+        let result = self.app.dispatch(request).instrument(span).await;
+        self.app.flush().await;
+        let mut ack = json!({ "envelope_id": envelope_id });
+        match result {
+            Ok(response) => {
+                let accepts_payload = envelope
+                    .get("accepts_response_payload")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                if accepts_payload && !matches!(response.body, SlackResponseBody::Empty) {
+                    ack["payload"] = serde_json::to_value(&response.body).unwrap_or(Value::Null);
+                }
+            }
+            // Slack shows the user its generic failure message once the ack carries nothing
+            Err(e) => error!("Socket Mode request {} failed: {}", envelope_id, e),
+        }
+        Some(ack)
+    }
+}
+
+// Full jitter over an exponential ceiling, so connections dropped together don't all come
+// back at the same moment
+fn reconnect_delay(failures: u32) -> Duration {
+    let ceiling = RECONNECT_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(failures))
+        .min(RECONNECT_MAX_DELAY);
+    // No rand dependency; the clock's low bits are random enough to spread reconnects
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    ceiling.mul_f64(f64::from(nanos % 1000) / 1000.0)
+}
+
+// Socket Mode payloads are the JSON form of what HTTP delivers, commands included
+pub fn request_from_envelope(envelope_type: &str, payload: Value) -> Result<SlackRequest> {
+    let raw_body = Bytes::from(serde_json::to_vec(&payload)?);
+    let body = match envelope_type {
+        "events_api" => SlackRequestBody::Event(serde_json::from_value(payload)?),
+        "interactive" => SlackRequestBody::Interactive(serde_json::from_value(payload)?),
+        "slash_commands" => SlackRequestBody::Command(command_from_payload(&payload)),
+        _ => SlackRequestBody::Unknown(UnknownRequest { payload }),
+    };
+
+    Ok(SlackRequest {
+        method: "POST".to_string(),
+        path: "/slack/events".to_string(),
+        headers: HeaderMap::new(),
+        query_params: HashMap::new(),
+        multi_query_params: HashMap::new(),
+        body,
+        raw_body,
+        gateway: None,
+        correlation_id: None,
+        deferred: false,
+    })
+}
+
+fn with_retry_headers(mut request: SlackRequest, envelope: &Value) -> SlackRequest {
+    let attempt = envelope.get("retry_attempt").and_then(|v| v.as_u64()).unwrap_or(0);
+    if attempt > 0 {
+        request.headers.insert(RETRY_NUM_HEADER, HeaderValue::from(attempt));
+        let reason = envelope.get("retry_reason").and_then(|v| v.as_str()).unwrap_or_default();
+        if let Ok(reason) = HeaderValue::from_str(reason) {
+            request.headers.insert(RETRY_REASON_HEADER, reason);
+        }
+    }
+    request
+}
+
+fn command_from_payload(payload: &Value) -> CommandRequest {
+    let field = |name: &str| payload.get(name).and_then(|v| v.as_str()).map(str::to_string);
+    CommandRequest {
+        token: field("token").unwrap_or_default(),
+        team_id: field("team_id").unwrap_or_default(),
+        team_domain: field("team_domain").unwrap_or_default(),
+        channel_id: field("channel_id").unwrap_or_default(),
+        channel_name: field("channel_name").unwrap_or_default(),
+        user_id: field("user_id").unwrap_or_default(),
+        user_name: field("user_name").unwrap_or_default(),
+        command: field("command").unwrap_or_default(),
+        text: field("text").unwrap_or_default(),
+        response_url: field("response_url").unwrap_or_default(),
+        trigger_id: field("trigger_id").unwrap_or_default(),
+        enterprise_id: field("enterprise_id"),
+    }
+}
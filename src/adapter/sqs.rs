@@ -0,0 +1,187 @@
+// Workers for requests deferred onto an SQS queue by `context.defer()` (through the app's
+// `request_queue`): each message body is a JSON-serialized `SlackRequest` that was verified
+// before it was enqueued. Configure the event source mapping with `ReportBatchItemFailures`
+// so only the failed records are redelivered.
+use crate::app::{lifecycle, App};
+use crate::client::PostMessageRequest;
+use crate::error::Result;
+use crate::request::SlackRequest;
+use lambda_runtime::{service_fn, Error as LambdaError, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{error, info_span, warn, Instrument};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SqsEvent {
+    #[serde(rename = "Records", default)]
+    pub records: Vec<SqsMessage>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SqsMessage {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+    #[serde(rename = "eventSourceARN")]
+    pub event_source_arn: Option<String>,
+}
+
+impl SqsMessage {
+    pub fn receive_count(&self) -> u32 {
+        self.attributes
+            .get("ApproximateReceiveCount")
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(1)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SqsBatchResponse {
+    #[serde(rename = "batchItemFailures")]
+    pub batch_item_failures: Vec<BatchItemFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchItemFailure {
+    #[serde(rename = "itemIdentifier")]
+    pub item_identifier: String,
+}
+
+#[derive(Clone)]
+pub struct SqsHandler {
+    app: App,
+}
+
+impl SqsHandler {
+    pub fn new(app: App) -> Self {
+        Self { app }
+    }
+
+    pub async fn run(self) -> std::result::Result<(), LambdaError> {
+        self.app.run_init_hooks().await?;
+        lifecycle::spawn_shutdown_listener(self.app.clone());
+
+        lambda_runtime::run(service_fn(move |event: LambdaEvent<SqsEvent>| {
+            let handler = self.clone();
+            async move { Ok::<_, LambdaError>(handler.handle_batch(event.payload).await) }
+        }))
+        .await
+    }
+
+    pub async fn handle_batch(&self, event: SqsEvent) -> SqsBatchResponse {
+        let mut response = SqsBatchResponse::default();
+
+        for message in event.records {
+            let span = info_span!(
+                "sqs_message",
+                message_id = %message.message_id,
+                correlation_id = tracing::field::Empty
+            );
+            let result = self.handle_message(&message).instrument(span).await;
+
+            match result {
+                Ok(()) => {}
+                // Transient failures are handed back to SQS for redelivery (and eventually the DLQ);
+                // anything else would fail the same way every time, so it is reported once and dropped
+                Err(e) if e.is_retriable() => {
+                    warn!("Deferred request {} failed, leaving it for redelivery: {}", message.message_id, e);
+                    response.batch_item_failures.push(BatchItemFailure {
+                        item_identifier: message.message_id.clone(),
+                    });
+                }
+                Err(e) => {
+                    error!("Deferred request {} failed permanently: {}", message.message_id, e);
+                    notify_failure(&self.app, &message, &e.to_string()).await;
+                }
+            }
+        }
+
+        self.app.flush().await;
+        response
+    }
+
+    async fn handle_message(&self, message: &SqsMessage) -> Result<()> {
+        let mut request: SlackRequest = serde_json::from_str(&message.body)?;
+        // Also for bodies enqueued by hand: run to completion before the message is deleted
+        request.deferred = true;
+        // Carried over from the request that deferred this one
+        if let Some(correlation_id) = &request.correlation_id {
+            tracing::Span::current().record("correlation_id", correlation_id.as_str());
+        }
+        self.app.dispatch(request).await.map(|_| ())
+    }
+}
+
+// Consumes the dead-letter queue behind an `SqsHandler` queue: every record there exhausted
+// its retries, so report it to the configured channel and let SQS delete it
+#[derive(Clone)]
+pub struct DeadLetterHandler {
+    app: App,
+}
+
+impl DeadLetterHandler {
+    pub fn new(app: App) -> Self {
+        Self { app }
+    }
+
+    pub async fn run(self) -> std::result::Result<(), LambdaError> {
+        self.app.run_init_hooks().await?;
+        lifecycle::spawn_shutdown_listener(self.app.clone());
+
+        lambda_runtime::run(service_fn(move |event: LambdaEvent<SqsEvent>| {
+            let handler = self.clone();
+            async move { Ok::<_, LambdaError>(handler.handle_batch(event.payload).await) }
+        }))
+        .await
+    }
+
+    pub async fn handle_batch(&self, event: SqsEvent) -> SqsBatchResponse {
+        for message in event.records {
+            let reason = format!("gave up after {} attempts", message.receive_count());
+            error!("Dead-lettered request {}: {}", message.message_id, reason);
+            notify_failure(&self.app, &message, &reason).await;
+        }
+
+        self.app.flush().await;
+        SqsBatchResponse::default()
+    }
+}
+
+async fn notify_failure(app: &App, message: &SqsMessage, reason: &str) {
+    app.metrics().counter("DeadLetteredRequests", 1, &[]);
+
+    let channel = match &app.config().dead_letter_channel {
+        Some(channel) => channel.clone(),
+        None => return,
+    };
+
+    let summary = serde_json::from_str::<SlackRequest>(&message.body)
+        .map(|request| {
+            format!(
+                "{} `{}` from team {}",
+                request.body.kind(),
+                request.routing_key().unwrap_or("unknown"),
+                request.team_id().unwrap_or("unknown")
+            )
+        })
+        .unwrap_or_else(|_| "an unreadable message".to_string());
+
+    let notification = PostMessageRequest {
+        channel,
+        text: Some(format!(
+            ":warning: Failed to process {} (SQS message `{}`): {}",
+            summary, message.message_id, reason
+        )),
+        blocks: None,
+        thread_ts: None,
+        metadata: None,
+    };
+
+    // Nothing more can be done if the notification itself fails
+    if let Err(e) = app.client().post_message(&notification).await {
+        error!("Failed to post dead-letter notification: {}", e);
+    }
+}
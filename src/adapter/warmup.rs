@@ -0,0 +1,26 @@
+use serde_json::Value;
+
+// Recognizes keep-warm invocations so they can be answered without touching Slack parsing:
+// EventBridge/CloudWatch scheduled events, serverless-plugin-warmup, lambda-warmer's
+// `{"warmer": true}` payload, and HTTP pings carrying the configured warmup header.
+pub fn is_warmup_event(event: &Value, warmup_header: Option<&str>) -> bool {
+    let source = event.get("source").and_then(|v| v.as_str());
+    let detail_type = event.get("detail-type").and_then(|v| v.as_str());
+
+    if source == Some("aws.events") && detail_type == Some("Scheduled Event") {
+        return true;
+    }
+
+    if source == Some("serverless-plugin-warmup") {
+        return true;
+    }
+
+    if event.get("warmer").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return true;
+    }
+
+    match (warmup_header, event.get("headers").and_then(|v| v.as_object())) {
+        (Some(header), Some(headers)) => headers.keys().any(|key| key.eq_ignore_ascii_case(header)),
+        _ => false,
+    }
+}
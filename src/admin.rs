@@ -0,0 +1,219 @@
+// Built-in `/slackapp-admin` command: an operator console over the stores the app already
+// has. Register the command in the app's manifest and pass the user IDs allowed to run it;
+// everyone else gets a refusal, and an empty allowlist refuses everyone.
+//
+//   /slackapp-admin installations [cursor]   one page of installations
+//   /slackapp-admin settings [team_id]       stored team settings
+//   /slackapp-admin auth-test                auth.test with this workspace's token
+//   /slackapp-admin errors [team_id]         recent failed requests from the audit sink
+use crate::app::App;
+use crate::audit::{AuditOutcome, AuditSink};
+use crate::context::Context;
+use crate::error::Result;
+use crate::oauth::InstallationStore;
+use crate::response::SlackResponse;
+use crate::settings::TeamSettingsStore;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+use tracing::info;
+
+pub const ADMIN_COMMAND: &str = "/slackapp-admin";
+
+const INSTALLATIONS_PAGE: usize = 20;
+// Audit records scanned for failures; most traffic succeeds, so read well past what is shown
+const AUDIT_SCAN: usize = 100;
+const ERRORS_SHOWN: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct AdminCommand {
+    command: String,
+    allowed_users: Vec<String>,
+}
+
+impl AdminCommand {
+    pub fn new<I, S>(allowed_users: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            command: ADMIN_COMMAND.to_string(),
+            allowed_users: allowed_users.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    // For apps that already use the default name or run several deployments in one workspace
+    pub fn with_command<S: Into<String>>(mut self, command: S) -> Self {
+        self.command = command.into();
+        self
+    }
+
+    pub fn is_allowed(&self, user_id: &str) -> bool {
+        self.allowed_users.iter().any(|allowed| allowed == user_id)
+    }
+}
+
+// The stores the console reads, captured when the command is registered
+#[derive(Clone)]
+struct Console {
+    admin: AdminCommand,
+    installation_store: Option<Arc<dyn InstallationStore>>,
+    team_settings_store: Option<Arc<dyn TeamSettingsStore>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+}
+
+impl App {
+    // Registers the admin console; call after the builder has configured the stores
+    pub fn admin_command(&mut self, admin: AdminCommand) -> &mut Self {
+        let command = admin.command.clone();
+        let console = Console {
+            admin,
+            installation_store: self
+                .oauth_settings()
+                .and_then(|settings| settings.installation_store.clone()),
+            team_settings_store: self.team_settings_store(),
+            audit_sink: self.audit_sink(),
+        };
+
+        self.command(command, move |context| {
+            let console = console.clone();
+            async move { console.run(context).await }
+        })
+    }
+}
+
+impl Console {
+    async fn run(&self, context: Context) -> Result<SlackResponse> {
+        let command = context.command()?;
+        if !self.admin.is_allowed(&command.user_id) {
+            return context
+                .ack
+                .ephemeral("You are not allowed to run admin commands.")
+                .await;
+        }
+
+        let mut args = command.text.split_whitespace();
+        let subcommand = args.next().unwrap_or("help");
+        let arg = args.next();
+        info!(user_id = %command.user_id, subcommand, "Admin command");
+
+        // Team subcommands default to the workspace the command was run in
+        let (team_id, enterprise_id) = match arg {
+            Some(team_id) => (Some(team_id), None),
+            None => (context.request.team_id(), context.request.enterprise_id()),
+        };
+        let text = match subcommand {
+            "installations" => self.installations(arg).await,
+            "settings" => self.settings(team_id, enterprise_id).await,
+            "auth-test" => auth_test(&context).await,
+            "errors" => self.errors(team_id).await,
+            _ => Ok(help(&self.admin.command)),
+        };
+        // Failures are reported to the operator rather than to Slack as a failed command
+        let text = text.unwrap_or_else(|e| format!("Failed: {}", e));
+        context.ack.ephemeral(text).await
+    }
+
+    async fn installations(&self, cursor: Option<&str>) -> Result<String> {
+        let Some(store) = &self.installation_store else {
+            return Ok("No installation store is configured.".to_string());
+        };
+
+        let page = store.list_page(cursor, INSTALLATIONS_PAGE).await?;
+        if page.installations.is_empty() {
+            return Ok("No installations.".to_string());
+        }
+
+        let mut text = String::new();
+        for installation in &page.installations {
+            let _ = writeln!(
+                text,
+                "• `{}`{} installed {}, bot token {}",
+                installation.team_id,
+                installation
+                    .enterprise_id
+                    .as_deref()
+                    .map(|enterprise_id| format!(" (enterprise `{}`)", enterprise_id))
+                    .unwrap_or_default(),
+                installation.installed_at.format("%Y-%m-%d"),
+                if installation.bot_token.is_some() { "present" } else { "missing" },
+            );
+        }
+        if let Some(next_cursor) = page.next_cursor {
+            let _ = write!(text, "Next page: `{} installations {}`", self.admin.command, next_cursor);
+        }
+        Ok(text)
+    }
+
+    async fn settings(&self, team_id: Option<&str>, enterprise_id: Option<&str>) -> Result<String> {
+        let Some(store) = &self.team_settings_store else {
+            return Ok("No team settings store is configured.".to_string());
+        };
+        let Some(team_id) = team_id else {
+            return Ok("Pass a team ID.".to_string());
+        };
+
+        match store.find(team_id, enterprise_id).await? {
+            Some(settings) => Ok(format!("```{}```", serde_json::to_string_pretty(&settings)?)),
+            None => Ok(format!("No settings stored for `{}`.", team_id)),
+        }
+    }
+
+    async fn errors(&self, team_id: Option<&str>) -> Result<String> {
+        let Some(sink) = &self.audit_sink else {
+            return Ok("No audit sink is configured.".to_string());
+        };
+
+        let failures: Vec<_> = sink
+            .recent(team_id, AUDIT_SCAN)
+            .await?
+            .into_iter()
+            .filter(|record| record.is_failure())
+            .take(ERRORS_SHOWN)
+            .collect();
+        if failures.is_empty() {
+            return Ok(format!("No failures in the last {} requests.", AUDIT_SCAN));
+        }
+
+        let mut text = String::new();
+        for record in failures {
+            let error = match &record.outcome {
+                AuditOutcome::Failed { error, .. } => error.as_str(),
+                AuditOutcome::Handled { .. } => "",
+            };
+            let _ = writeln!(
+                text,
+                "• {} `{}` {}: {}",
+                record.received_at.format("%Y-%m-%d %H:%M:%S"),
+                record.routing_key.as_deref().unwrap_or(&record.kind),
+                record.request_id.as_deref().unwrap_or(&record.id),
+                error,
+            );
+        }
+        Ok(text)
+    }
+}
+
+// Uses the request's client, so multi-workspace apps test the installation's own token
+async fn auth_test(context: &Context) -> Result<String> {
+    let response = context.client.call("auth.test", json!({})).await?;
+    let field = |name: &str| response.get(name).and_then(|v| v.as_str()).unwrap_or("-").to_string();
+    Ok(format!(
+        "auth.test ok: team `{}` ({}), bot user `{}`, bot ID `{}`",
+        field("team_id"),
+        field("team"),
+        field("user_id"),
+        field("bot_id"),
+    ))
+}
+
+fn help(command: &str) -> String {
+    format!(
+        "`{0} installations [cursor]` list installations\n\
+         `{0} settings [team_id]` show team settings\n\
+         `{0} auth-test` re-run auth.test\n\
+         `{0} errors [team_id]` recent failed requests",
+        command
+    )
+}
@@ -1,12 +1,61 @@
-use crate::app::{App, AppConfig};
+use crate::adapter::{ErrorPolicy, RequestQueue};
+use crate::audit::AuditSink;
+use crate::app::lifecycle::{lifecycle_hook, LifecycleHook};
+use crate::app::install::{install_hook, uninstall_hook, InstallHook, UninstallHook};
+use crate::app::rate_limit::{rate_limit_hook, RateLimitHook};
+use crate::app::{App, AppConfig, AppState, HomeStateProvider};
+#[cfg(not(feature = "http-client"))]
+use crate::client::UnconfiguredClient;
+use crate::client::policy::content_policy;
+use crate::client::{ContentPolicy, PolicyClient, RateLimiter, SlackApi};
+#[cfg(feature = "http-client")]
+use crate::client::SlackClient;
+use crate::listener::{ExecutionMode, ResponsePolicy, TimeoutBudget};
+use crate::metrics::Metrics;
+use crate::conversation::{ConversationLock, ConversationStore};
 use crate::error::{Result, SlackError};
-use crate::oauth::OAuthSettings;
+use crate::i18n::Localizer;
+use crate::oauth::{Installation, OAuthSettings};
+use crate::request::payloads::{AppRateLimited, Uninstall};
+use crate::secret::SecretString;
+use crate::settings::TeamSettingsStore;
 use std::env;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct AppBuilder {
     config: AppConfig,
     oauth_settings: Option<OAuthSettings>,
+    error_policy: Option<ErrorPolicy>,
+    conversation_store: Option<Arc<dyn ConversationStore>>,
+    conversation_lock: Option<Arc<dyn ConversationLock>>,
+    metrics: Option<Arc<dyn Metrics>>,
+    client: Option<Arc<dyn SlackApi>>,
+    init_hooks: Vec<LifecycleHook>,
+    restore_hooks: Vec<LifecycleHook>,
+    rate_limit_hooks: Vec<RateLimitHook>,
+    install_hooks: Vec<InstallHook>,
+    uninstall_hooks: Vec<UninstallHook>,
+    flush_hooks: Vec<LifecycleHook>,
+    shutdown_hooks: Vec<LifecycleHook>,
+    execution_mode: ExecutionMode,
+    response_policy: ResponsePolicy,
+    timeout_budget: TimeoutBudget,
+    state: AppState,
+    localizer: Option<Arc<dyn Localizer>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    request_queue: Option<Arc<dyn RequestQueue>>,
+    rate_limiter: Option<Arc<dyn RateLimiter>>,
+    team_settings_store: Option<Arc<dyn TeamSettingsStore>>,
+    home_state_provider: Option<Arc<dyn HomeStateProvider>>,
+    content_policies: Vec<ContentPolicy>,
+}
+
+impl Default for AppBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AppBuilder {
@@ -14,10 +63,33 @@ impl AppBuilder {
         Self {
             config: AppConfig::new(String::new()),
             oauth_settings: None,
+            error_policy: None,
+            conversation_store: None,
+            conversation_lock: None,
+            metrics: None,
+            client: None,
+            init_hooks: Vec::new(),
+            restore_hooks: Vec::new(),
+            rate_limit_hooks: Vec::new(),
+            install_hooks: Vec::new(),
+            uninstall_hooks: Vec::new(),
+            flush_hooks: Vec::new(),
+            shutdown_hooks: Vec::new(),
+            execution_mode: ExecutionMode::default(),
+            response_policy: ResponsePolicy::default(),
+            timeout_budget: TimeoutBudget::default(),
+            state: AppState::new(),
+            localizer: None,
+            audit_sink: None,
+            request_queue: None,
+            rate_limiter: None,
+            team_settings_store: None,
+            home_state_provider: None,
+            content_policies: Vec::new(),
         }
     }
 
-    pub fn token<S: Into<String>>(mut self, token: S) -> Self {
+    pub fn token<S: Into<SecretString>>(mut self, token: S) -> Self {
         self.config.bot_token = Some(token.into());
         self
     }
@@ -25,11 +97,45 @@ impl AppBuilder {
     pub fn token_from_env<S: AsRef<str>>(mut self, env_var: S) -> Result<Self> {
         let token = env::var(env_var.as_ref())
             .map_err(|_| SlackError::MissingEnvVar(env_var.as_ref().to_string()))?;
-        self.config.bot_token = Some(token);
+        self.config.bot_token = Some(token.into());
+        Ok(self)
+    }
+
+    // App-level token (xapp-...) with `connections:write`, used to open Socket Mode connections
+    pub fn app_token<S: Into<SecretString>>(mut self, token: S) -> Self {
+        self.config.app_token = Some(token.into());
+        self
+    }
+
+    pub fn app_token_from_env<S: AsRef<str>>(mut self, env_var: S) -> Result<Self> {
+        let token = env::var(env_var.as_ref())
+            .map_err(|_| SlackError::MissingEnvVar(env_var.as_ref().to_string()))?;
+        self.config.app_token = Some(token.into());
         Ok(self)
     }
 
-    pub fn signing_secret<S: Into<String>>(mut self, secret: S) -> Self {
+    // Receive events, commands and interactions over Socket Mode from `App::run`. Combine with
+    // `http_mode(true)` to keep serving HTTP routes such as OAuth from the same app, e.g.
+    // under `cargo lambda watch` during development.
+    pub fn socket_mode(mut self, enabled: bool) -> Self {
+        self.config.socket_mode = enabled;
+        self
+    }
+
+    // Open several Socket Mode connections (up to 10) for high event volumes; Slack delivers
+    // each envelope on one of them, and one reconnecting doesn't leave the app without a socket
+    pub fn socket_mode_connections(mut self, connections: usize) -> Self {
+        self.config.socket_mode_connections = connections;
+        self
+    }
+
+    // On by default; turn off for Socket Mode-only apps, which then need no signing secret
+    pub fn http_mode(mut self, enabled: bool) -> Self {
+        self.config.http_mode = enabled;
+        self
+    }
+
+    pub fn signing_secret<S: Into<SecretString>>(mut self, secret: S) -> Self {
         self.config.signing_secret = secret.into();
         self
     }
@@ -37,7 +143,7 @@ impl AppBuilder {
     pub fn signing_secret_from_env<S: AsRef<str>>(mut self, env_var: S) -> Result<Self> {
         let secret = env::var(env_var.as_ref())
             .map_err(|_| SlackError::MissingEnvVar(env_var.as_ref().to_string()))?;
-        self.config.signing_secret = secret;
+        self.config.signing_secret = secret.into();
         Ok(self)
     }
 
@@ -53,7 +159,7 @@ impl AppBuilder {
         Ok(self)
     }
 
-    pub fn client_secret<S: Into<String>>(mut self, client_secret: S) -> Self {
+    pub fn client_secret<S: Into<SecretString>>(mut self, client_secret: S) -> Self {
         self.config.client_secret = Some(client_secret.into());
         self
     }
@@ -61,7 +167,7 @@ impl AppBuilder {
     pub fn client_secret_from_env<S: AsRef<str>>(mut self, env_var: S) -> Result<Self> {
         let client_secret = env::var(env_var.as_ref())
             .map_err(|_| SlackError::MissingEnvVar(env_var.as_ref().to_string()))?;
-        self.config.client_secret = Some(client_secret);
+        self.config.client_secret = Some(client_secret.into());
         Ok(self)
     }
 
@@ -74,6 +180,26 @@ impl AppBuilder {
         self
     }
 
+    // For internal apps: only accept requests from these workspaces, answering anything else
+    // with a 403 before it reaches middleware or listeners
+    pub fn allowed_team_ids<I>(mut self, team_ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.config.allowed_team_ids = team_ids.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
+    pub fn allowed_enterprise_ids<I>(mut self, enterprise_ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.config.allowed_enterprise_ids = enterprise_ids.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
     pub fn user_scopes<I>(mut self, scopes: I) -> Self
     where
         I: IntoIterator,
@@ -88,6 +214,83 @@ impl AppBuilder {
         self
     }
 
+    pub fn warmup_header<S: Into<String>>(mut self, header: S) -> Self {
+        self.config.warmup_header = Some(header.into());
+        self
+    }
+
+    pub fn max_body_bytes(mut self, limit: usize) -> Self {
+        self.config.max_body_bytes = limit;
+        self
+    }
+
+    pub fn max_blocks(mut self, limit: usize) -> Self {
+        self.config.max_blocks = limit;
+        self
+    }
+
+    // Keep payloads that don't match a known shape as `SlackRequestBody::Unknown`
+    // instead of rejecting or ignoring them; pair with `App::any` to receive them
+    pub fn lenient_parsing(mut self, enabled: bool) -> Self {
+        self.config.lenient_parsing = enabled;
+        self
+    }
+
+    // Adds nosniff, frame and CSP headers to every HTML response, including the OAuth pages
+    pub fn security_headers(mut self, enabled: bool) -> Self {
+        self.config.security_headers = enabled;
+        self
+    }
+
+    // Logs each request, the listeners it matched, middleware that stopped it and the response
+    // at debug level, with secrets redacted. Meant for development, not production traffic.
+    pub fn debug_mode(mut self, enabled: bool) -> Self {
+        self.config.debug_mode = enabled;
+        self
+    }
+
+    // On by default. When disabled, `SlackError::User` is handled like any other listener error:
+    // logged and acknowledged, with nothing shown to the user.
+    pub fn surface_user_errors(mut self, enabled: bool) -> Self {
+        self.config.surface_user_errors = enabled;
+        self
+    }
+
+    // When disabled, Slack is answered as soon as a listener acks and the listener keeps running
+    // on a spawned task. Only for long-lived processes (local dev server, Socket Mode); on Lambda
+    // the spawned work would be frozen with the environment.
+    pub fn process_before_response(mut self, enabled: bool) -> Self {
+        self.config.process_before_response = enabled;
+        self
+    }
+
+    // Stage or custom-domain base path to strip from request paths, e.g. "/prod" so that
+    // "/prod/slack/events" is routed as "/slack/events"
+    pub fn base_path<S: Into<String>>(mut self, base_path: S) -> Self {
+        self.config.base_path = Some(base_path.into());
+        self
+    }
+
+    // Serves a JSON health report on GET /slack/health without signature verification. The
+    // report names listener kinds and configured stores, so only expose the route where that
+    // is acceptable.
+    pub fn health_check(mut self, enabled: bool) -> Self {
+        self.config.health_check = enabled;
+        self
+    }
+
+    // Reported as `version` by the health check, e.g. `env!("CARGO_PKG_VERSION")` of the app
+    pub fn app_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.config.app_version = Some(version.into());
+        self
+    }
+
+    // Channel told about deferred (SQS) requests that failed permanently or were dead-lettered
+    pub fn dead_letter_channel<S: Into<String>>(mut self, channel: S) -> Self {
+        self.config.dead_letter_channel = Some(channel.into());
+        self
+    }
+
     pub fn oauth_settings<F>(mut self, f: F) -> Self
     where
         F: FnOnce(OAuthSettings) -> OAuthSettings,
@@ -97,6 +300,180 @@ impl AppBuilder {
         self
     }
 
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = Some(policy);
+        self
+    }
+
+    pub fn conversation_store<S: ConversationStore + 'static>(mut self, store: S) -> Self {
+        self.conversation_store = Some(Arc::new(store));
+        self
+    }
+
+    // Per-workspace configuration, available to listeners through `context.team_settings()`
+    pub fn team_settings_store<S: TeamSettingsStore + 'static>(mut self, store: S) -> Self {
+        self.team_settings_store = Some(Arc::new(store));
+        self
+    }
+
+    // Per-user data handed to the `app.home` render function
+    pub fn home_state_provider<P: HomeStateProvider + 'static>(mut self, provider: P) -> Self {
+        self.home_state_provider = Some(Arc::new(provider));
+        self
+    }
+
+    pub fn conversation_lock<L: ConversationLock + 'static>(mut self, lock: L) -> Self {
+        self.conversation_lock = Some(Arc::new(lock));
+        self
+    }
+
+    // Every verified request is archived with its routing outcome once it has been handled
+    pub fn audit_sink<S: AuditSink + 'static>(mut self, sink: S) -> Self {
+        self.audit_sink = Some(Arc::new(sink));
+        self
+    }
+
+    // Where `context.defer()` sends requests to finish in an `SqsHandler`
+    pub fn request_queue<Q: RequestQueue + 'static>(mut self, queue: Q) -> Self {
+        self.request_queue = Some(Arc::new(queue));
+        self
+    }
+
+    pub fn metrics<M: Metrics + 'static>(mut self, metrics: M) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    // Shares Slack's rate limits across all concurrent instances, e.g. `DynamoDbRateLimiter`.
+    // Applies to the built-in client; a client passed to `client()` manages its own limits.
+    pub fn rate_limiter<R: RateLimiter + 'static>(mut self, rate_limiter: R) -> Self {
+        self.rate_limiter = Some(Arc::new(rate_limiter));
+        self
+    }
+
+    // Replaces the Web API client handed to handlers, e.g. with a `MockSlackClient` in tests
+    pub fn client<C: SlackApi + 'static>(mut self, client: C) -> Self {
+        self.client = Some(Arc::new(client));
+        self
+    }
+
+    // Runs over the text of everything the app's client sends, in registration order, e.g.
+    // `client::policy::mask_emails()`. Also applies to a client passed to `client`.
+    pub fn content_policy<F>(mut self, policy: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.content_policies.push(content_policy(policy));
+        self
+    }
+
+    pub fn execution_mode(mut self, mode: ExecutionMode) -> Self {
+        self.execution_mode = mode;
+        self
+    }
+
+    pub fn response_policy(mut self, policy: ResponsePolicy) -> Self {
+        self.response_policy = policy;
+        self
+    }
+
+    pub fn handler_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_budget.limit = Some(timeout);
+        self
+    }
+
+    // Handlers are cut off this long before the Lambda deadline so there is time left to respond
+    pub fn deadline_margin(mut self, margin: Duration) -> Self {
+        self.timeout_budget.safety_margin = margin;
+        self
+    }
+
+    // Shared resources (DB pools, HTTP clients, feature flags) handed to every handler
+    // through `context.state::<T>()`; one value per type
+    pub fn state<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.state.insert(value);
+        self
+    }
+
+    // Translations for `context.t` and the built-in OAuth pages
+    pub fn localizer<L: Localizer + 'static>(mut self, localizer: L) -> Self {
+        self.localizer = Some(Arc::new(localizer));
+        self
+    }
+
+    // Runs once during the Lambda init phase, before the first request is accepted
+    pub fn on_init<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(App) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.init_hooks.push(lifecycle_hook(f));
+        self
+    }
+
+    // Runs once after a SnapStart restore, before the first request of the restored environment
+    pub fn on_restore<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(App) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.restore_hooks.push(lifecycle_hook(f));
+        self
+    }
+
+    // Runs after every invocation, e.g. to force-flush a tracing exporter before Lambda freezes
+    pub fn on_flush<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(App) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.flush_hooks.push(lifecycle_hook(f));
+        self
+    }
+
+    // Runs once when the environment is shutting down (SIGTERM or Ctrl-C), followed by a flush
+    pub fn on_shutdown<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(App) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.shutdown_hooks.push(lifecycle_hook(f));
+        self
+    }
+
+    // Called when Slack reports it is dropping this app's events (app_rate_limited),
+    // e.g. to page an operator; the event is acknowledged whatever the hook returns
+    pub fn on_rate_limited<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(AppRateLimited) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.rate_limit_hooks.push(rate_limit_hook(f));
+        self
+    }
+
+    // Called after an OAuth install has been stored, e.g. to send a welcome DM or pick a
+    // default channel; the install still succeeds if the hook fails
+    pub fn on_install<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(Installation) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.install_hooks.push(install_hook(f));
+        self
+    }
+
+    // Called for app_uninstalled and tokens_revoked, after revoked credentials have been
+    // removed from the installation store
+    pub fn on_uninstall<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(Uninstall) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.uninstall_hooks.push(uninstall_hook(f));
+        self
+    }
+
     pub fn build(self) -> Result<App> {
         self.config.validate()?;
 
@@ -106,6 +483,59 @@ impl AppBuilder {
             app.oauth_settings = Some(Arc::new(oauth_settings));
         }
 
+        if let Some(error_policy) = self.error_policy {
+            app.error_policy = Arc::new(error_policy);
+        }
+
+        app.conversation_store = self.conversation_store;
+        app.audit_sink = self.audit_sink;
+        app.request_queue = self.request_queue;
+        app.conversation_lock = self.conversation_lock;
+        if let Some(metrics) = self.metrics {
+            app.metrics = metrics;
+        }
+        app.rate_limiter = self.rate_limiter;
+        app.team_settings_store = self.team_settings_store;
+        if let Some(provider) = self.home_state_provider {
+            app.home_state_provider = provider;
+        }
+        app.client = match self.client {
+            Some(client) => client,
+            None => default_client(&app),
+        };
+        if !self.content_policies.is_empty() {
+            app.client = Arc::new(PolicyClient::new(app.client, self.content_policies));
+        }
+        app.init_hooks = Arc::new(self.init_hooks);
+        app.restore_hooks = Arc::new(self.restore_hooks);
+        app.rate_limit_hooks = Arc::new(self.rate_limit_hooks);
+        app.install_hooks = Arc::new(self.install_hooks);
+        app.uninstall_hooks = Arc::new(self.uninstall_hooks);
+        app.flush_hooks = Arc::new(self.flush_hooks);
+        app.shutdown_hooks = Arc::new(self.shutdown_hooks);
+        app.state = Arc::new(self.state);
+        if let Some(localizer) = self.localizer {
+            app.localizer = localizer;
+        }
+        app.router_mut().set_execution_mode(self.execution_mode);
+        app.router_mut().set_response_policy(self.response_policy);
+        app.router_mut().set_timeout_budget(self.timeout_budget);
+
         Ok(app)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "http-client")]
+fn default_client(app: &App) -> Arc<dyn SlackApi> {
+    let mut client = SlackClient::new(app.config().bot_token.clone()).with_metrics(app.metrics());
+    if let Some(rate_limiter) = app.rate_limiter() {
+        client = client.with_rate_limiter(rate_limiter);
+    }
+    Arc::new(client)
+}
+
+// Without an HTTP client the host has to hand one in through `client`
+#[cfg(not(feature = "http-client"))]
+fn default_client(_app: &App) -> Arc<dyn SlackApi> {
+    Arc::new(UnconfiguredClient)
+}
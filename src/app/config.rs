@@ -1,36 +1,117 @@
 use crate::error::{Result, SlackError};
+use crate::secret::SecretString;
+
+// Routes the adapters serve for the OAuth install flow
+pub const OAUTH_INSTALL_PATH: &str = "/slack/install";
+pub const OAUTH_REDIRECT_PATH: &str = "/slack/oauth_redirect";
+// Unsigned GET route for load balancer and uptime checks, served when `health_check` is on
+pub const HEALTH_PATH: &str = "/slack/health";
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
-    pub bot_token: Option<String>,
-    pub signing_secret: String,
-    pub app_token: Option<String>,
+    pub bot_token: Option<SecretString>,
+    pub signing_secret: SecretString,
+    pub app_token: Option<SecretString>,
     pub client_id: Option<String>,
-    pub client_secret: Option<String>,
+    pub client_secret: Option<SecretString>,
     pub redirect_uri: Option<String>,
     pub scopes: Vec<String>,
     pub user_scopes: Vec<String>,
+    pub warmup_header: Option<String>,
+    pub max_body_bytes: usize,
+    pub max_blocks: usize,
+    pub lenient_parsing: bool,
+    pub security_headers: bool,
+    pub debug_mode: bool,
+    pub dead_letter_channel: Option<String>,
+    // Show `SlackError::User` messages to the user instead of only logging them
+    pub surface_user_errors: bool,
+    pub process_before_response: bool,
+    pub base_path: Option<String>,
+    pub health_check: bool,
+    pub app_version: Option<String>,
+    // Adapters `App::run` starts; both may be on, sharing one app
+    pub socket_mode: bool,
+    pub http_mode: bool,
+    // Concurrent Socket Mode connections; Slack spreads envelopes across them
+    pub socket_mode_connections: usize,
+    // Single-tenant pinning: when set, requests from any other workspace or org are rejected
+    // before routing, whatever token or signature they carry
+    pub allowed_team_ids: Vec<String>,
+    pub allowed_enterprise_ids: Vec<String>,
 }
 
 impl AppConfig {
-    pub fn new(signing_secret: String) -> Self {
+    pub fn new<S: Into<SecretString>>(signing_secret: S) -> Self {
         Self {
             bot_token: None,
-            signing_secret,
+            signing_secret: signing_secret.into(),
             app_token: None,
             client_id: None,
             client_secret: None,
             redirect_uri: None,
             scopes: vec!["chat:write".to_string()],
             user_scopes: vec![],
+            warmup_header: None,
+            // Slack payloads are a few KB; anything near the API Gateway limit is not from Slack
+            max_body_bytes: 1024 * 1024,
+            // Slack's own limit for messages, modals and home tabs
+            max_blocks: 100,
+            lenient_parsing: false,
+            security_headers: false,
+            debug_mode: false,
+            dead_letter_channel: None,
+            surface_user_errors: true,
+            // Lambda freezes the environment once the response is returned, so finish first
+            process_before_response: true,
+            base_path: None,
+            health_check: false,
+            app_version: None,
+            socket_mode: false,
+            http_mode: true,
+            socket_mode_connections: 1,
+            allowed_team_ids: vec![],
+            allowed_enterprise_ids: vec![],
+        }
+    }
+
+    // Org-level payloads carry no team_id; with both lists set they pass on the enterprise alone
+    pub fn is_workspace_allowed(&self, team_id: Option<&str>, enterprise_id: Option<&str>) -> bool {
+        let listed = |ids: &[String], id: Option<&str>| id.is_some_and(|id| ids.iter().any(|allowed| allowed == id));
+
+        if !self.allowed_enterprise_ids.is_empty() && !listed(&self.allowed_enterprise_ids, enterprise_id) {
+            return false;
+        }
+        if self.allowed_team_ids.is_empty() {
+            return true;
+        }
+        match team_id {
+            Some(_) => listed(&self.allowed_team_ids, team_id),
+            None => !self.allowed_enterprise_ids.is_empty(),
         }
     }
 
     pub fn validate(&self) -> Result<()> {
-        if self.signing_secret.is_empty() {
+        // Socket Mode requests are authenticated by the connection, not signed
+        if self.signing_secret.is_empty() && self.http_mode {
             return Err(SlackError::Config("Signing secret is required".to_string()));
         }
 
+        if self.socket_mode && self.app_token.is_none() {
+            return Err(SlackError::Config("Socket Mode requires an app-level token".to_string()));
+        }
+
+        // Slack allows up to 10 open connections per app
+        if !(1..=10).contains(&self.socket_mode_connections) {
+            return Err(SlackError::Config(
+                "socket_mode_connections must be between 1 and 10".to_string(),
+            ));
+        }
+
+        if !self.socket_mode && !self.http_mode {
+            return Err(SlackError::Config("Enable Socket Mode, HTTP or both".to_string()));
+        }
+
         if self.bot_token.is_none() && self.client_id.is_none() {
             return Err(SlackError::Config(
                 "Either bot_token or client_id must be provided".to_string(),
@@ -50,7 +131,66 @@ impl AppConfig {
         self.client_id.is_some() && self.client_secret.is_some()
     }
 
+    // Path Slack redirects back to after an install, taken from the configured redirect URI
+    pub fn oauth_redirect_path(&self) -> String {
+        self.redirect_uri
+            .as_deref()
+            .and_then(|uri| url::Url::parse(uri).ok())
+            .map(|uri| uri.path().to_string())
+            .unwrap_or_else(|| OAUTH_REDIRECT_PATH.to_string())
+    }
+
     pub fn get_bot_token(&self) -> Option<&str> {
-        self.bot_token.as_deref()
+        self.bot_token.as_ref().map(SecretString::expose)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(teams: &[&str], enterprises: &[&str]) -> AppConfig {
+        let mut config = AppConfig::new("secret");
+        config.allowed_team_ids = teams.iter().map(|id| id.to_string()).collect();
+        config.allowed_enterprise_ids = enterprises.iter().map(|id| id.to_string()).collect();
+        config
+    }
+
+    #[test]
+    fn no_lists_allow_everyone() {
+        let config = config(&[], &[]);
+
+        assert!(config.is_workspace_allowed(Some("T1"), None));
+        assert!(config.is_workspace_allowed(None, None));
+    }
+
+    #[test]
+    fn team_list_allows_only_listed_teams() {
+        let config = config(&["T1"], &[]);
+
+        assert!(config.is_workspace_allowed(Some("T1"), None));
+        assert!(config.is_workspace_allowed(Some("T1"), Some("E1")));
+        assert!(!config.is_workspace_allowed(Some("T2"), None));
+        assert!(!config.is_workspace_allowed(None, Some("E1")));
+    }
+
+    #[test]
+    fn enterprise_list_allows_every_team_in_listed_enterprises() {
+        let config = config(&[], &["E1"]);
+
+        assert!(config.is_workspace_allowed(Some("T1"), Some("E1")));
+        assert!(config.is_workspace_allowed(None, Some("E1")));
+        assert!(!config.is_workspace_allowed(Some("T1"), Some("E2")));
+        assert!(!config.is_workspace_allowed(Some("T1"), None));
+    }
+
+    #[test]
+    fn both_lists_require_both_and_org_payloads_pass_on_the_enterprise() {
+        let config = config(&["T1"], &["E1"]);
+
+        assert!(config.is_workspace_allowed(Some("T1"), Some("E1")));
+        assert!(config.is_workspace_allowed(None, Some("E1")));
+        assert!(!config.is_workspace_allowed(Some("T2"), Some("E1")));
+        assert!(!config.is_workspace_allowed(Some("T1"), Some("E2")));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::app::App;
+use crate::client::{PostMessageRequest, SlackApi};
+use crate::conversation::{ConversationKey, ConversationState, ConversationStore};
+use crate::error::{Result, SlackError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::Arc;
+
+// Slack rejects messages over 50 blocks; leave room for the header and footer
+const MAX_ITEM_BLOCKS: usize = 45;
+
+// Versioned writes against the digest's one item; writers that lose the race re-read and try
+// again, as many times as there could plausibly be instances adding at once
+const MAX_CONTENTION_RETRIES: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DigestItem {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+impl DigestItem {
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            user_id: None,
+            link: None,
+            added_at: Utc::now(),
+        }
+    }
+
+    pub fn with_user_id<S: Into<String>>(mut self, user_id: S) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    pub fn with_link<S: Into<String>>(mut self, link: S) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+}
+
+pub type DigestRender = Arc<dyn Fn(&str, &[DigestItem]) -> Vec<Value> + Send + Sync>;
+
+// Items collected in the conversation store between runs, posted to one channel as a single
+// summary when the digest's scheduled job fires, e.g. standup answers or merged PRs.
+// The pending list is rewritten with `ConversationStore::update_versioned`, so the store
+// must support conditional writes (`DynamoDbConversationStore` does).
+#[derive(Clone)]
+pub struct Digest {
+    name: String,
+    title: String,
+    team_id: String,
+    channel: String,
+    store: Arc<dyn ConversationStore>,
+    render: DigestRender,
+    skip_empty: bool,
+}
+
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Digest")
+            .field("name", &self.name)
+            .field("team_id", &self.team_id)
+            .field("channel", &self.channel)
+            .finish()
+    }
+}
+
+impl Digest {
+    // `name` is also the scheduled job name, so it must be unique within the app
+    pub fn new<S: Into<String>>(name: S, team_id: S, channel: S, store: Arc<dyn ConversationStore>) -> Self {
+        let name = name.into();
+        Self {
+            title: name.clone(),
+            name,
+            team_id: team_id.into(),
+            channel: channel.into(),
+            store,
+            render: Arc::new(default_render),
+            skip_empty: true,
+        }
+    }
+
+    pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_render<F>(mut self, render: F) -> Self
+    where
+        F: Fn(&str, &[DigestItem]) -> Vec<Value> + Send + Sync + 'static,
+    {
+        self.render = Arc::new(render);
+        self
+    }
+
+    // Post "nothing to report" runs too, instead of staying quiet
+    pub fn post_when_empty(mut self) -> Self {
+        self.skip_empty = false;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn add(&self, item: DigestItem) -> Result<()> {
+        for _ in 0..MAX_CONTENTION_RETRIES {
+            let (mut items, version) = self.read().await?;
+            items.push(item.clone());
+            if self.save(&items, version).await? {
+                return Ok(());
+            }
+        }
+
+        Err(self.contended())
+    }
+
+    pub async fn items(&self) -> Result<Vec<DigestItem>> {
+        Ok(self.read().await?.0)
+    }
+
+    pub fn render(&self, items: &[DigestItem]) -> Vec<Value> {
+        (self.render)(&self.title, items)
+    }
+
+    // Posts the pending items and starts a new collection. Items are only removed once the
+    // post succeeded, so a failed run is retried with the same items; anything added while the
+    // post is in flight stays for the next run. Returns the message ts.
+    pub async fn publish(&self, client: &dyn SlackApi) -> Result<Option<String>> {
+        let (items, version) = self.read().await?;
+        if items.is_empty() && self.skip_empty {
+            return Ok(None);
+        }
+
+        let response = client
+            .post_message(&PostMessageRequest {
+                channel: self.channel.clone(),
+                text: Some(format!("{} ({} items)", self.title, items.len())),
+                blocks: Some(self.render(&items)),
+                thread_ts: None,
+                metadata: None,
+            })
+            .await?;
+
+        self.remove(&items, version).await?;
+        Ok(response.ts)
+    }
+
+    // Drops the posted items, keeping whatever was added since they were read at `version`
+    async fn remove(&self, posted: &[DigestItem], mut version: Option<u64>) -> Result<()> {
+        if posted.is_empty() {
+            return Ok(());
+        }
+
+        let mut items = posted.to_vec();
+        for _ in 0..MAX_CONTENTION_RETRIES {
+            items.retain(|item| !posted.contains(item));
+            if self.save(&items, version).await? {
+                return Ok(());
+            }
+            (items, version) = self.read().await?;
+        }
+
+        Err(self.contended())
+    }
+
+    // The pending items and the version they were read at, None if nothing was ever added
+    async fn read(&self) -> Result<(Vec<DigestItem>, Option<u64>)> {
+        match self.store.find(&self.key()).await? {
+            Some(state) => Ok((serde_json::from_value(state.value)?, Some(state.version))),
+            None => Ok((Vec::new(), None)),
+        }
+    }
+
+    // False if someone else wrote since `version` was read
+    async fn save(&self, items: &[DigestItem], version: Option<u64>) -> Result<bool> {
+        let mut state = ConversationState::new(serde_json::to_value(items)?);
+        state.version = version.map_or(1, |version| version + 1);
+        self.store.update_versioned(&self.key(), &state, version).await
+    }
+
+    fn contended(&self) -> SlackError {
+        SlackError::Storage(format!("Digest {} kept changing under concurrent writers", self.name))
+    }
+
+    // Kept apart from the channel's real conversations by a pseudo thread ts
+    fn key(&self) -> ConversationKey {
+        ConversationKey::new(self.team_id.as_str(), self.channel.as_str())
+            .with_thread_ts(format!("digest:{}", self.name))
+    }
+}
+
+impl App {
+    // Registers the digest's scheduled job under its name; trigger it with
+    // `{"job": "<name>"}` through `ScheduleHandler`
+    pub fn digest(&mut self, digest: Digest) -> &mut Self {
+        let name = digest.name.clone();
+        self.scheduled(name, move |app| {
+            let digest = digest.clone();
+            async move { digest.publish(app.client().as_ref()).await.map(|_| ()) }
+        })
+    }
+}
+
+fn default_render(title: &str, items: &[DigestItem]) -> Vec<Value> {
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": title },
+    })];
+
+    if items.is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": "Nothing to report." },
+        }));
+        return blocks;
+    }
+
+    for item in items.iter().take(MAX_ITEM_BLOCKS) {
+        let mut text = match &item.link {
+            Some(link) => format!("• <{}|{}>", link, item.text),
+            None => format!("• {}", item.text),
+        };
+        if let Some(user_id) = &item.user_id {
+            text.push_str(&format!(" — <@{}>", user_id));
+        }
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": text },
+        }));
+    }
+
+    let overflow = items.len().saturating_sub(MAX_ITEM_BLOCKS);
+    let footer = if overflow > 0 {
+        format!("{} items, {} not shown", items.len(), overflow)
+    } else {
+        format!("{} items", items.len())
+    };
+    blocks.push(json!({
+        "type": "context",
+        "elements": [{ "type": "mrkdwn", "text": footer }],
+    }));
+    blocks
+}
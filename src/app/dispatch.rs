@@ -0,0 +1,244 @@
+use crate::app::{user_error, App};
+use crate::audit::PendingAudit;
+use crate::context::{correlation, invocation, Context};
+use crate::conversation::ConversationKey;
+use crate::error::{ErrorKind, Result, SlackError};
+use crate::event_types::{self, EventType};
+use crate::middleware::{debug, Next};
+use crate::oauth::Installation;
+use crate::request::{SlackRequest, SlackRequestBody};
+use crate::response::SlackResponse;
+use tokio::sync::oneshot;
+use tracing::{debug, error, warn};
+
+// Everything that happens to a request once an adapter has parsed and verified it.
+// Adapters, Socket Mode and the test harness all go through here so they behave the same.
+impl App {
+    pub async fn dispatch(&self, mut request: SlackRequest) -> Result<SlackResponse> {
+        // Adapters adopt an ID from upstream; Socket Mode and tests get a fresh one here
+        let correlation_id = request.correlation_id.get_or_insert_with(correlation::generate).clone();
+        correlation::CURRENT.scope(correlation_id, self.dispatch_correlated(request)).await
+    }
+
+    async fn dispatch_correlated(&self, request: SlackRequest) -> Result<SlackResponse> {
+        let sink = match self.audit_sink() {
+            Some(sink) => sink,
+            None => return self.dispatch_unaudited(request).await,
+        };
+
+        let listeners = self.router().matched_listener_count(&request);
+        let request_id = invocation::current().map(|invocation| invocation.request_id);
+        let pending = PendingAudit::new(&request, listeners, request_id);
+        let result = self.dispatch_unaudited(request).await;
+
+        // The audit trail must not decide whether Slack sees a failure
+        if let Err(e) = sink.record(&pending.finish(&result)).await {
+            error!("Failed to write audit record: {}", e);
+        }
+
+        result
+    }
+
+    async fn dispatch_unaudited(&self, request: SlackRequest) -> Result<SlackResponse> {
+        match &request.body {
+            SlackRequestBody::Event(event_req) => {
+                // Handle URL verification challenge
+                if let Some(challenge) = &event_req.challenge {
+                    return Ok(SlackResponse::challenge(challenge));
+                }
+                self.check_workspace(&request)?;
+                if event_req.event_type == event_types::APP_RATE_LIMITED {
+                    return Ok(self.handle_rate_limited(event_req).await);
+                }
+                if matches!(event_req.inner_type(), EventType::AppUninstalled | EventType::TokensRevoked) {
+                    self.handle_uninstall(event_req).await?;
+                }
+
+                self.process(request).await
+            }
+            SlackRequestBody::Command(_) | SlackRequestBody::Interactive(_) => {
+                self.check_workspace(&request)?;
+                self.process(request).await
+            }
+            // Only catch-all listeners can match these, so skip the work when there are none
+            SlackRequestBody::Raw(_) | SlackRequestBody::Unknown(_) if self.router().has_any_handlers() => {
+                self.check_workspace(&request)?;
+                self.process(request).await
+            }
+            SlackRequestBody::Raw(_) | SlackRequestBody::Unknown(_) => {
+                debug!("Ignoring {} request with no catch-all listener", request.body.kind());
+                Ok(SlackResponse::empty())
+            }
+            SlackRequestBody::OAuth(_) => Ok(SlackResponse::empty()),
+        }
+    }
+
+    fn check_workspace(&self, request: &SlackRequest) -> Result<()> {
+        let (team_id, enterprise_id) = (request.team_id(), request.enterprise_id());
+        if self.config().is_workspace_allowed(team_id, enterprise_id) {
+            return Ok(());
+        }
+
+        warn!("Rejecting request from team {:?} (enterprise {:?})", team_id, enterprise_id);
+        Err(SlackError::WorkspaceNotAllowed {
+            team_id: team_id.map(str::to_string),
+            enterprise_id: enterprise_id.map(str::to_string),
+        })
+    }
+
+    // With process_before_response off, Slack gets the first ack (or the listener's outcome if it
+    // finishes without acking) while the rest of the listener runs in the background. Deferred
+    // requests have nobody waiting on the ack, and their worker must not return (and let SQS
+    // delete the message) before the listener is done, so they always run to completion.
+    async fn process(&self, request: SlackRequest) -> Result<SlackResponse> {
+        if self.config().process_before_response || request.deferred {
+            return self.route_serialized(request, None).await;
+        }
+
+        let (responder, acked) = oneshot::channel();
+        let app = self.clone();
+        // Task-locals don't cross tokio::spawn, so the correlation ID is carried over explicitly
+        let correlation_id = correlation::current().unwrap_or_else(correlation::generate);
+        let mut task = tokio::spawn(correlation::CURRENT.scope(correlation_id, async move {
+            app.route_serialized(request, Some(responder)).await
+        }));
+
+        tokio::select! {
+            Ok(response) = acked => {
+                tokio::spawn(async move {
+                    match task.await {
+                        Ok(Err(e)) => error!("Listener failed after acknowledging: {}", e),
+                        Err(e) => error!("Listener task failed after acknowledging: {}", e),
+                        Ok(Ok(_)) => {}
+                    }
+                });
+                Ok(response)
+            }
+            joined = &mut task => {
+                joined.map_err(|e| SlackError::Internal(format!("Listener task failed: {}", e)))?
+            }
+        }
+    }
+
+    async fn route_serialized(
+        &self,
+        request: SlackRequest,
+        responder: Option<oneshot::Sender<SlackResponse>>,
+    ) -> Result<SlackResponse> {
+        let lock = self.conversation_lock().zip(ConversationKey::from_request(&request));
+        let owner = correlation::unique_id();
+
+        if let Some((lock, key)) = &lock {
+            lock.acquire(key, &owner).await?;
+        }
+
+        let result = self.route(request, responder).await;
+
+        if let Some((lock, key)) = &lock {
+            if let Err(e) = lock.release(key, &owner).await {
+                warn!("Failed to release conversation lock {}: {}", key.as_storage_key(), e);
+            }
+        }
+
+        result
+    }
+
+    async fn route(
+        &self,
+        request: SlackRequest,
+        responder: Option<oneshot::Sender<SlackResponse>>,
+    ) -> Result<SlackResponse> {
+        let debug_mode = self.config().debug_mode;
+        if debug_mode {
+            debug::log_request(&request);
+        }
+
+        let mut context = self.build_context(request).await?;
+        if let Some(responder) = responder {
+            context.ack = context.ack.with_responder(responder);
+        }
+        let surface = self.config().surface_user_errors.then(|| {
+            (context.request.clone(), context.client.clone(), context.ack.clone())
+        });
+        let router = self.router.clone();
+        let endpoint: Next = Box::new(move |context| {
+            Box::pin(async move {
+                if debug_mode {
+                    let matched = router.matched_listener_count(&context.request);
+                    debug::log_match(&context.request, matched, router.any_handler_count());
+                }
+
+                Ok(router
+                    .route_request(context)
+                    .await?
+                    .unwrap_or_else(SlackResponse::empty))
+            })
+        });
+
+        let result = if debug_mode {
+            let result = self.middleware().execute_traced(context, endpoint).await;
+            debug::log_result(&result);
+            result
+        } else {
+            self.middleware().execute(context, endpoint).await
+        };
+
+        match (result, surface) {
+            (Err(SlackError::User(error)), Some((request, client, ack))) => {
+                Ok(user_error::surface(&request, client.as_ref(), &ack, error).await)
+            }
+            // Middleware rejections (bad signature, IP allowlist, ...) keep their own status
+            (Err(e), _) if e.kind() == ErrorKind::InvalidRequest => Err(e),
+            (Err(e), _) => Err(SlackError::Listener(Box::new(e))),
+            (result, _) => result,
+        }
+    }
+
+    async fn build_context(&self, request: SlackRequest) -> Result<Context> {
+        let mut context = Context::new(request, self.client());
+        context.metrics = self.metrics();
+        context.set_state(self.state());
+        context.set_localizer(self.localizer());
+        if let Some(invocation) = invocation::current() {
+            context.set_invocation(invocation);
+        }
+
+        if let Some(installation) = self.find_installation(&context.request).await? {
+            context.set_installation(installation);
+        }
+
+        if let Some(store) = self.conversation_store() {
+            context.load_conversation(store).await?;
+        }
+
+        if let Some(store) = self.team_settings_store() {
+            context.set_team_settings_store(store);
+        }
+
+        if let Some(home) = self.home_tab() {
+            context.set_home_tab(home);
+        }
+
+        if let Some(queue) = self.request_queue() {
+            context.set_request_queue(queue);
+        }
+
+        Ok(context)
+    }
+
+    async fn find_installation(&self, request: &SlackRequest) -> Result<Option<Installation>> {
+        let store = match self.oauth_settings().and_then(|settings| settings.installation_store.as_deref()) {
+            Some(store) => store,
+            None => return Ok(None),
+        };
+        let team_id = match request.team_id() {
+            Some(team_id) => team_id,
+            None => return Ok(None),
+        };
+
+        Ok(store
+            .find_by_team(team_id, request.enterprise_id())
+            .await?
+            .filter(|installation| !installation.is_expired()))
+    }
+}
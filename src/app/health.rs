@@ -0,0 +1,150 @@
+use crate::app::App;
+use crate::response::SlackResponse;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Uptime checks hit the route every few seconds; auth.test is Tier 4 but still shared
+// with the app's real traffic
+const SLACK_CHECK_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    // The app's own version, from `AppBuilder::app_version`
+    pub version: Option<String>,
+    pub framework_version: &'static str,
+    pub listeners: BTreeMap<&'static str, usize>,
+    pub surfaces: Surfaces,
+    pub slack: SlackCheck,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    // Serving requests, but Slack rejected or could not be reached by the last check
+    Degraded,
+}
+
+// Which optional parts of the app are configured
+#[derive(Debug, Clone, Serialize)]
+pub struct Surfaces {
+    pub oauth: bool,
+    pub installation_store: bool,
+    pub state_store: bool,
+    pub conversation_store: bool,
+    pub team_settings_store: bool,
+    pub audit_sink: bool,
+    pub rate_limiter: bool,
+    pub home_tab: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlackCheck {
+    pub status: SlackCheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlackCheckStatus {
+    Ok,
+    Error,
+    // OAuth apps have no app-wide bot token to test
+    Skipped,
+}
+
+// Last auth.test result, shared by every clone of the app
+#[derive(Debug, Default)]
+pub(crate) struct HealthCache {
+    slack: Mutex<Option<(Instant, SlackCheck)>>,
+}
+
+impl HealthReport {
+    pub fn is_healthy(&self) -> bool {
+        self.status == HealthStatus::Ok
+    }
+
+    // 200 when healthy and 503 otherwise, so load balancers need not parse the body
+    pub fn response(&self) -> SlackResponse {
+        let status = if self.is_healthy() { 200 } else { 503 };
+        let body = serde_json::to_value(self).unwrap_or_else(|_| json!({ "status": "degraded" }));
+        SlackResponse::json(body).with_status(status).no_cache()
+    }
+}
+
+impl App {
+    pub async fn health(&self) -> HealthReport {
+        let slack = self.slack_check().await;
+        let status = match slack.status {
+            SlackCheckStatus::Error => HealthStatus::Degraded,
+            SlackCheckStatus::Ok | SlackCheckStatus::Skipped => HealthStatus::Ok,
+        };
+        let oauth_settings = self.oauth_settings();
+
+        HealthReport {
+            status,
+            version: self.config().app_version.clone(),
+            framework_version: env!("CARGO_PKG_VERSION"),
+            listeners: self.router().listener_counts(),
+            surfaces: Surfaces {
+                oauth: self.config().is_oauth_enabled(),
+                installation_store: oauth_settings.is_some_and(|s| s.installation_store.is_some()),
+                state_store: oauth_settings.is_some_and(|s| s.state_store.is_some()),
+                conversation_store: self.conversation_store().is_some(),
+                team_settings_store: self.team_settings_store().is_some(),
+                audit_sink: self.audit_sink().is_some(),
+                rate_limiter: self.rate_limiter().is_some(),
+                home_tab: self.home_tab().is_some(),
+            },
+            slack,
+        }
+    }
+
+    async fn slack_check(&self) -> SlackCheck {
+        if self.config().bot_token.is_none() {
+            return SlackCheck {
+                status: SlackCheckStatus::Skipped,
+                team_id: None,
+                bot_user_id: None,
+                error: None,
+                checked_at: Utc::now(),
+            };
+        }
+
+        if let Some((at, check)) = self.health_cache.slack.lock().unwrap().as_ref() {
+            if at.elapsed() < SLACK_CHECK_TTL {
+                return check.clone();
+            }
+        }
+
+        let check = match self.client().call("auth.test", json!({})).await {
+            Ok(response) => SlackCheck {
+                status: SlackCheckStatus::Ok,
+                team_id: response.get("team_id").and_then(|v| v.as_str()).map(str::to_string),
+                bot_user_id: response.get("user_id").and_then(|v| v.as_str()).map(str::to_string),
+                error: None,
+                checked_at: Utc::now(),
+            },
+            Err(e) => SlackCheck {
+                status: SlackCheckStatus::Error,
+                team_id: None,
+                bot_user_id: None,
+                error: Some(e.to_string()),
+                checked_at: Utc::now(),
+            },
+        };
+        *self.health_cache.slack.lock().unwrap() = Some((Instant::now(), check.clone()));
+        check
+    }
+}
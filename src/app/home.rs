@@ -0,0 +1,102 @@
+use crate::app::App;
+use crate::blocks::View;
+use crate::client::SlackApi;
+use crate::error::Result;
+use crate::event_types;
+use crate::listener::event::EventFilter;
+use crate::listener::handler;
+use crate::request::payloads::ViewPayload;
+use crate::response::SlackResponse;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+// Loads whatever the home tab shows for one user, e.g. their open tickets
+#[async_trait]
+pub trait HomeStateProvider: Send + Sync + Debug {
+    async fn load(&self, team_id: Option<&str>, user_id: &str) -> Result<Value>;
+}
+
+// Renders every user's home tab from nothing but their user ID
+#[derive(Debug, Clone, Default)]
+pub struct NoHomeState;
+
+#[async_trait]
+impl HomeStateProvider for NoHomeState {
+    async fn load(&self, _team_id: Option<&str>, _user_id: &str) -> Result<Value> {
+        Ok(Value::Null)
+    }
+}
+
+// What the render function gets for one user
+#[derive(Debug, Clone)]
+pub struct HomeState {
+    pub team_id: Option<String>,
+    pub user_id: String,
+    pub data: Value,
+}
+
+pub type HomeRender = Arc<dyn Fn(&HomeState) -> View + Send + Sync>;
+
+#[derive(Clone)]
+pub struct HomeTab {
+    render: HomeRender,
+    state_provider: Arc<dyn HomeStateProvider>,
+}
+
+impl HomeTab {
+    pub fn new(render: HomeRender, state_provider: Arc<dyn HomeStateProvider>) -> Self {
+        Self { render, state_provider }
+    }
+
+    // Loads the user's state, renders and publishes. No hash is sent: the freshly rendered
+    // view always wins over whatever the user has open.
+    pub async fn publish(&self, client: &dyn SlackApi, team_id: Option<&str>, user_id: &str) -> Result<ViewPayload> {
+        let data = self.state_provider.load(team_id, user_id).await?;
+        let state = HomeState {
+            team_id: team_id.map(str::to_string),
+            user_id: user_id.to_string(),
+            data,
+        };
+        let view = (self.render)(&state);
+        client.publish_view(user_id, &view, None).await
+    }
+}
+
+impl App {
+    // Publishes the home tab each time a user opens it, and from `context.refresh_home`.
+    // Per-user data comes from the builder's `home_state_provider`. An `app_home_opened`
+    // listener registered with `event` takes precedence over this one.
+    pub fn home<F>(&mut self, render: F) -> &mut Self
+    where
+        F: Fn(&HomeState) -> View + Send + Sync + 'static,
+    {
+        let home = Arc::new(HomeTab::new(Arc::new(render), self.home_state_provider.clone()));
+        self.home = Some(home.clone());
+
+        self.router_mut().add_filtered_event_handler(
+            event_types::APP_HOME_OPENED,
+            home_tab_filter(),
+            handler(move |context| {
+                let home = home.clone();
+                async move {
+                    let user_id = context.payload.get("user").and_then(|v| v.as_str()).unwrap_or_default();
+                    home.publish(context.client.as_ref(), context.request.team_id(), user_id)
+                        .await?;
+                    Ok(SlackResponse::empty())
+                }
+            }),
+        );
+        self
+    }
+
+    pub fn home_tab(&self) -> Option<Arc<HomeTab>> {
+        self.home.clone()
+    }
+}
+
+// app_home_opened also fires for the Messages and About tabs
+fn home_tab_filter() -> EventFilter {
+    Arc::new(|event| event.get("tab").and_then(|v| v.as_str()) == Some("home"))
+}
@@ -0,0 +1,94 @@
+use crate::app::config::{HEALTH_PATH, OAUTH_INSTALL_PATH};
+use crate::app::App;
+use crate::infrastructure::{InfrastructureDescriptor, QueueSpec, Resource};
+
+// Default names for the deferred-request queue pair consumed by `SqsHandler`/`DeadLetterHandler`
+const DEFERRED_QUEUE: &str = "slack-deferred-requests";
+const DEFERRED_DEAD_LETTER_QUEUE: &str = "slack-deferred-requests-dlq";
+
+impl App {
+    // Resources, environment variables and HTTP routes this app expects, as configured
+    pub fn describe_infrastructure(&self) -> InfrastructureDescriptor {
+        let config = self.config();
+        let mut descriptor = InfrastructureDescriptor::default();
+
+        descriptor.add_env_var("SLACK_SIGNING_SECRET", true, "Signing secret used to verify requests");
+        descriptor.add_env_var(
+            "SLACK_BOT_TOKEN",
+            !config.is_oauth_enabled(),
+            "Bot token for single-workspace apps",
+        );
+        if config.socket_mode {
+            descriptor.add_env_var("SLACK_APP_TOKEN", true, "App-level token for Socket Mode");
+        }
+        if config.http_mode {
+            descriptor.add_route("POST", "/slack/events", "Events API, slash commands and interactivity");
+        }
+
+        if config.health_check {
+            descriptor.add_route("GET", HEALTH_PATH, "Health report for load balancers and uptime checks");
+        }
+
+        if config.is_oauth_enabled() {
+            descriptor.add_env_var("SLACK_CLIENT_ID", true, "OAuth client ID");
+            descriptor.add_env_var("SLACK_CLIENT_SECRET", true, "OAuth client secret");
+            descriptor.add_env_var(
+                "SLACK_REDIRECT_URI",
+                config.redirect_uri.is_none(),
+                "OAuth redirect URI",
+            );
+
+            descriptor.add_route("GET", OAUTH_INSTALL_PATH, "Starts the OAuth install flow");
+            descriptor.add_route("GET", config.oauth_redirect_path(), "OAuth redirect from Slack");
+        }
+
+        if let Some(settings) = self.oauth_settings() {
+            let stores = settings
+                .installation_store
+                .iter()
+                .flat_map(|store| store.required_resources());
+            for resource in stores.chain(settings.state_store.iter().flat_map(|store| store.required_resources())) {
+                descriptor.add_resource(resource);
+            }
+        }
+        if let Some(store) = self.conversation_store() {
+            store
+                .required_resources()
+                .into_iter()
+                .for_each(|r| descriptor.add_resource(r));
+        }
+        if let Some(lock) = self.conversation_lock() {
+            lock.required_resources()
+                .into_iter()
+                .for_each(|r| descriptor.add_resource(r));
+        }
+        if let Some(sink) = self.audit_sink() {
+            sink.required_resources()
+                .into_iter()
+                .for_each(|r| descriptor.add_resource(r));
+        }
+        if let Some(store) = self.team_settings_store() {
+            store
+                .required_resources()
+                .into_iter()
+                .for_each(|r| descriptor.add_resource(r));
+        }
+        if let Some(rate_limiter) = self.rate_limiter() {
+            rate_limiter
+                .required_resources()
+                .into_iter()
+                .for_each(|r| descriptor.add_resource(r));
+        }
+
+        // A dead-letter channel only makes sense with the SQS workers in play
+        if config.dead_letter_channel.is_some() {
+            descriptor.add_resource(Resource::Queue(QueueSpec {
+                queue_name: DEFERRED_QUEUE.to_string(),
+                dead_letter_queue: Some(DEFERRED_DEAD_LETTER_QUEUE.to_string()),
+                max_receive_count: 5,
+            }));
+        }
+
+        descriptor
+    }
+}
@@ -0,0 +1,119 @@
+use crate::app::lifecycle::HookFuture;
+use crate::app::App;
+use crate::error::Result;
+#[cfg(feature = "oauth")]
+use crate::error::SlackError;
+#[cfg(feature = "oauth")]
+use crate::oauth::flow::{InstallOutcome, OAuthFlow};
+use crate::oauth::Installation;
+use crate::request::payloads::{Uninstall, UninstallKind};
+use crate::request::EventRequest;
+use std::future::Future;
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub type InstallHook = Arc<dyn Fn(Installation) -> HookFuture + Send + Sync>;
+pub type UninstallHook = Arc<dyn Fn(Uninstall) -> HookFuture + Send + Sync>;
+
+pub fn install_hook<F, Fut>(f: F) -> InstallHook
+where
+    F: Fn(Installation) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    Arc::new(move |installation| Box::pin(f(installation)))
+}
+
+pub fn uninstall_hook<F, Fut>(f: F) -> UninstallHook
+where
+    F: Fn(Uninstall) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    Arc::new(move |uninstall| Box::pin(f(uninstall)))
+}
+
+impl App {
+    #[cfg(feature = "oauth")]
+    pub fn oauth_flow(&self) -> Result<OAuthFlow> {
+        let settings = self
+            .oauth_settings()
+            .ok_or_else(|| SlackError::Config("OAuth is not configured".to_string()))?;
+        OAuthFlow::from_settings(self.config(), settings)
+    }
+
+    // Authorize URL to register as the App Directory's direct install URL
+    #[cfg(feature = "oauth")]
+    pub fn direct_install_url(&self) -> Result<String> {
+        self.oauth_flow()?.direct_install_url()
+    }
+
+    // Exchanges the redirect's code and stores the installation, then runs the install hooks.
+    // The workspace is installed once the store has it, so a failing hook is only logged.
+    #[cfg(feature = "oauth")]
+    pub async fn complete_install(&self, code: &str, state: &str) -> Result<InstallOutcome> {
+        let outcome = self.oauth_flow()?.complete(code, state).await?;
+        let installation = &outcome.installation;
+        info!(team_id = %installation.team_id, "App installed");
+        self.metrics()
+            .counter("AppInstalled", 1, &[("TeamId", installation.team_id.as_str())]);
+
+        for hook in self.install_hooks.iter() {
+            if let Err(e) = hook(installation.clone()).await {
+                error!("on_install hook failed: {}", e);
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    // Forgets revoked credentials before the uninstall hooks run. A failed cleanup is
+    // returned so Slack retries the event; hooks only fire once cleanup has succeeded.
+    pub(crate) async fn handle_uninstall(&self, event_req: &EventRequest) -> Result<()> {
+        let uninstall = match Uninstall::from_event(event_req) {
+            Some(uninstall) => uninstall,
+            None => return Ok(()),
+        };
+
+        self.remove_credentials(&uninstall).await?;
+        info!(team_id = %uninstall.team_id, kind = ?uninstall.kind, "Credentials revoked");
+
+        for hook in self.uninstall_hooks.iter() {
+            if let Err(e) = hook(uninstall.clone()).await {
+                error!("on_uninstall hook failed: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn remove_credentials(&self, uninstall: &Uninstall) -> Result<()> {
+        let store = match self
+            .oauth_settings()
+            .and_then(|settings| settings.installation_store.as_deref())
+        {
+            Some(store) => store,
+            None => return Ok(()),
+        };
+        let enterprise_id = uninstall.enterprise_id.as_deref();
+
+        if uninstall.removes_bot() {
+            return store.delete(&uninstall.team_id, enterprise_id).await;
+        }
+
+        // Only user tokens went away; keep the bot installation but drop the installer's token
+        if let UninstallKind::TokensRevoked { users, .. } = &uninstall.kind {
+            if let Some(mut installation) = store.find_by_team(&uninstall.team_id, enterprise_id).await? {
+                if installation
+                    .user_id
+                    .as_ref()
+                    .is_some_and(|user_id| users.contains(user_id))
+                {
+                    installation.user_token = None;
+                    installation.user_scopes.clear();
+                    store.save(&installation).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::app::App;
+use crate::error::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+pub type HookFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+pub type LifecycleHook = Arc<dyn Fn(App) -> HookFuture + Send + Sync>;
+
+pub fn lifecycle_hook<F, Fut>(f: F) -> LifecycleHook
+where
+    F: Fn(App) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    Arc::new(move |app| Box::pin(f(app)))
+}
+
+// Runs the shutdown hooks and exits on SIGTERM (sent by Lambda at shutdown when an extension
+// is registered, and by container runtimes) or Ctrl-C for local servers
+pub fn spawn_shutdown_listener(app: App) {
+    tokio::spawn(async move {
+        wait_for_signal().await;
+        info!("Shutdown signal received");
+        app.shutdown().await;
+        std::process::exit(0);
+    });
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+        Err(e) => {
+            warn!("Failed to listen for SIGTERM: {}", e);
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+// Lambda sets this to "snap-start" when the environment was restored from a snapshot
+pub fn is_snapstart() -> bool {
+    std::env::var("AWS_LAMBDA_INITIALIZATION_TYPE").map(|v| v == "snap-start").unwrap_or(false)
+}
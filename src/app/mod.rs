@@ -1,21 +1,86 @@
 pub mod builder;
 pub mod config;
+pub mod digest;
+pub mod dispatch;
+pub mod health;
+pub mod home;
+pub mod infrastructure;
+pub mod install;
+pub mod lifecycle;
+pub mod rate_limit;
+pub mod run;
+pub mod schedule;
+pub mod state;
+mod user_error;
 
 pub use builder::AppBuilder;
 pub use config::AppConfig;
+pub use digest::{Digest, DigestItem};
+pub use health::{HealthReport, HealthStatus, SlackCheck, SlackCheckStatus, Surfaces};
+pub use home::{HomeState, HomeStateProvider, HomeTab, NoHomeState};
+pub use install::{InstallHook, UninstallHook};
+pub use lifecycle::LifecycleHook;
+pub use rate_limit::RateLimitHook;
+pub use state::AppState;
 
-use crate::error::{Result, SlackError};
-use crate::listener::EventRouter;
-use crate::middleware::MiddlewareStack;
+use crate::adapter::{ErrorPolicy, RequestQueue};
+use health::HealthCache;
+use crate::audit::AuditSink;
+#[cfg(not(feature = "http-client"))]
+use crate::client::UnconfiguredClient;
+use crate::client::{RateLimiter, SlackApi};
+#[cfg(feature = "http-client")]
+use crate::client::SlackClient;
+use crate::conversation::{ConversationLock, ConversationStore};
+use crate::context::Context;
+use crate::error::Result;
+use crate::i18n::{Localizer, StaticLocalizer};
+use crate::metrics::{Metrics, NoopMetrics};
+use crate::event_types;
+use crate::blocks::View;
+use crate::listener::event::{mention_handler, reaction_filter};
+use crate::listener::message::TextPattern;
+use crate::listener::{handler, typed_handler, CustomFunction, EventRouter, WorkflowStep};
+use crate::request::CommandRequest;
+use crate::request::payloads::{Mention, MessageEvent, ReactionAddedEvent, ReactionRemovedEvent};
+use crate::response::SlackResponse;
+use crate::middleware::{after_response, middleware, MiddlewareStack, Next};
 use crate::oauth::OAuthSettings;
+use crate::settings::TeamSettingsStore;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use tracing::error;
 
 #[derive(Clone)]
 pub struct App {
     config: Arc<AppConfig>,
     router: Arc<EventRouter>,
     middleware: Arc<MiddlewareStack>,
+    client: Arc<dyn SlackApi>,
     oauth_settings: Option<Arc<OAuthSettings>>,
+    error_policy: Arc<ErrorPolicy>,
+    conversation_store: Option<Arc<dyn ConversationStore>>,
+    conversation_lock: Option<Arc<dyn ConversationLock>>,
+    metrics: Arc<dyn Metrics>,
+    init_hooks: Arc<Vec<LifecycleHook>>,
+    restore_hooks: Arc<Vec<LifecycleHook>>,
+    rate_limit_hooks: Arc<Vec<RateLimitHook>>,
+    install_hooks: Arc<Vec<InstallHook>>,
+    uninstall_hooks: Arc<Vec<UninstallHook>>,
+    flush_hooks: Arc<Vec<LifecycleHook>>,
+    shutdown_hooks: Arc<Vec<LifecycleHook>>,
+    state: Arc<AppState>,
+    localizer: Arc<dyn Localizer>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    request_queue: Option<Arc<dyn RequestQueue>>,
+    rate_limiter: Option<Arc<dyn RateLimiter>>,
+    team_settings_store: Option<Arc<dyn TeamSettingsStore>>,
+    home_state_provider: Arc<dyn HomeStateProvider>,
+    home: Option<Arc<HomeTab>>,
+    health_cache: Arc<HealthCache>,
+    scheduled_jobs: Arc<HashMap<String, LifecycleHook>>,
 }
 
 impl App {
@@ -24,11 +89,39 @@ impl App {
     }
 
     pub fn new(config: AppConfig) -> Self {
+        // Built once per execution environment and cloned into each request's context
+        #[cfg(feature = "http-client")]
+        let client: Arc<dyn SlackApi> = Arc::new(SlackClient::new(config.bot_token.clone()));
+        #[cfg(not(feature = "http-client"))]
+        let client: Arc<dyn SlackApi> = Arc::new(UnconfiguredClient);
+
         Self {
             config: Arc::new(config),
             router: Arc::new(EventRouter::new()),
             middleware: Arc::new(MiddlewareStack::new()),
+            client,
             oauth_settings: None,
+            error_policy: Arc::new(ErrorPolicy::new()),
+            conversation_store: None,
+            conversation_lock: None,
+            metrics: Arc::new(NoopMetrics),
+            init_hooks: Arc::new(Vec::new()),
+            restore_hooks: Arc::new(Vec::new()),
+            rate_limit_hooks: Arc::new(Vec::new()),
+            install_hooks: Arc::new(Vec::new()),
+            uninstall_hooks: Arc::new(Vec::new()),
+            flush_hooks: Arc::new(Vec::new()),
+            shutdown_hooks: Arc::new(Vec::new()),
+            state: Arc::new(AppState::new()),
+            localizer: Arc::new(StaticLocalizer::new()),
+            audit_sink: None,
+            request_queue: None,
+            rate_limiter: None,
+            team_settings_store: None,
+            home_state_provider: Arc::new(NoHomeState),
+            home: None,
+            health_cache: Arc::new(HealthCache::default()),
+            scheduled_jobs: Arc::new(HashMap::new()),
         }
     }
 
@@ -40,16 +133,365 @@ impl App {
         &self.router
     }
 
+    fn router_mut(&mut self) -> &mut EventRouter {
+        Arc::make_mut(&mut self.router)
+    }
+
+    // Middleware wraps every listener; call `next(context)` to continue down the chain
+    pub fn use_middleware<F, Fut>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Context, Next) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        Arc::make_mut(&mut self.middleware).add(middleware(f));
+        self
+    }
+
+    // Runs after the middleware chain and listener with their outcome, e.g. to add response
+    // headers, record metrics or enqueue follow-up work; whatever it returns is sent to Slack
+    pub fn after_response<F, Fut>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Context, Result<SlackResponse>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        Arc::make_mut(&mut self.middleware).add_after(after_response(f));
+        self
+    }
+
+    // Applies to the listener registered just before, e.g.
+    // `app.event("message", audit).receive_retries(true)`. Slack retries a delivery that timed
+    // out even though the app may have handled it; listeners skip those duplicates unless
+    // they opt in, as an at-least-once audit log would. Retries of failed deliveries always run.
+    pub fn receive_retries(&mut self, receive: bool) -> &mut Self {
+        self.router_mut().set_receive_retries(receive);
+        self
+    }
+
+    // Also accepts "*" for every event and prefix patterns such as "message.*"
+    pub fn event<S, F, Fut>(&mut self, event_type: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_event_handler(event_type, handler(f));
+        self
+    }
+
+    // reaction_added for one emoji ("white_check_mark", ":+1:" or "*" for any), e.g. to
+    // approve a request by reacting to it
+    pub fn reaction<S, F, Fut>(&mut self, name: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context, ReactionAddedEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut()
+            .add_filtered_event_handler(event_types::REACTION_ADDED, reaction_filter(name), typed_handler(f));
+        self
+    }
+
+    pub fn reaction_removed<S, F, Fut>(&mut self, name: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context, ReactionRemovedEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut()
+            .add_filtered_event_handler(event_types::REACTION_REMOVED, reaction_filter(name), typed_handler(f));
+        self
+    }
+
+    pub fn command<S, F, Fut>(&mut self, command: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_command_handler(command, handler(f));
+        self
+    }
+
+    pub fn action<S, F, Fut>(&mut self, action_id: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_action_handler(action_id, handler(f));
+        self
+    }
+
+    // view_submission for modals opened with this callback_id. Returning an empty response
+    // closes the modal; respond with a `response_action` body to show errors or update it, or
+    // return a `UserError` to show its message under one field.
+    pub fn view<S, F, Fut>(&mut self, callback_id: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_view_handler(callback_id, handler(f));
+        self
+    }
+
+    // Acks the command, opens the modal built from it and routes the submission to
+    // `on_submit`. The command name is used as the modal's callback_id.
+    pub fn command_opens_modal<S, V, F, Fut>(&mut self, command: S, view_factory: V, on_submit: F) -> &mut Self
+    where
+        S: Into<String>,
+        V: Fn(&CommandRequest) -> View + Send + Sync + 'static,
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        let command = command.into();
+        let callback_id = command.clone();
+        let view_factory = Arc::new(view_factory);
+        self.router_mut().add_view_handler(command.clone(), handler(on_submit));
+
+        self.command(command, move |context| {
+            let view_factory = view_factory.clone();
+            let callback_id = callback_id.clone();
+            async move {
+                let command = context.command()?;
+                let view = view_factory(command).with_callback_id(callback_id);
+                let trigger_id = command.trigger_id.clone();
+                // trigger_ids expire after three seconds, so open before doing anything else
+                context.client().open_view(&trigger_id, &view).await?;
+                context.ack.empty().await
+            }
+        });
+        self
+    }
+
+    // Matches both global and message shortcuts with this callback_id
+    pub fn shortcut<S, F, Fut>(&mut self, callback_id: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_shortcut_handler(callback_id, handler(f));
+        self
+    }
+
+    // Global shortcuts only; see `Context::global_shortcut` for the payload
+    pub fn global_shortcut<S, F, Fut>(&mut self, callback_id: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_global_shortcut_handler(callback_id, handler(f));
+        self
+    }
+
+    // Message shortcuts only; `Context::message_shortcut` carries the target message and channel
+    pub fn message_shortcut<S, F, Fut>(&mut self, callback_id: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_message_shortcut_handler(callback_id, handler(f));
+        self
+    }
+
+    pub fn message<F, Fut>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_message_handler(handler(f));
+        self
+    }
+
+    // app_mention, with the text minus the bot's own mention in `Mention::text`
+    pub fn mention<F, Fut>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Context, Mention) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_event_handler(event_types::APP_MENTION, mention_handler(f));
+        self
+    }
+
+    // Messages whose text contains the keyword, case-insensitively by default; pass a
+    // `TextPattern` for case-sensitive or whole-word matching
+    pub fn message_contains<P, F, Fut>(&mut self, pattern: P, f: F) -> &mut Self
+    where
+        P: Into<TextPattern>,
+        F: Fn(Context, MessageEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut()
+            .add_filtered_event_handler(event_types::MESSAGE, pattern.into().contains_filter(), typed_handler(f));
+        self
+    }
+
+    // Messages whose text starts with the prefix, ignoring leading whitespace
+    pub fn message_starts_with<P, F, Fut>(&mut self, pattern: P, f: F) -> &mut Self
+    where
+        P: Into<TextPattern>,
+        F: Fn(Context, MessageEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut()
+            .add_filtered_event_handler(event_types::MESSAGE, pattern.into().starts_with_filter(), typed_handler(f));
+        self
+    }
+
+    // Receives every request no other listener handled, including payloads only lenient
+    // parsing understands; useful for logging or forwarding unsupported payloads
+    pub fn any<F, Fut>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_any_handler(handler(f));
+        self
+    }
+
+    pub fn step<S: Into<String>>(&mut self, callback_id: S, step: WorkflowStep) -> &mut Self {
+        self.router_mut().add_step_handler(callback_id, step);
+        self
+    }
+
+    pub fn function<F, Fut>(&mut self, function: CustomFunction, f: F) -> &mut Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.router_mut().add_function_handler(function, handler(f));
+        self
+    }
+
+    // The `functions` section of the app manifest for every function registered with `App::function`
+    pub fn functions_manifest(&self) -> Value {
+        let functions: Map<String, Value> = self
+            .router
+            .functions()
+            .iter()
+            .map(|function| (function.callback_id.clone(), function.manifest_entry()))
+            .collect();
+
+        serde_json::json!({ "functions": functions })
+    }
+
     pub fn middleware(&self) -> &MiddlewareStack {
         &self.middleware
     }
 
+    pub fn client(&self) -> Arc<dyn SlackApi> {
+        self.client.clone()
+    }
+
     pub fn oauth_settings(&self) -> Option<&OAuthSettings> {
         self.oauth_settings.as_deref()
     }
 
+    pub fn error_policy(&self) -> &ErrorPolicy {
+        &self.error_policy
+    }
+
+    pub fn conversation_store(&self) -> Option<Arc<dyn ConversationStore>> {
+        self.conversation_store.clone()
+    }
+
+    pub fn conversation_lock(&self) -> Option<&dyn ConversationLock> {
+        self.conversation_lock.as_deref()
+    }
+
+    pub fn state(&self) -> Arc<AppState> {
+        self.state.clone()
+    }
+
+    pub fn localizer(&self) -> Arc<dyn Localizer> {
+        self.localizer.clone()
+    }
+
+    pub fn audit_sink(&self) -> Option<Arc<dyn AuditSink>> {
+        self.audit_sink.clone()
+    }
+
+    pub fn request_queue(&self) -> Option<Arc<dyn RequestQueue>> {
+        self.request_queue.clone()
+    }
+
+    pub fn rate_limiter(&self) -> Option<Arc<dyn RateLimiter>> {
+        self.rate_limiter.clone()
+    }
+
+    pub fn team_settings_store(&self) -> Option<Arc<dyn TeamSettingsStore>> {
+        self.team_settings_store.clone()
+    }
+
+    pub fn metrics(&self) -> Arc<dyn Metrics> {
+        self.metrics.clone()
+    }
+
+    // Runs the `on_init` hooks in registration order, stopping at the first failure
+    pub async fn run_init_hooks(&self) -> Result<()> {
+        for hook in self.init_hooks.iter() {
+            hook(self.clone()).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn run_restore_hooks(&self) -> Result<()> {
+        for hook in self.restore_hooks.iter() {
+            hook(self.clone()).await?;
+        }
+        Ok(())
+    }
+
+    // Pushes out buffered telemetry; adapters call this before each invocation returns.
+    // Failing hooks are logged rather than failing a request that has already been handled.
+    pub async fn flush(&self) {
+        self.metrics.flush();
+        if let Some(sink) = &self.audit_sink {
+            if let Err(e) = sink.flush().await {
+                error!("Failed to flush audit records: {}", e);
+            }
+        }
+        for hook in self.flush_hooks.iter() {
+            if let Err(e) = hook(self.clone()).await {
+                error!("Flush hook failed: {}", e);
+            }
+        }
+    }
+
+    pub async fn shutdown(&self) {
+        for hook in self.shutdown_hooks.iter() {
+            if let Err(e) = hook(self.clone()).await {
+                error!("Shutdown hook failed: {}", e);
+            }
+        }
+        self.flush().await;
+    }
+
     #[cfg(feature = "lambda")]
     pub fn lambda_handler(self) -> crate::adapter::aws_lambda::LambdaHandler {
         crate::adapter::aws_lambda::LambdaHandler::new(self)
     }
+
+    #[cfg(feature = "lambda")]
+    pub fn sqs_handler(self) -> crate::adapter::sqs::SqsHandler {
+        crate::adapter::sqs::SqsHandler::new(self)
+    }
+
+    #[cfg(feature = "lambda")]
+    pub fn schedule_handler(self) -> crate::adapter::schedule::ScheduleHandler {
+        crate::adapter::schedule::ScheduleHandler::new(self)
+    }
+
+    #[cfg(feature = "socket-mode")]
+    pub fn socket_mode_handler(self) -> crate::adapter::socket_mode::SocketModeHandler {
+        crate::adapter::socket_mode::SocketModeHandler::new(self)
+    }
+
+    #[cfg(feature = "lambda")]
+    pub fn dead_letter_handler(self) -> crate::adapter::sqs::DeadLetterHandler {
+        crate::adapter::sqs::DeadLetterHandler::new(self)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+use crate::app::lifecycle::HookFuture;
+use crate::app::App;
+use crate::request::payloads::AppRateLimited;
+use crate::request::EventRequest;
+use crate::response::SlackResponse;
+use std::future::Future;
+use std::sync::Arc;
+use tracing::{error, warn};
+
+pub type RateLimitHook = Arc<dyn Fn(AppRateLimited) -> HookFuture + Send + Sync>;
+
+pub fn rate_limit_hook<F, Fut>(f: F) -> RateLimitHook
+where
+    F: Fn(AppRateLimited) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::error::Result<()>> + Send + 'static,
+{
+    Arc::new(move |event| Box::pin(f(event)))
+}
+
+impl App {
+    // Slack sends app_rate_limited once per minute in which it dropped events for a team.
+    // It is acknowledged regardless of what the hooks do; a failing hook is only logged.
+    pub(crate) async fn handle_rate_limited(&self, event_req: &EventRequest) -> SlackResponse {
+        let event = AppRateLimited::from(event_req);
+        warn!(
+            team_id = %event.team_id,
+            minute_rate_limited = event.minute_rate_limited,
+            "Slack is rate limiting events for this app"
+        );
+        self.metrics().counter("AppRateLimited", 1, &[("TeamId", event.team_id.as_str())]);
+
+        for hook in self.rate_limit_hooks.iter() {
+            if let Err(e) = hook(event.clone()).await {
+                error!("app_rate_limited hook failed: {}", e);
+            }
+        }
+
+        SlackResponse::empty()
+    }
+}
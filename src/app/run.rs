@@ -0,0 +1,47 @@
+use crate::app::{lifecycle, App};
+use crate::error::SlackError;
+
+pub type RunError = Box<dyn std::error::Error + Send + Sync>;
+
+impl App {
+    // Starts the adapters selected with `AppBuilder::socket_mode` and `http_mode` and runs
+    // until one of them stops. Both share this app's router, middleware and stores; init
+    // hooks run once beforehand.
+    pub async fn run(self) -> Result<(), RunError> {
+        let config = self.config().clone();
+        self.run_init_hooks().await?;
+        lifecycle::spawn_shutdown_listener(self.clone());
+
+        match (config.socket_mode, config.http_mode) {
+            (true, true) => {
+                let socket_mode = self.clone().serve_socket_mode();
+                let http = self.serve_http();
+                tokio::try_join!(socket_mode, http)?;
+                Ok(())
+            }
+            (true, false) => self.serve_socket_mode().await,
+            (false, true) => self.serve_http().await,
+            (false, false) => Err(SlackError::Config("Enable Socket Mode, HTTP or both".to_string()).into()),
+        }
+    }
+
+    #[cfg(feature = "socket-mode")]
+    async fn serve_socket_mode(self) -> Result<(), RunError> {
+        Ok(crate::adapter::socket_mode::SocketModeHandler::new(self).serve().await?)
+    }
+
+    #[cfg(not(feature = "socket-mode"))]
+    async fn serve_socket_mode(self) -> Result<(), RunError> {
+        Err(SlackError::Config("Socket Mode needs the `socket-mode` feature".to_string()).into())
+    }
+
+    #[cfg(feature = "lambda")]
+    async fn serve_http(self) -> Result<(), RunError> {
+        crate::adapter::aws_lambda::LambdaHandler::new(self).serve().await
+    }
+
+    #[cfg(not(feature = "lambda"))]
+    async fn serve_http(self) -> Result<(), RunError> {
+        Err(SlackError::Config("HTTP mode needs the `lambda` feature".to_string()).into())
+    }
+}
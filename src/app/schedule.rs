@@ -0,0 +1,33 @@
+use crate::app::lifecycle::{lifecycle_hook, LifecycleHook};
+use crate::app::App;
+use crate::error::{Result, SlackError};
+use std::future::Future;
+use std::sync::Arc;
+use tracing::info;
+
+impl App {
+    // Work run by name from `ScheduleHandler`, e.g. a nightly report. Registering a second job
+    // under the same name replaces the first.
+    pub fn scheduled<S, F, Fut>(&mut self, name: S, f: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: Fn(App) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        Arc::make_mut(&mut self.scheduled_jobs).insert(name.into(), lifecycle_hook(f));
+        self
+    }
+
+    pub fn scheduled_job_names(&self) -> Vec<&str> {
+        self.scheduled_jobs.keys().map(String::as_str).collect()
+    }
+
+    pub async fn run_scheduled(&self, name: &str) -> Result<()> {
+        let job: &LifecycleHook = self
+            .scheduled_jobs
+            .get(name)
+            .ok_or_else(|| SlackError::Config(format!("No scheduled job named {}", name)))?;
+        info!(job = name, "Running scheduled job");
+        job(self.clone()).await
+    }
+}
@@ -0,0 +1,86 @@
+use crate::client::SlackApi;
+use crate::context::Ack;
+use crate::error::UserError;
+use crate::request::{SlackRequest, SlackRequestBody};
+use crate::response::SlackResponse;
+use serde_json::{json, Value};
+use tracing::{error, warn};
+
+// Shows a listener's `UserError` to the user who triggered the request and answers Slack with
+// a 200, so the failure is neither silent on their side nor redelivered.
+//
+// A modal submission that hasn't been acked gets the message as a field error, keeping the
+// modal open. An unacked slash command gets it as the ephemeral ack body. Everything else
+// goes through response_url, or chat.postEphemeral for events in a channel.
+pub(crate) async fn surface(request: &SlackRequest, client: &dyn SlackApi, ack: &Ack, error: UserError) -> SlackResponse {
+    match &error.cause {
+        Some(cause) => error!("Listener failed with a user error {:?}, caused by: {}", error.message, cause),
+        None => warn!("Listener failed with a user error: {}", error.message),
+    }
+
+    let acked = ack.is_acknowledged();
+    match &request.body {
+        SlackRequestBody::Interactive(interactive) if interactive.payload_type == "view_submission" => {
+            if acked {
+                warn!("Modal submission was already acknowledged, the user error is not shown");
+                return SlackResponse::empty();
+            }
+            let view = interactive.view.as_ref().unwrap_or(&Value::Null);
+            match error.block_id.as_deref().or_else(|| first_input_block(view)) {
+                Some(block_id) => SlackResponse::json(json!({
+                    "response_action": "errors",
+                    "errors": { block_id: error.message },
+                })),
+                None => {
+                    warn!("Modal has no input block to show the user error under");
+                    SlackResponse::empty()
+                }
+            }
+        }
+        SlackRequestBody::Command(_) if !acked => SlackResponse::json(ephemeral(&error.message)),
+        _ => {
+            if let Err(e) = send_ephemeral(request, client, &error.message).await {
+                error!("Failed to show user error: {}", e);
+            }
+            SlackResponse::empty()
+        }
+    }
+}
+
+async fn send_ephemeral(request: &SlackRequest, client: &dyn SlackApi, message: &str) -> crate::error::Result<()> {
+    let response_url = match &request.body {
+        SlackRequestBody::Interactive(interactive) => Some(interactive.response_url.as_str()),
+        SlackRequestBody::Command(command) => Some(command.response_url.as_str()),
+        _ => None,
+    }
+    .filter(|url| !url.is_empty());
+    if let Some(response_url) = response_url {
+        let mut payload = ephemeral(message);
+        payload["replace_original"] = json!(false);
+        return client.respond(response_url, payload).await;
+    }
+
+    match (request.channel_id(), request.user_id()) {
+        (Some(channel), Some(user)) => client
+            .call("chat.postEphemeral", json!({ "channel": channel, "user": user, "text": message }))
+            .await
+            .map(|_| ()),
+        _ => {
+            warn!("No channel or response_url to show the user error in");
+            Ok(())
+        }
+    }
+}
+
+fn ephemeral(message: &str) -> Value {
+    json!({ "response_type": "ephemeral", "text": message })
+}
+
+fn first_input_block(view: &Value) -> Option<&str> {
+    view.get("blocks")?
+        .as_array()?
+        .iter()
+        .find(|block| block.get("type").and_then(|v| v.as_str()) == Some("input"))?
+        .get("block_id")?
+        .as_str()
+}
@@ -0,0 +1,62 @@
+use crate::audit::{AuditRecord, AuditSink};
+use crate::error::Result;
+use crate::infrastructure::Resource;
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+// Holds records back and writes them with one `record_batch` when the app flushes at the end of
+// an invocation, so an SQS batch costs a few batch writes instead of a write per request.
+// Records still held when the process dies are lost, and a failed flush drops its records like
+// a failed `record` does; wrap a sink only where that is acceptable.
+#[derive(Debug)]
+pub struct BufferedAuditSink<S> {
+    inner: S,
+    records: Mutex<Vec<AuditRecord>>,
+}
+
+impl<S: AuditSink> BufferedAuditSink<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn buffered(&self) -> usize {
+        self.records.lock().map(|records| records.len()).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl<S: AuditSink> AuditSink for BufferedAuditSink<S> {
+    async fn record(&self, record: &AuditRecord) -> Result<()> {
+        if let Ok(mut records) = self.records.lock() {
+            records.push(record.clone());
+        }
+        Ok(())
+    }
+
+    async fn record_batch(&self, records: &[AuditRecord]) -> Result<()> {
+        self.inner.record_batch(records).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let records = self
+            .records
+            .lock()
+            .map(|mut records| std::mem::take(&mut *records))
+            .unwrap_or_default();
+        if records.is_empty() {
+            return Ok(());
+        }
+        self.inner.record_batch(&records).await
+    }
+
+    fn required_resources(&self) -> Vec<Resource> {
+        self.inner.required_resources()
+    }
+
+    async fn recent(&self, team_id: Option<&str>, limit: usize) -> Result<Vec<AuditRecord>> {
+        self.inner.recent(team_id, limit).await
+    }
+}
@@ -0,0 +1,165 @@
+use crate::audit::{AuditRecord, AuditSink};
+use crate::dynamodb::{batch_put_items, ensure_table, TableSpec};
+use crate::error::{Result, SlackError};
+use crate::infrastructure::Resource;
+use async_trait::async_trait;
+use aws_sdk_dynamodb::{Client as DynamoDbClient, types::AttributeValue};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration as StdDuration;
+
+// One item per record keyed by team and `received_at#id`, so a team's history reads in order
+#[derive(Debug, Clone)]
+pub struct DynamoDbAuditSink {
+    client: DynamoDbClient,
+    table_name: String,
+    retention: Option<Duration>,
+}
+
+impl DynamoDbAuditSink {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self {
+            client,
+            table_name,
+            retention: None,
+        }
+    }
+
+    // Sets an `expires_at` TTL attribute, which `create_table` enables TTL on
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    // Creates the table with TTL on `expires_at` if it doesn't exist yet
+    pub async fn create_table(&self) -> Result<()> {
+        ensure_table(&self.client, &self.spec(), StdDuration::from_secs(60)).await.map(|_| ())
+    }
+
+    // TTL is enabled even without a retention; records without `expires_at` are kept forever
+    fn spec(&self) -> TableSpec {
+        TableSpec::new(self.table_name.as_str(), "team_id")
+            .with_sort_key("record_key")
+            .with_ttl("expires_at")
+    }
+
+    fn record_to_item(&self, record: &AuditRecord) -> Result<HashMap<String, AttributeValue>> {
+        let mut item = HashMap::new();
+
+        item.insert(
+            "team_id".to_string(),
+            AttributeValue::S(record.team_id.clone().unwrap_or_else(|| "NONE".to_string())),
+        );
+        item.insert(
+            "record_key".to_string(),
+            AttributeValue::S(format!("{}#{}", record.received_at.to_rfc3339(), record.id)),
+        );
+        item.insert("id".to_string(), AttributeValue::S(record.id.clone()));
+        item.insert("kind".to_string(), AttributeValue::S(record.kind.clone()));
+        item.insert("received_at".to_string(), AttributeValue::S(record.received_at.to_rfc3339()));
+        item.insert("duration_ms".to_string(), AttributeValue::N(record.duration_ms.to_string()));
+        item.insert("payload".to_string(), AttributeValue::S(serde_json::to_string(&record.payload)?));
+        item.insert("outcome".to_string(), AttributeValue::S(serde_json::to_string(&record.outcome)?));
+
+        if let Some(routing_key) = &record.routing_key {
+            item.insert("routing_key".to_string(), AttributeValue::S(routing_key.clone()));
+        }
+
+        if let Some(request_id) = &record.request_id {
+            item.insert("request_id".to_string(), AttributeValue::S(request_id.clone()));
+        }
+
+        if let Some(retention) = self.retention {
+            let expires_at = record.received_at + retention;
+            item.insert("expires_at".to_string(), AttributeValue::N(expires_at.timestamp().to_string()));
+        }
+
+        Ok(item)
+    }
+
+    // Fields not written by `record_to_item` come back empty
+    fn item_to_record(item: &HashMap<String, AttributeValue>) -> Result<AuditRecord> {
+        let string = |name: &str| item.get(name).and_then(|v| v.as_s().ok()).cloned();
+        let received_at = string("received_at")
+            .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+            .map(|v| v.with_timezone(&Utc))
+            .ok_or_else(|| SlackError::DynamoDb("Audit record without received_at".to_string()))?;
+        let duration_ms = item
+            .get("duration_ms")
+            .and_then(|v| v.as_n().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or_default();
+        let team_id = string("team_id").filter(|team_id| team_id != "NONE");
+
+        Ok(AuditRecord {
+            id: string("id").unwrap_or_default(),
+            received_at,
+            completed_at: received_at + Duration::milliseconds(duration_ms as i64),
+            duration_ms,
+            kind: string("kind").unwrap_or_default(),
+            team_id,
+            enterprise_id: None,
+            user_id: None,
+            channel_id: None,
+            routing_key: string("routing_key"),
+            request_id: string("request_id"),
+            payload: match string("payload") {
+                Some(payload) => serde_json::from_str(&payload)?,
+                None => Value::Null,
+            },
+            outcome: serde_json::from_str(&string("outcome").unwrap_or_default())?,
+        })
+    }
+}
+
+#[async_trait]
+impl AuditSink for DynamoDbAuditSink {
+    fn required_resources(&self) -> Vec<Resource> {
+        vec![Resource::Table(self.spec())]
+    }
+
+    async fn recent(&self, team_id: Option<&str>, limit: usize) -> Result<Vec<AuditRecord>> {
+        let output = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .key_condition_expression("team_id = :team_id")
+            .expression_attribute_values(":team_id", AttributeValue::S(team_id.unwrap_or("NONE").to_string()))
+            .scan_index_forward(false)
+            .limit(limit.min(i32::MAX as usize) as i32)
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        output.items().iter().map(Self::item_to_record).collect()
+    }
+
+    async fn record(&self, record: &AuditRecord) -> Result<()> {
+        let item = self.record_to_item(record)?;
+
+        // Never overwrite an existing record
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(record_key)")
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        Ok(())
+    }
+
+    // BatchWriteItem can't keep `record`'s no-overwrite condition, but a record's key is fixed by
+    // its ID and timestamp, so writing one again (a replay, or a retried batch) only rewrites
+    // the same item
+    async fn record_batch(&self, records: &[AuditRecord]) -> Result<()> {
+        let items = records
+            .iter()
+            .map(|record| self.record_to_item(record))
+            .collect::<Result<Vec<_>>>()?;
+
+        batch_put_items(&self.client, &self.table_name, items).await
+    }
+}
@@ -0,0 +1,146 @@
+// Archive of every verified inbound payload and what the app did with it
+pub mod buffered;
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb_sink;
+#[cfg(feature = "s3")]
+pub mod s3_sink;
+
+pub use buffered::BufferedAuditSink;
+#[cfg(feature = "dynamodb")]
+pub use dynamodb_sink::DynamoDbAuditSink;
+#[cfg(feature = "s3")]
+pub use s3_sink::S3AuditSink;
+
+use crate::error::{Result, SlackError};
+use crate::context::correlation;
+use crate::middleware::debug::redact;
+use crate::request::SlackRequest;
+use crate::response::SlackResponse;
+use async_trait::async_trait;
+use crate::infrastructure::Resource;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AuditOutcome {
+    // Dispatched; `listeners` is how many matched (0 means only catch-alls or nothing ran)
+    Handled { listeners: usize, status_code: u16 },
+    Failed { listeners: usize, error: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub id: String,
+    pub received_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub kind: String,
+    pub team_id: Option<String>,
+    pub enterprise_id: Option<String>,
+    pub user_id: Option<String>,
+    pub channel_id: Option<String>,
+    pub routing_key: Option<String>,
+    pub request_id: Option<String>,
+    // Parsed body with tokens and response URLs redacted
+    pub payload: Value,
+    pub outcome: AuditOutcome,
+}
+
+impl AuditRecord {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.outcome, AuditOutcome::Failed { .. })
+    }
+
+    pub fn storage_key(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.team_id.as_deref().unwrap_or("none"),
+            self.received_at.format("%Y/%m/%d"),
+            self.id
+        )
+    }
+}
+
+#[async_trait]
+pub trait AuditSink: Send + Sync + Debug {
+    // Records are written once and never updated
+    async fn record(&self, record: &AuditRecord) -> Result<()>;
+
+    // For backfills, replays and `BufferedAuditSink`. Sinks that can write many records per
+    // call override this.
+    async fn record_batch(&self, records: &[AuditRecord]) -> Result<()> {
+        for record in records {
+            self.record(record).await?;
+        }
+        Ok(())
+    }
+
+    // Writes whatever the sink is holding back; called by `App::flush` after every invocation
+    async fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn required_resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+
+    // Newest first, for operator tooling. Append-only sinks such as S3 need not support reads.
+    async fn recent(&self, team_id: Option<&str>, limit: usize) -> Result<Vec<AuditRecord>> {
+        let _ = (team_id, limit);
+        Err(SlackError::Config(format!("{:?} does not support reading records back", self)))
+    }
+}
+
+// Captured before dispatch so the record reflects the request as received
+pub(crate) struct PendingAudit {
+    started: Instant,
+    received_at: DateTime<Utc>,
+    record: AuditRecord,
+}
+
+impl PendingAudit {
+    pub(crate) fn new(request: &SlackRequest, listeners: usize, request_id: Option<String>) -> Self {
+        let received_at = Utc::now();
+        let payload = serde_json::to_value(&request.body).unwrap_or(Value::Null);
+
+        Self {
+            started: Instant::now(),
+            received_at,
+            record: AuditRecord {
+                id: correlation::unique_id(),
+                received_at,
+                completed_at: received_at,
+                duration_ms: 0,
+                kind: request.body.kind().to_string(),
+                team_id: request.team_id().map(str::to_string),
+                enterprise_id: request.enterprise_id().map(str::to_string),
+                user_id: request.user_id().map(str::to_string),
+                channel_id: request.channel_id().map(str::to_string),
+                routing_key: request.routing_key().map(str::to_string),
+                request_id,
+                payload: redact(&payload),
+                outcome: AuditOutcome::Handled { listeners, status_code: 200 },
+            },
+        }
+    }
+
+    pub(crate) fn finish(self, result: &Result<SlackResponse>) -> AuditRecord {
+        let listeners = match &self.record.outcome {
+            AuditOutcome::Handled { listeners, .. } | AuditOutcome::Failed { listeners, .. } => *listeners,
+        };
+
+        AuditRecord {
+            completed_at: self.received_at + self.started.elapsed(),
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            outcome: match result {
+                Ok(response) => AuditOutcome::Handled { listeners, status_code: response.status_code },
+                Err(e) => AuditOutcome::Failed { listeners, error: e.to_string() },
+            },
+            ..self.record
+        }
+    }
+}
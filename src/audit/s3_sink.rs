@@ -0,0 +1,59 @@
+use crate::audit::{AuditRecord, AuditSink};
+use crate::error::{Result, SlackError};
+use crate::infrastructure::Resource;
+use async_trait::async_trait;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client as S3Client;
+
+// One JSON object per record at `<prefix>/<team>/<yyyy>/<mm>/<dd>/<id>.json`.
+// Pair with S3 Object Lock on the bucket for a tamper-proof archive.
+#[derive(Debug, Clone)]
+pub struct S3AuditSink {
+    client: S3Client,
+    bucket: String,
+    prefix: String,
+}
+
+impl S3AuditSink {
+    pub fn new(client: S3Client, bucket: String) -> Self {
+        Self {
+            client,
+            bucket,
+            prefix: "audit".to_string(),
+        }
+    }
+
+    pub fn with_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn object_key(&self, record: &AuditRecord) -> String {
+        format!("{}/{}.json", self.prefix, record.storage_key())
+    }
+}
+
+#[async_trait]
+impl AuditSink for S3AuditSink {
+    fn required_resources(&self) -> Vec<Resource> {
+        vec![Resource::Bucket { bucket_name: self.bucket.clone() }]
+    }
+
+    async fn record(&self, record: &AuditRecord) -> Result<()> {
+        let body = serde_json::to_vec(record)?;
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(record))
+            .content_type("application/json")
+            // Keys are unique per record; refuse to replace one if it somehow exists
+            .if_none_match("*")
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| SlackError::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+}
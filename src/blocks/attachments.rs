@@ -0,0 +1,230 @@
+// Converts legacy message attachments, as posted by old incoming-webhook integrations, into
+// Block Kit. Block Kit has no color bar, so `migrate_message` keeps colored attachments as
+// attachments wrapping the converted blocks; everything else moves into top-level blocks.
+use serde_json::{json, Map, Value};
+
+// Slack's limit on fields in one section block
+const MAX_SECTION_FIELDS: usize = 10;
+
+// Rewrites a message payload's `attachments`; other keys are left alone
+pub fn migrate_message(message: &Value) -> Value {
+    let Some(attachments) = message.get("attachments").and_then(|v| v.as_array()) else {
+        return message.clone();
+    };
+
+    let mut migrated = message.as_object().cloned().unwrap_or_default();
+    let mut blocks = migrated
+        .remove("blocks")
+        .and_then(|blocks| blocks.as_array().cloned())
+        .unwrap_or_default();
+    let mut colored = Vec::new();
+
+    for attachment in attachments {
+        let converted = attachment_to_blocks(attachment);
+        match attachment.get("color").and_then(|v| v.as_str()) {
+            Some(color) => {
+                let mut wrapper = Map::new();
+                wrapper.insert("color".to_string(), json!(normalize_color(color)));
+                if let Some(fallback) = attachment.get("fallback") {
+                    wrapper.insert("fallback".to_string(), fallback.clone());
+                }
+                wrapper.insert("blocks".to_string(), Value::Array(converted));
+                colored.push(Value::Object(wrapper));
+            }
+            None => blocks.extend(converted),
+        }
+    }
+
+    // Notifications and screen readers use the top-level text once blocks are present
+    if migrated.get("text").and_then(|v| v.as_str()).unwrap_or_default().is_empty() {
+        let fallback = attachments
+            .iter()
+            .find_map(|attachment| attachment.get("fallback").and_then(|v| v.as_str()));
+        if let Some(fallback) = fallback {
+            migrated.insert("text".to_string(), json!(fallback));
+        }
+    }
+
+    migrated.remove("attachments");
+    if !blocks.is_empty() {
+        migrated.insert("blocks".to_string(), Value::Array(blocks));
+    }
+    if !colored.is_empty() {
+        migrated.insert("attachments".to_string(), Value::Array(colored));
+    }
+    Value::Object(migrated)
+}
+
+// The blocks for one attachment, in the order Slack rendered its parts
+pub fn attachment_to_blocks(attachment: &Value) -> Vec<Value> {
+    let field = |name: &str| {
+        attachment
+            .get(name)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+    };
+    let mut blocks = Vec::new();
+
+    if let Some(pretext) = field("pretext") {
+        blocks.push(section(pretext));
+    }
+
+    if let Some(author) = field("author_name") {
+        let mut elements = Vec::new();
+        if let Some(icon) = field("author_icon") {
+            elements.push(json!({ "type": "image", "image_url": icon, "alt_text": author }));
+        }
+        elements.push(mrkdwn(&link(author, field("author_link"))));
+        blocks.push(json!({ "type": "context", "elements": elements }));
+    }
+
+    if let Some(title) = field("title") {
+        blocks.push(section(&format!("*{}*", link(title, field("title_link")))));
+    }
+
+    if let Some(text) = field("text") {
+        let mut block = section(text);
+        if let Some(thumb) = field("thumb_url") {
+            block["accessory"] = json!({ "type": "image", "image_url": thumb, "alt_text": "thumbnail" });
+        }
+        blocks.push(block);
+    }
+
+    if let Some(fields) = attachment.get("fields").and_then(|v| v.as_array()) {
+        blocks.extend(field_blocks(fields));
+    }
+
+    if let Some(image) = field("image_url") {
+        let alt_text = field("fallback").or(field("title")).unwrap_or("image");
+        blocks.push(json!({ "type": "image", "image_url": image, "alt_text": alt_text }));
+    }
+
+    if let Some(actions) = attachment.get("actions").and_then(|v| v.as_array()) {
+        let buttons: Vec<Value> = actions.iter().enumerate().filter_map(|(i, action)| button(i, action)).collect();
+        if !buttons.is_empty() {
+            blocks.push(json!({ "type": "actions", "elements": buttons }));
+        }
+    }
+
+    if let Some(footer) = footer(attachment) {
+        blocks.push(footer);
+    }
+
+    blocks
+}
+
+// Short fields sit side by side in a section's two columns; long ones get a section each
+fn field_blocks(fields: &[Value]) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut short = Vec::new();
+
+    for field in fields {
+        let title = field.get("title").and_then(|v| v.as_str()).unwrap_or_default();
+        let value = field.get("value").and_then(|v| v.as_str()).unwrap_or_default();
+        let text = match (title.is_empty(), value.is_empty()) {
+            (true, true) => continue,
+            (true, false) => value.to_string(),
+            (false, true) => format!("*{}*", title),
+            (false, false) => format!("*{}*\n{}", title, value),
+        };
+
+        if field.get("short").and_then(|v| v.as_bool()).unwrap_or(false) {
+            short.push(mrkdwn(&text));
+            if short.len() == MAX_SECTION_FIELDS {
+                blocks.push(json!({ "type": "section", "fields": std::mem::take(&mut short) }));
+            }
+        } else {
+            if !short.is_empty() {
+                blocks.push(json!({ "type": "section", "fields": std::mem::take(&mut short) }));
+            }
+            blocks.push(section(&text));
+        }
+    }
+    if !short.is_empty() {
+        blocks.push(json!({ "type": "section", "fields": short }));
+    }
+    blocks
+}
+
+// Legacy link buttons and interactive buttons; menus have no faithful equivalent and are dropped
+fn button(index: usize, action: &Value) -> Option<Value> {
+    if action.get("type").and_then(|v| v.as_str()) != Some("button") {
+        return None;
+    }
+    let text = action.get("text").and_then(|v| v.as_str())?;
+    let action_id = action
+        .get("name")
+        .and_then(|v| v.as_str())
+        .map(|name| format!("{}_{}", name, index))
+        .unwrap_or_else(|| format!("button_{}", index));
+
+    let mut button = json!({
+        "type": "button",
+        "text": { "type": "plain_text", "text": text },
+        "action_id": action_id,
+    });
+    for key in ["url", "value"] {
+        if let Some(value) = action.get(key).and_then(|v| v.as_str()) {
+            button[key] = json!(value);
+        }
+    }
+    if let Some(style @ ("primary" | "danger")) = action.get("style").and_then(|v| v.as_str()) {
+        button["style"] = json!(style);
+    }
+    Some(button)
+}
+
+fn footer(attachment: &Value) -> Option<Value> {
+    let footer = attachment.get("footer").and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+    // `ts` was documented as an integer but is often sent as a string
+    let ts = attachment.get("ts").and_then(|v| match v {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.split('.').next().and_then(|s| s.parse().ok()),
+        _ => None,
+    });
+    if footer.is_none() && ts.is_none() {
+        return None;
+    }
+
+    let mut elements = Vec::new();
+    if let Some(icon) = attachment.get("footer_icon").and_then(|v| v.as_str()) {
+        elements.push(json!({ "type": "image", "image_url": icon, "alt_text": footer.unwrap_or("footer") }));
+    }
+    let date = ts.map(|ts| format!("<!date^{}^{{date_short_pretty}} at {{time}}|{}>", ts, ts));
+    let text = match (footer, date) {
+        (Some(footer), Some(date)) => format!("{} | {}", footer, date),
+        (Some(footer), None) => footer.to_string(),
+        (None, Some(date)) => date,
+        (None, None) => return None,
+    };
+    elements.push(mrkdwn(&text));
+    Some(json!({ "type": "context", "elements": elements }))
+}
+
+// Attachments accepted "good"/"warning"/"danger" besides hex codes
+fn normalize_color(color: &str) -> String {
+    match color {
+        "good" => "#2eb886".to_string(),
+        "warning" => "#daa038".to_string(),
+        "danger" => "#a30200".to_string(),
+        hex if !hex.starts_with('#') && hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+            format!("#{}", hex)
+        }
+        other => other.to_string(),
+    }
+}
+
+fn link(text: &str, url: Option<&str>) -> String {
+    match url {
+        Some(url) => format!("<{}|{}>", url, text),
+        None => text.to_string(),
+    }
+}
+
+fn section(text: &str) -> Value {
+    json!({ "type": "section", "text": mrkdwn(text) })
+}
+
+fn mrkdwn(text: &str) -> Value {
+    json!({ "type": "mrkdwn", "text": text })
+}
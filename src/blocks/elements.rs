@@ -0,0 +1,686 @@
+use crate::blocks::options::{validate_option_groups, validate_options, MAX_OPTIONS};
+use crate::blocks::{OptionGroup, OptionObject, Text};
+use crate::error::{Result, SlackError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Interactive elements for input, actions and section blocks
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Element {
+    PlainTextInput(TextInput),
+    EmailTextInput(TextInput),
+    UrlTextInput(TextInput),
+    NumberInput(NumberInput),
+    Datepicker(DatePicker),
+    Timepicker(TimePicker),
+    Datetimepicker(DateTimePicker),
+    StaticSelect(Select),
+    MultiStaticSelect(Select),
+    ExternalSelect(Select),
+    MultiExternalSelect(Select),
+    UsersSelect(Select),
+    MultiUsersSelect(Select),
+    ConversationsSelect(Select),
+    MultiConversationsSelect(Select),
+    ChannelsSelect(Select),
+    MultiChannelsSelect(Select),
+    RadioButtons(Choices),
+    Checkboxes(Choices),
+    Overflow(Choices),
+    FileInput(FileInput),
+}
+
+impl Element {
+    pub fn plain_text_input<S: Into<String>>(action_id: S) -> Self {
+        Element::PlainTextInput(TextInput::new(action_id))
+    }
+
+    pub fn email_input<S: Into<String>>(action_id: S) -> Self {
+        Element::EmailTextInput(TextInput::new(action_id))
+    }
+
+    pub fn url_input<S: Into<String>>(action_id: S) -> Self {
+        Element::UrlTextInput(TextInput::new(action_id))
+    }
+
+    pub fn number_input<S: Into<String>>(action_id: S, is_decimal_allowed: bool) -> Self {
+        Element::NumberInput(NumberInput::new(action_id, is_decimal_allowed))
+    }
+
+    pub fn datepicker<S: Into<String>>(action_id: S) -> Self {
+        Element::Datepicker(DatePicker::new(action_id))
+    }
+
+    pub fn timepicker<S: Into<String>>(action_id: S) -> Self {
+        Element::Timepicker(TimePicker::new(action_id))
+    }
+
+    pub fn datetimepicker<S: Into<String>>(action_id: S) -> Self {
+        Element::Datetimepicker(DateTimePicker::new(action_id))
+    }
+
+    pub fn static_select<S: Into<String>>(action_id: S, options: Vec<OptionObject>) -> Self {
+        Element::StaticSelect(Select::new(action_id).with_options(options))
+    }
+
+    // Lists over 100 options are split into numbered groups instead of being rejected by Slack
+    pub fn static_select_chunked<S: Into<String>>(action_id: S, options: Vec<OptionObject>) -> Result<Self> {
+        let select = Select::new(action_id);
+        if options.len() <= MAX_OPTIONS {
+            return Ok(Element::StaticSelect(select.with_options(options)));
+        }
+        let groups = OptionGroup::chunked(options, |n| format!("Page {}", n))?;
+        Ok(Element::StaticSelect(select.with_option_groups(groups)))
+    }
+
+    pub fn grouped_select<S: Into<String>>(action_id: S, option_groups: Vec<OptionGroup>) -> Self {
+        Element::StaticSelect(Select::new(action_id).with_option_groups(option_groups))
+    }
+
+    pub fn multi_static_select<S: Into<String>>(action_id: S, options: Vec<OptionObject>) -> Self {
+        Element::MultiStaticSelect(Select::new(action_id).with_options(options))
+    }
+
+    pub fn external_select<S: Into<String>>(action_id: S) -> Self {
+        Element::ExternalSelect(Select::new(action_id))
+    }
+
+    pub fn multi_external_select<S: Into<String>>(action_id: S) -> Self {
+        Element::MultiExternalSelect(Select::new(action_id))
+    }
+
+    pub fn users_select<S: Into<String>>(action_id: S) -> Self {
+        Element::UsersSelect(Select::new(action_id))
+    }
+
+    pub fn multi_users_select<S: Into<String>>(action_id: S) -> Self {
+        Element::MultiUsersSelect(Select::new(action_id))
+    }
+
+    pub fn conversations_select<S: Into<String>>(action_id: S) -> Self {
+        Element::ConversationsSelect(Select::new(action_id))
+    }
+
+    pub fn multi_conversations_select<S: Into<String>>(action_id: S) -> Self {
+        Element::MultiConversationsSelect(Select::new(action_id))
+    }
+
+    pub fn channels_select<S: Into<String>>(action_id: S) -> Self {
+        Element::ChannelsSelect(Select::new(action_id))
+    }
+
+    pub fn multi_channels_select<S: Into<String>>(action_id: S) -> Self {
+        Element::MultiChannelsSelect(Select::new(action_id))
+    }
+
+    pub fn radio_buttons<S: Into<String>>(action_id: S, options: Vec<OptionObject>) -> Self {
+        Element::RadioButtons(Choices::new(action_id, options))
+    }
+
+    pub fn checkboxes<S: Into<String>>(action_id: S, options: Vec<OptionObject>) -> Self {
+        Element::Checkboxes(Choices::new(action_id, options))
+    }
+
+    // Overflow menus belong in section accessories and actions blocks, not input blocks
+    pub fn overflow<S: Into<String>>(action_id: S, options: Vec<OptionObject>) -> Self {
+        Element::Overflow(Choices::new(action_id, options))
+    }
+
+    pub fn file_input<S: Into<String>>(action_id: S) -> Self {
+        Element::FileInput(FileInput::new(action_id))
+    }
+
+    // Chained setters below apply to the elements that have the field and are ignored by the rest
+
+    pub fn with_placeholder<T: Into<Text>>(mut self, placeholder: T) -> Self {
+        let placeholder = Some(placeholder.into());
+        match &mut self {
+            Element::PlainTextInput(e) | Element::EmailTextInput(e) | Element::UrlTextInput(e) => {
+                e.placeholder = placeholder
+            }
+            Element::NumberInput(e) => e.placeholder = placeholder,
+            Element::Datepicker(e) => e.placeholder = placeholder,
+            Element::Timepicker(e) => e.placeholder = placeholder,
+            _ => {
+                if let Some(select) = self.select_mut() {
+                    select.placeholder = placeholder;
+                }
+            }
+        }
+        self
+    }
+
+    pub fn with_initial_value<S: Into<String>>(mut self, value: S) -> Self {
+        match &mut self {
+            Element::PlainTextInput(e) | Element::EmailTextInput(e) | Element::UrlTextInput(e) => {
+                e.initial_value = Some(value.into())
+            }
+            Element::NumberInput(e) => e.initial_value = Some(value.into()),
+            _ => {}
+        }
+        self
+    }
+
+    pub fn multiline(mut self) -> Self {
+        if let Element::PlainTextInput(e) = &mut self {
+            e.multiline = Some(true);
+        }
+        self
+    }
+
+    pub fn with_length(mut self, min_length: Option<u32>, max_length: Option<u32>) -> Self {
+        if let Element::PlainTextInput(e) = &mut self {
+            e.min_length = min_length;
+            e.max_length = max_length;
+        }
+        self
+    }
+
+    pub fn with_range<S: ToString>(mut self, min_value: Option<S>, max_value: Option<S>) -> Self {
+        if let Element::NumberInput(e) = &mut self {
+            e.min_value = min_value.map(|v| v.to_string());
+            e.max_value = max_value.map(|v| v.to_string());
+        }
+        self
+    }
+
+    // Send block_actions while the user types, e.g. to validate or filter as they go.
+    // The enclosing input block also needs `dispatch_action`.
+    pub fn dispatch_on(mut self, triggers: Vec<TriggerActionsOn>) -> Self {
+        let config = Some(DispatchActionConfig {
+            trigger_actions_on: triggers,
+        });
+        match &mut self {
+            Element::PlainTextInput(e) | Element::EmailTextInput(e) | Element::UrlTextInput(e) => {
+                e.dispatch_action_config = config
+            }
+            Element::NumberInput(e) => e.dispatch_action_config = config,
+            _ => {}
+        }
+        self
+    }
+
+    pub fn with_initial_date<S: Into<String>>(mut self, date: S) -> Self {
+        if let Element::Datepicker(e) = &mut self {
+            e.initial_date = Some(date.into());
+        }
+        self
+    }
+
+    pub fn with_initial_time<S: Into<String>>(mut self, time: S) -> Self {
+        if let Element::Timepicker(e) = &mut self {
+            e.initial_time = Some(time.into());
+        }
+        self
+    }
+
+    pub fn with_timezone<S: Into<String>>(mut self, timezone: S) -> Self {
+        if let Element::Timepicker(e) = &mut self {
+            e.timezone = Some(timezone.into());
+        }
+        self
+    }
+
+    pub fn with_initial_date_time(mut self, epoch_seconds: i64) -> Self {
+        if let Element::Datetimepicker(e) = &mut self {
+            e.initial_date_time = Some(epoch_seconds);
+        }
+        self
+    }
+
+    // For single-choice elements (static/external selects and radio buttons)
+    pub fn with_initial_option(mut self, option: OptionObject) -> Self {
+        match &mut self {
+            Element::RadioButtons(e) => e.initial_option = Some(option),
+            _ => {
+                if let Some(select) = self.select_mut() {
+                    select.initial_option = Some(option);
+                }
+            }
+        }
+        self
+    }
+
+    // For multi-choice elements (multi static/external selects and checkboxes)
+    pub fn with_initial_options(mut self, options: Vec<OptionObject>) -> Self {
+        match &mut self {
+            Element::Checkboxes(e) => e.initial_options = options,
+            _ => {
+                if let Some(select) = self.select_mut() {
+                    select.initial_options = options;
+                }
+            }
+        }
+        self
+    }
+
+    // Pre-selected user, conversation or channel IDs; single selects take the first
+    pub fn with_initial_ids<I>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let ids: Vec<String> = ids.into_iter().map(|id| id.into()).collect();
+        match &mut self {
+            Element::UsersSelect(e) => e.initial_user = ids.into_iter().next(),
+            Element::MultiUsersSelect(e) => e.initial_users = ids,
+            Element::ConversationsSelect(e) => e.initial_conversation = ids.into_iter().next(),
+            Element::MultiConversationsSelect(e) => e.initial_conversations = ids,
+            Element::ChannelsSelect(e) => e.initial_channel = ids.into_iter().next(),
+            Element::MultiChannelsSelect(e) => e.initial_channels = ids,
+            _ => {}
+        }
+        self
+    }
+
+    pub fn default_to_current_conversation(mut self) -> Self {
+        if let Element::ConversationsSelect(e) | Element::MultiConversationsSelect(e) = &mut self {
+            e.default_to_current_conversation = Some(true);
+        }
+        self
+    }
+
+    pub fn with_filter(mut self, filter: ConversationFilter) -> Self {
+        if let Element::ConversationsSelect(e) | Element::MultiConversationsSelect(e) = &mut self {
+            e.filter = Some(filter);
+        }
+        self
+    }
+
+    pub fn with_min_query_length(mut self, min_query_length: u32) -> Self {
+        if let Element::ExternalSelect(e) | Element::MultiExternalSelect(e) = &mut self {
+            e.min_query_length = Some(min_query_length);
+        }
+        self
+    }
+
+    pub fn with_max_selected_items(mut self, max_selected_items: u32) -> Self {
+        if let Some(select) = self.select_mut() {
+            select.max_selected_items = Some(max_selected_items);
+        }
+        self
+    }
+
+    pub fn with_filetypes<I>(mut self, filetypes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        if let Element::FileInput(e) = &mut self {
+            e.filetypes = filetypes.into_iter().map(|t| t.into()).collect();
+        }
+        self
+    }
+
+    pub fn with_max_files(mut self, max_files: u32) -> Self {
+        if let Element::FileInput(e) = &mut self {
+            e.max_files = Some(max_files);
+        }
+        self
+    }
+
+    // Only one element per view may have this set
+    pub fn focus_on_load(mut self) -> Self {
+        let focus = Some(true);
+        match &mut self {
+            Element::PlainTextInput(e) | Element::EmailTextInput(e) | Element::UrlTextInput(e) => {
+                e.focus_on_load = focus
+            }
+            Element::NumberInput(e) => e.focus_on_load = focus,
+            Element::Datepicker(e) => e.focus_on_load = focus,
+            Element::Timepicker(e) => e.focus_on_load = focus,
+            Element::Datetimepicker(e) => e.focus_on_load = focus,
+            Element::RadioButtons(e) | Element::Checkboxes(e) => e.focus_on_load = focus,
+            _ => {
+                if let Some(select) = self.select_mut() {
+                    select.focus_on_load = focus;
+                }
+            }
+        }
+        self
+    }
+
+    fn select_mut(&mut self) -> Option<&mut Select> {
+        match self {
+            Element::StaticSelect(e)
+            | Element::MultiStaticSelect(e)
+            | Element::ExternalSelect(e)
+            | Element::MultiExternalSelect(e)
+            | Element::UsersSelect(e)
+            | Element::MultiUsersSelect(e)
+            | Element::ConversationsSelect(e)
+            | Element::MultiConversationsSelect(e)
+            | Element::ChannelsSelect(e)
+            | Element::MultiChannelsSelect(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    // Option counts and lengths; radio buttons and checkboxes hold 10, overflow menus 2 to 5
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Element::RadioButtons(e) | Element::Checkboxes(e) => {
+                check_count(&e.options, 1, 10)?;
+                validate_options(&e.options)
+            }
+            Element::Overflow(e) => {
+                check_count(&e.options, 2, 5)?;
+                validate_options(&e.options)
+            }
+            _ => match self.select() {
+                Some(select) => {
+                    validate_options(&select.options)?;
+                    validate_option_groups(&select.option_groups)
+                }
+                None => Ok(()),
+            },
+        }
+    }
+
+    fn select(&self) -> Option<&Select> {
+        match self {
+            Element::StaticSelect(e)
+            | Element::MultiStaticSelect(e)
+            | Element::ExternalSelect(e)
+            | Element::MultiExternalSelect(e)
+            | Element::UsersSelect(e)
+            | Element::MultiUsersSelect(e)
+            | Element::ConversationsSelect(e)
+            | Element::MultiConversationsSelect(e)
+            | Element::ChannelsSelect(e)
+            | Element::MultiChannelsSelect(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub fn action_id(&self) -> &str {
+        match self {
+            Element::PlainTextInput(e) | Element::EmailTextInput(e) | Element::UrlTextInput(e) => &e.action_id,
+            Element::NumberInput(e) => &e.action_id,
+            Element::Datepicker(e) => &e.action_id,
+            Element::Timepicker(e) => &e.action_id,
+            Element::Datetimepicker(e) => &e.action_id,
+            Element::StaticSelect(e)
+            | Element::MultiStaticSelect(e)
+            | Element::ExternalSelect(e)
+            | Element::MultiExternalSelect(e)
+            | Element::UsersSelect(e)
+            | Element::MultiUsersSelect(e)
+            | Element::ConversationsSelect(e)
+            | Element::MultiConversationsSelect(e)
+            | Element::ChannelsSelect(e)
+            | Element::MultiChannelsSelect(e) => &e.action_id,
+            Element::RadioButtons(e) | Element::Checkboxes(e) | Element::Overflow(e) => &e.action_id,
+            Element::FileInput(e) => &e.action_id,
+        }
+    }
+}
+
+impl From<Element> for Value {
+    fn from(element: Element) -> Self {
+        serde_json::to_value(element).unwrap_or(Value::Null)
+    }
+}
+
+// When an input should send block_actions before the view is submitted
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DispatchActionConfig {
+    pub trigger_actions_on: Vec<TriggerActionsOn>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerActionsOn {
+    OnEnterPressed,
+    OnCharacterEntered,
+}
+
+// Shared by plain_text_input, email_text_input and url_text_input; multiline and the length
+// limits only apply to plain text
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TextInput {
+    pub action_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<Text>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dispatch_action_config: Option<DispatchActionConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_on_load: Option<bool>,
+}
+
+impl TextInput {
+    pub fn new<S: Into<String>>(action_id: S) -> Self {
+        Self {
+            action_id: action_id.into(),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NumberInput {
+    pub action_id: String,
+    pub is_decimal_allowed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<Text>,
+    // Numbers travel as strings in Block Kit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dispatch_action_config: Option<DispatchActionConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_on_load: Option<bool>,
+}
+
+impl NumberInput {
+    pub fn new<S: Into<String>>(action_id: S, is_decimal_allowed: bool) -> Self {
+        Self {
+            action_id: action_id.into(),
+            is_decimal_allowed,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DatePicker {
+    pub action_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<Text>,
+    // YYYY-MM-DD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_on_load: Option<bool>,
+}
+
+impl DatePicker {
+    pub fn new<S: Into<String>>(action_id: S) -> Self {
+        Self {
+            action_id: action_id.into(),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TimePicker {
+    pub action_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<Text>,
+    // HH:mm, 24 hour
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_time: Option<String>,
+    // IANA name; defaults to the user's own timezone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_on_load: Option<bool>,
+}
+
+impl TimePicker {
+    pub fn new<S: Into<String>>(action_id: S) -> Self {
+        Self {
+            action_id: action_id.into(),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DateTimePicker {
+    pub action_id: String,
+    // Epoch seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_date_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_on_load: Option<bool>,
+}
+
+impl DateTimePicker {
+    pub fn new<S: Into<String>>(action_id: S) -> Self {
+        Self {
+            action_id: action_id.into(),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConversationFilter {
+    // Any of "im", "mpim", "private", "public"
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub include: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_external_shared_channels: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_bot_users: Option<bool>,
+}
+
+// Every select menu, single and multi. Which `initial_*` field applies depends on the source:
+// static/external use options, users/conversations/channels use IDs.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Select {
+    pub action_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<Text>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub options: Vec<OptionObject>,
+    // Static selects take either options or option_groups
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub option_groups: Vec<OptionGroup>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_option: Option<OptionObject>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub initial_options: Vec<OptionObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_user: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub initial_users: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_conversation: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub initial_conversations: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_channel: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub initial_channels: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_to_current_conversation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<ConversationFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_query_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_selected_items: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_on_load: Option<bool>,
+}
+
+impl Select {
+    pub fn new<S: Into<String>>(action_id: S) -> Self {
+        Self {
+            action_id: action_id.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_options(mut self, options: Vec<OptionObject>) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_option_groups(mut self, option_groups: Vec<OptionGroup>) -> Self {
+        self.option_groups = option_groups;
+        self
+    }
+}
+
+// radio_buttons, checkboxes and overflow
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Choices {
+    pub action_id: String,
+    pub options: Vec<OptionObject>,
+    // Radio buttons only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_option: Option<OptionObject>,
+    // Checkboxes only
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub initial_options: Vec<OptionObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_on_load: Option<bool>,
+}
+
+impl Choices {
+    pub fn new<S: Into<String>>(action_id: S, options: Vec<OptionObject>) -> Self {
+        Self {
+            action_id: action_id.into(),
+            options,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct FileInput {
+    pub action_id: String,
+    // Extensions without the dot, e.g. "pdf"; empty accepts anything
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub filetypes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<u32>,
+}
+
+impl FileInput {
+    pub fn new<S: Into<String>>(action_id: S) -> Self {
+        Self {
+            action_id: action_id.into(),
+            ..Self::default()
+        }
+    }
+}
+
+fn check_count(options: &[OptionObject], min: usize, max: usize) -> Result<()> {
+    if options.len() < min || options.len() > max {
+        return Err(SlackError::InvalidOption(format!(
+            "{} options given, this element takes {} to {}",
+            options.len(),
+            min,
+            max
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::blocks::{Element, Text};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Holds one input element in a modal (or, with dispatch_action, anywhere interactive)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename = "input")]
+pub struct InputBlock {
+    pub label: Text,
+    pub element: Element,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Text>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    // Deliver block_actions as the element changes instead of only on submit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dispatch_action: bool,
+}
+
+impl InputBlock {
+    pub fn new<T: Into<Text>>(label: T, element: Element) -> Self {
+        Self {
+            label: label.into(),
+            element,
+            block_id: None,
+            hint: None,
+            optional: false,
+            dispatch_action: false,
+        }
+    }
+
+    pub fn with_block_id<S: Into<String>>(mut self, block_id: S) -> Self {
+        self.block_id = Some(block_id.into());
+        self
+    }
+
+    pub fn with_hint<T: Into<Text>>(mut self, hint: T) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    pub fn dispatch_action(mut self) -> Self {
+        self.dispatch_action = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename = "actions")]
+pub struct ActionsBlock {
+    pub elements: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_id: Option<String>,
+}
+
+impl ActionsBlock {
+    // Takes `Value`s so buttons and other raw elements can sit next to typed ones
+    pub fn new<I>(elements: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Value>,
+    {
+        Self {
+            elements: elements.into_iter().map(Into::into).collect(),
+            block_id: None,
+        }
+    }
+
+    pub fn with_block_id<S: Into<String>>(mut self, block_id: S) -> Self {
+        self.block_id = Some(block_id.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename = "section")]
+pub struct SectionBlock {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Text>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<Text>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accessory: Option<Element>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_id: Option<String>,
+}
+
+impl SectionBlock {
+    pub fn new<T: Into<Text>>(text: T) -> Self {
+        Self {
+            text: Some(text.into()),
+            fields: Vec::new(),
+            accessory: None,
+            block_id: None,
+        }
+    }
+
+    pub fn with_fields(mut self, fields: Vec<Text>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    pub fn with_accessory(mut self, accessory: Element) -> Self {
+        self.accessory = Some(accessory);
+        self
+    }
+
+    pub fn with_block_id<S: Into<String>>(mut self, block_id: S) -> Self {
+        self.block_id = Some(block_id.into());
+        self
+    }
+}
+
+impl From<InputBlock> for Value {
+    fn from(block: InputBlock) -> Self {
+        serde_json::to_value(block).unwrap_or(Value::Null)
+    }
+}
+
+impl From<ActionsBlock> for Value {
+    fn from(block: ActionsBlock) -> Self {
+        serde_json::to_value(block).unwrap_or(Value::Null)
+    }
+}
+
+impl From<SectionBlock> for Value {
+    fn from(block: SectionBlock) -> Self {
+        serde_json::to_value(block).unwrap_or(Value::Null)
+    }
+}
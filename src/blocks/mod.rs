@@ -0,0 +1,64 @@
+// Typed Block Kit surfaces and composition objects. Blocks themselves stay `Value` so anything
+// Slack adds can be sent without waiting for a release.
+pub mod attachments;
+pub mod elements;
+pub mod layout;
+pub mod options;
+pub mod view;
+
+pub use attachments::{attachment_to_blocks, migrate_message};
+pub use elements::{ConversationFilter, Element, TriggerActionsOn};
+pub use layout::{ActionsBlock, InputBlock, SectionBlock};
+pub use options::{OptionGroup, OptionObject};
+pub use view::{View, ViewType};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Text {
+    PlainText {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        emoji: Option<bool>,
+    },
+    Mrkdwn {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        verbatim: Option<bool>,
+    },
+}
+
+impl Text {
+    pub fn plain<S: Into<String>>(text: S) -> Self {
+        Text::PlainText {
+            text: text.into(),
+            emoji: None,
+        }
+    }
+
+    pub fn mrkdwn<S: Into<String>>(text: S) -> Self {
+        Text::Mrkdwn {
+            text: text.into(),
+            verbatim: None,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        match self {
+            Text::PlainText { text, .. } | Text::Mrkdwn { text, .. } => text,
+        }
+    }
+}
+
+impl From<&str> for Text {
+    fn from(text: &str) -> Self {
+        Text::plain(text)
+    }
+}
+
+impl From<String> for Text {
+    fn from(text: String) -> Self {
+        Text::plain(text)
+    }
+}
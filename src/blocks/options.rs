@@ -0,0 +1,157 @@
+use crate::blocks::Text;
+use crate::error::{Result, SlackError};
+use serde::{Deserialize, Serialize};
+
+// Slack's limits for option objects and the menus that hold them
+pub const MAX_OPTION_TEXT: usize = 75;
+pub const MAX_OPTION_VALUE: usize = 150;
+pub const MAX_OPTIONS: usize = 100;
+pub const MAX_OPTION_GROUPS: usize = 100;
+
+// An option object as used by selects, radio buttons, checkboxes and overflow menus
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OptionObject {
+    pub text: Text,
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Text>,
+    // Only for overflow menus
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl OptionObject {
+    // Text over 75 characters is cut with an ellipsis (it is only a label); use `try_new`
+    // to get an error instead
+    pub fn new<T: Into<Text>, V: Into<String>>(text: T, value: V) -> Self {
+        Self {
+            text: truncate_text(text.into(), MAX_OPTION_TEXT),
+            value: value.into(),
+            description: None,
+            url: None,
+        }
+    }
+
+    // Values are what the app gets back, so they are never truncated; both constructors
+    // leave an over-long value for `validate` to report
+    pub fn try_new<T: Into<Text>, V: Into<String>>(text: T, value: V) -> Result<Self> {
+        let option = Self {
+            text: text.into(),
+            value: value.into(),
+            description: None,
+            url: None,
+        };
+        option.validate()?;
+        Ok(option)
+    }
+
+    pub fn with_description<T: Into<Text>>(mut self, description: T) -> Self {
+        self.description = Some(truncate_text(description.into(), MAX_OPTION_TEXT));
+        self
+    }
+
+    pub fn with_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        check_length("option text", self.text.text(), MAX_OPTION_TEXT)?;
+        check_length("option value", &self.value, MAX_OPTION_VALUE)?;
+        if let Some(description) = &self.description {
+            check_length("option description", description.text(), MAX_OPTION_TEXT)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OptionGroup {
+    pub label: Text,
+    pub options: Vec<OptionObject>,
+}
+
+impl OptionGroup {
+    pub fn new<T: Into<Text>>(label: T, options: Vec<OptionObject>) -> Self {
+        Self {
+            label: truncate_text(label.into(), MAX_OPTION_TEXT),
+            options,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        check_length("option group label", self.label.text(), MAX_OPTION_TEXT)?;
+        validate_options(&self.options)
+    }
+
+    // Splits a list too long for one menu into groups of at most 100, labelled by `label`
+    // with the 1-based group number. Errors when even 100 groups cannot hold them.
+    pub fn chunked<F>(options: Vec<OptionObject>, label: F) -> Result<Vec<OptionGroup>>
+    where
+        F: Fn(usize) -> String,
+    {
+        let max = MAX_OPTIONS * MAX_OPTION_GROUPS;
+        if options.len() > max {
+            return Err(SlackError::InvalidOption(format!(
+                "{} options do not fit in {} groups of {}",
+                options.len(),
+                MAX_OPTION_GROUPS,
+                MAX_OPTIONS
+            )));
+        }
+
+        Ok(options
+            .chunks(MAX_OPTIONS)
+            .enumerate()
+            .map(|(index, chunk)| OptionGroup::new(label(index + 1), chunk.to_vec()))
+            .collect())
+    }
+}
+
+pub fn validate_options(options: &[OptionObject]) -> Result<()> {
+    if options.len() > MAX_OPTIONS {
+        return Err(SlackError::InvalidOption(format!(
+            "{} options given, menus hold at most {}; group them with OptionGroup::chunked",
+            options.len(),
+            MAX_OPTIONS
+        )));
+    }
+    options.iter().try_for_each(OptionObject::validate)
+}
+
+pub fn validate_option_groups(groups: &[OptionGroup]) -> Result<()> {
+    if groups.len() > MAX_OPTION_GROUPS {
+        return Err(SlackError::InvalidOption(format!(
+            "{} option groups given, menus hold at most {}",
+            groups.len(),
+            MAX_OPTION_GROUPS
+        )));
+    }
+    groups.iter().try_for_each(OptionGroup::validate)
+}
+
+fn check_length(what: &str, text: &str, max: usize) -> Result<()> {
+    let length = text.chars().count();
+    if length > max {
+        return Err(SlackError::InvalidOption(format!(
+            "{} is {} characters, the limit is {}: {:?}",
+            what, length, max, text
+        )));
+    }
+    Ok(())
+}
+
+fn truncate_text(text: Text, max: usize) -> Text {
+    if text.text().chars().count() <= max {
+        return text;
+    }
+
+    let truncated: String = text.text().chars().take(max - 1).chain(std::iter::once('…')).collect();
+    match text {
+        Text::PlainText { emoji, .. } => Text::PlainText { text: truncated, emoji },
+        Text::Mrkdwn { verbatim, .. } => Text::Mrkdwn {
+            text: truncated,
+            verbatim,
+        },
+    }
+}
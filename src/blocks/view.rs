@@ -0,0 +1,160 @@
+use crate::blocks::Text;
+use crate::error::{Result, SlackError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewType {
+    Modal,
+    Home,
+}
+
+// A view to send with views.open/push/update/publish. Title, submit and close only apply to modals.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct View {
+    #[serde(rename = "type")]
+    pub view_type: ViewType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<Text>,
+    pub blocks: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submit: Option<Text>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close: Option<Text>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_metadata: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    // Ask for a view_closed request when the user dismisses the modal
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub notify_on_close: bool,
+    // Closing dismisses the whole stack, not just this view
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub clear_on_close: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub submit_disabled: bool,
+}
+
+// Slack's limits on private_metadata and external_id
+const MAX_PRIVATE_METADATA: usize = 3000;
+const MAX_EXTERNAL_ID: usize = 255;
+
+impl View {
+    pub fn modal<T: Into<Text>>(title: T) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..Self::new(ViewType::Modal)
+        }
+    }
+
+    pub fn home() -> Self {
+        Self::new(ViewType::Home)
+    }
+
+    fn new(view_type: ViewType) -> Self {
+        Self {
+            view_type,
+            title: None,
+            blocks: Vec::new(),
+            submit: None,
+            close: None,
+            private_metadata: None,
+            callback_id: None,
+            external_id: None,
+            notify_on_close: false,
+            clear_on_close: false,
+            submit_disabled: false,
+        }
+    }
+
+    pub fn with_blocks(mut self, blocks: Vec<Value>) -> Self {
+        self.blocks = blocks;
+        self
+    }
+
+    pub fn with_block<B: Into<Value>>(mut self, block: B) -> Self {
+        self.blocks.push(block.into());
+        self
+    }
+
+    pub fn with_submit<T: Into<Text>>(mut self, submit: T) -> Self {
+        self.submit = Some(submit.into());
+        self
+    }
+
+    pub fn with_close<T: Into<Text>>(mut self, close: T) -> Self {
+        self.close = Some(close.into());
+        self
+    }
+
+    pub fn with_private_metadata<S: Into<String>>(mut self, private_metadata: S) -> Self {
+        self.private_metadata = Some(private_metadata.into());
+        self
+    }
+
+    pub fn with_callback_id<S: Into<String>>(mut self, callback_id: S) -> Self {
+        self.callback_id = Some(callback_id.into());
+        self
+    }
+
+    // Unique per workspace; lets later invocations update the view with
+    // `update_view_by_external_id` instead of keeping its view_id
+    pub fn with_external_id<S: Into<String>>(mut self, external_id: S) -> Self {
+        self.external_id = Some(external_id.into());
+        self
+    }
+
+    pub fn notify_on_close(mut self) -> Self {
+        self.notify_on_close = true;
+        self
+    }
+
+    pub fn clear_on_close(mut self) -> Self {
+        self.clear_on_close = true;
+        self
+    }
+
+    pub fn submit_disabled(mut self) -> Self {
+        self.submit_disabled = true;
+        self
+    }
+
+    // Catches the mistakes Slack would otherwise reject with a bare `invalid_arguments`
+    pub fn validate(&self) -> Result<()> {
+        if self.view_type == ViewType::Modal {
+            match &self.title {
+                None => return Err(SlackError::InvalidView("modals need a title".to_string())),
+                Some(Text::Mrkdwn { .. }) => {
+                    return Err(SlackError::InvalidView("modal titles must be plain_text".to_string()))
+                }
+                Some(title) if title.text().chars().count() > 24 => {
+                    return Err(SlackError::InvalidView(
+                        "modal titles are limited to 24 characters".to_string(),
+                    ))
+                }
+                _ => {}
+            }
+        }
+        if let Some(metadata) = &self.private_metadata {
+            if metadata.len() > MAX_PRIVATE_METADATA {
+                return Err(SlackError::InvalidView(format!(
+                    "private_metadata is limited to {} characters",
+                    MAX_PRIVATE_METADATA
+                )));
+            }
+        }
+        if self.external_id.as_ref().is_some_and(|id| id.len() > MAX_EXTERNAL_ID) {
+            return Err(SlackError::InvalidView(format!(
+                "external_id is limited to {} characters",
+                MAX_EXTERNAL_ID
+            )));
+        }
+        if self.blocks.len() > 100 {
+            return Err(SlackError::InvalidView("views are limited to 100 blocks".to_string()));
+        }
+        Ok(())
+    }
+}
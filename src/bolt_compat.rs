@@ -0,0 +1,141 @@
+// Bolt-style names for porting apps from Bolt for JavaScript or Python. Most Bolt listener
+// arguments already exist under the same name; this module fills in the rest.
+//
+//   Bolt                         this crate
+//   ----                         ----------
+//   app.event / command /        same names
+//     action / view / shortcut
+//   app.message("hi")            app.message_contains("hi", ...)
+//   app.use(fn)                  app.use_middleware(fn)
+//   await ack()                  ctx.ack()            (BoltContext)
+//   await ack({...})             ctx.ack_with(json!({...}))
+//   await say("hi")              ctx.say("hi")        (BoltContext)
+//   await respond({...})         ctx.respond(json!({...}))
+//   client.chat.postMessage      ctx.client().post_message(...)
+//   body / payload               ctx.body / ctx.payload
+//   context.botUserId etc.       ctx.bot_user_id() etc. (BoltContext)
+//   logger                       ctx.logger (a tracing span)
+//
+// Unlike Bolt, a listener returns its ack as `Ok(response)`; `ack()` hands back the response
+// to return.
+use crate::context::Context;
+use crate::error::{Result, SlackError};
+use crate::request::SlackRequestBody;
+use crate::response::SlackResponse;
+use crate::secret::SecretString;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+// What Bolt's `say` accepts: a bare string or a message object
+#[derive(Debug, Clone)]
+pub struct BoltMessage(Value);
+
+impl BoltMessage {
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+}
+
+impl From<&str> for BoltMessage {
+    fn from(text: &str) -> Self {
+        Self(json!({ "text": text }))
+    }
+}
+
+impl From<String> for BoltMessage {
+    fn from(text: String) -> Self {
+        Self(json!({ "text": text }))
+    }
+}
+
+impl From<Value> for BoltMessage {
+    fn from(message: Value) -> Self {
+        match message {
+            Value::String(text) => Self::from(text),
+            message => Self(message),
+        }
+    }
+}
+
+#[async_trait]
+pub trait BoltContext {
+    async fn ack(&self) -> Result<SlackResponse>;
+
+    // ack({ response_action: "errors", errors }) and the like
+    async fn ack_with(&self, body: Value) -> Result<SlackResponse>;
+
+    // Posts to the channel the request came from; returns chat.postMessage's response
+    async fn say<M: Into<BoltMessage> + Send>(&self, message: M) -> Result<Value>;
+
+    fn bot_user_id(&self) -> Option<&str>;
+    fn bot_token(&self) -> Option<&str>;
+    fn user_token(&self) -> Option<&str>;
+    fn team_id(&self) -> Option<&str>;
+    fn enterprise_id(&self) -> Option<&str>;
+    fn user_id(&self) -> Option<&str>;
+    fn channel_id(&self) -> Option<&str>;
+}
+
+#[async_trait]
+impl BoltContext for Context {
+    async fn ack(&self) -> Result<SlackResponse> {
+        self.ack.empty().await
+    }
+
+    async fn ack_with(&self, body: Value) -> Result<SlackResponse> {
+        self.ack.json(body).await
+    }
+
+    async fn say<M: Into<BoltMessage> + Send>(&self, message: M) -> Result<Value> {
+        let channel = self.channel_id().ok_or_else(|| SlackError::UnexpectedPayload {
+            expected: "request in a channel".to_string(),
+            found: format!("{} request without a channel", self.request.body.kind()),
+        })?;
+
+        let mut payload = message.into().into_value();
+        if !payload.is_object() {
+            return Err(SlackError::UnexpectedPayload {
+                expected: "string or message object".to_string(),
+                found: payload.to_string(),
+            });
+        }
+        payload["channel"] = json!(channel);
+        self.client.call("chat.postMessage", payload).await
+    }
+
+    // From the installation, or the event envelope's authorizations for single-workspace apps
+    fn bot_user_id(&self) -> Option<&str> {
+        self.installation()
+            .and_then(|installation| installation.bot_user_id.as_deref())
+            .or_else(|| match &self.request.body {
+                SlackRequestBody::Event(event_req) => event_req.bot_user_id(),
+                _ => None,
+            })
+    }
+
+    fn bot_token(&self) -> Option<&str> {
+        self.installation()
+            .and_then(|installation| installation.bot_token.as_ref().map(SecretString::expose))
+    }
+
+    fn user_token(&self) -> Option<&str> {
+        self.installation()
+            .and_then(|installation| installation.user_token.as_ref().map(SecretString::expose))
+    }
+
+    fn team_id(&self) -> Option<&str> {
+        self.request.team_id()
+    }
+
+    fn enterprise_id(&self) -> Option<&str> {
+        self.request.enterprise_id()
+    }
+
+    fn user_id(&self) -> Option<&str> {
+        self.request.user_id()
+    }
+
+    fn channel_id(&self) -> Option<&str> {
+        self.request.channel_id()
+    }
+}
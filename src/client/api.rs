@@ -0,0 +1,350 @@
+use crate::client::conversations::{Channel, ConversationInfoResponse};
+use crate::client::files::{File, FileInfoResponse};
+use crate::client::lists::{
+    CreateListItemRequest, ListItem, ListItemInfoResponse, ListItemResponse, ListItemsRequest, ListItemsResponse,
+    UpdateListItemsRequest,
+};
+use crate::client::migration::{MigrationExchangeRequest, MigrationExchangeResponse, MAX_EXCHANGE_USERS};
+use crate::client::triggers::{
+    CreateTriggerRequest, ListTriggersRequest, ListTriggersResponse, TriggerResponse, UpdateTriggerRequest,
+};
+use crate::client::unfurl::UnfurlRequest;
+use crate::client::users::{Presence, SetProfileRequest, UserPresence, UserProfile, UserProfileResponse};
+use crate::client::{
+    ApiResponse, DeleteMessageRequest, MessageMetadata, DeleteMessageResponse, FunctionCompleteErrorRequest,
+    FunctionCompleteSuccessRequest, PostMessageRequest, PostMessageResponse, StepCompletedRequest, StepFailedRequest,
+    UpdateMessageRequest, UpdateMessageResponse, UpdateStepRequest, ViewResponse,
+};
+use crate::blocks::View;
+use crate::error::{Result, SlackError};
+use crate::request::payloads::ViewPayload;
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+// How far back `find_message_by_external_id` looks, in pages of conversations.history
+const HISTORY_PAGE_SIZE: usize = 200;
+const MAX_EXTERNAL_ID_SEARCH: usize = 1000;
+
+// The Web API as seen by handlers. Implementations only provide `call`; every typed
+// method is built on top of it, so a test double sees exactly what would go over the wire.
+#[async_trait]
+pub trait SlackApi: Send + Sync + Debug {
+    // Returns the response body once its `ok` flag has been checked
+    async fn call(&self, method: &str, payload: Value) -> Result<Value>;
+
+    // The same client acting with a different token (e.g. a function execution token)
+    fn with_token(&self, token: String) -> Arc<dyn SlackApi>;
+
+    // Fetches a `url_private` file with this client's token attached
+    async fn download(&self, url: &str) -> Result<Bytes> {
+        Err(SlackError::Config(format!("{:?} cannot download {}", self, url)))
+    }
+
+    // Posts to an interaction's response_url, which needs no token and outlives the ack
+    // (up to five uses within 30 minutes). Incoming webhook URLs take the same kind of post.
+    async fn respond(&self, response_url: &str, payload: Value) -> Result<()> {
+        let _ = payload;
+        Err(SlackError::Config(format!("{:?} cannot post to {}", self, response_url)))
+    }
+
+    async fn file_info(&self, file_id: &str) -> Result<File> {
+        let response: FileInfoResponse = call_typed(self, "files.info", &json!({ "file": file_id })).await?;
+        Ok(response.file)
+    }
+
+    // Accepts a file ID (looked up with files.info) or a url_private(_download) URL
+    async fn download_file(&self, file_id_or_url: &str) -> Result<Bytes> {
+        if file_id_or_url.starts_with("https://") || file_id_or_url.starts_with("http://") {
+            return self.download(file_id_or_url).await;
+        }
+
+        let file = self.file_info(file_id_or_url).await?;
+        let url = file.download_url().ok_or_else(|| SlackError::SlackApi {
+            code: "file_not_downloadable".to_string(),
+            message: format!("File {} has no private download URL", file.id),
+        })?;
+        self.download(url).await
+    }
+
+    async fn post_message(&self, request: &PostMessageRequest) -> Result<PostMessageResponse> {
+        call_typed(self, "chat.postMessage", request).await
+    }
+
+    async fn update_message(&self, request: &UpdateMessageRequest) -> Result<UpdateMessageResponse> {
+        call_typed(self, "chat.update", request).await
+    }
+
+    // The newest message in the channel tagged with `MessageMetadata::external_id`, searching
+    // the most recent MAX_EXTERNAL_ID_SEARCH messages
+    async fn find_message_by_external_id(&self, channel: &str, external_id: &str) -> Result<Option<Value>> {
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_EXTERNAL_ID_SEARCH / HISTORY_PAGE_SIZE {
+            let mut payload = json!({ "channel": channel, "limit": HISTORY_PAGE_SIZE, "include_all_metadata": true });
+            if let Some(cursor) = &cursor {
+                payload["cursor"] = json!(cursor);
+            }
+            let response = self.call("conversations.history", payload).await?;
+
+            let messages = response.get("messages").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            let found = messages.into_iter().find(|message| {
+                message
+                    .get("metadata")
+                    .and_then(|metadata| serde_json::from_value::<MessageMetadata>(metadata.clone()).ok())
+                    .is_some_and(|metadata| metadata.external_id_value() == Some(external_id))
+            });
+            if found.is_some() {
+                return Ok(found);
+            }
+
+            cursor = response
+                .pointer("/response_metadata/next_cursor")
+                .and_then(|v| v.as_str())
+                .filter(|cursor| !cursor.is_empty())
+                .map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    async fn unfurl(&self, request: &UnfurlRequest) -> Result<ApiResponse> {
+        call_typed(self, "chat.unfurl", request).await
+    }
+
+    async fn delete_message(&self, request: &DeleteMessageRequest) -> Result<DeleteMessageResponse> {
+        call_typed(self, "chat.delete", request).await
+    }
+
+    async fn open_view(&self, trigger_id: &str, view: &View) -> Result<ViewPayload> {
+        view.validate()?;
+        let response: ViewResponse =
+            call_typed(self, "views.open", &json!({ "trigger_id": trigger_id, "view": view })).await?;
+        Ok(response.view)
+    }
+
+    async fn push_view(&self, trigger_id: &str, view: &View) -> Result<ViewPayload> {
+        view.validate()?;
+        let response: ViewResponse =
+            call_typed(self, "views.push", &json!({ "trigger_id": trigger_id, "view": view })).await?;
+        Ok(response.view)
+    }
+
+    // Passing the hash from the view being replaced makes Slack reject stale updates with `hash_conflict`
+    async fn update_view(&self, view_id: &str, view: &View, hash: Option<&str>) -> Result<ViewPayload> {
+        view.validate()?;
+        let mut payload = json!({ "view_id": view_id, "view": view });
+        if let Some(hash) = hash {
+            payload["hash"] = json!(hash);
+        }
+        let response: ViewResponse = call_typed(self, "views.update", &payload).await?;
+        Ok(response.view)
+    }
+
+    // views.update addressed by the external_id the view was opened with, so a later
+    // invocation can reach a modal without having kept its view_id
+    async fn update_view_by_external_id(&self, external_id: &str, view: &View, hash: Option<&str>) -> Result<ViewPayload> {
+        view.validate()?;
+        let mut payload = json!({ "external_id": external_id, "view": view });
+        if let Some(hash) = hash {
+            payload["hash"] = json!(hash);
+        }
+        let response: ViewResponse = call_typed(self, "views.update", &payload).await?;
+        Ok(response.view)
+    }
+
+    async fn publish_view(&self, user_id: &str, view: &View, hash: Option<&str>) -> Result<ViewPayload> {
+        view.validate()?;
+        let mut payload = json!({ "user_id": user_id, "view": view });
+        if let Some(hash) = hash {
+            payload["hash"] = json!(hash);
+        }
+        let response: ViewResponse = call_typed(self, "views.publish", &payload).await?;
+        Ok(response.view)
+    }
+
+    async fn update_step(&self, request: &UpdateStepRequest) -> Result<ApiResponse> {
+        call_typed(self, "workflows.updateStep", request).await
+    }
+
+    async fn step_completed(&self, request: &StepCompletedRequest) -> Result<ApiResponse> {
+        call_typed(self, "workflows.stepCompleted", request).await
+    }
+
+    async fn step_failed(&self, request: &StepFailedRequest) -> Result<ApiResponse> {
+        call_typed(self, "workflows.stepFailed", request).await
+    }
+
+    async fn complete_function_success(&self, request: &FunctionCompleteSuccessRequest) -> Result<ApiResponse> {
+        call_typed(self, "functions.completeSuccess", request).await
+    }
+
+    async fn complete_function_error(&self, request: &FunctionCompleteErrorRequest) -> Result<ApiResponse> {
+        call_typed(self, "functions.completeError", request).await
+    }
+
+    async fn conversation_info(&self, channel: &str) -> Result<Channel> {
+        let response: ConversationInfoResponse =
+            call_typed(self, "conversations.info", &json!({ "channel": channel })).await?;
+        Ok(response.channel)
+    }
+
+    async fn create_conversation(&self, name: &str, is_private: bool) -> Result<Channel> {
+        let response: ConversationInfoResponse =
+            call_typed(self, "conversations.create", &json!({ "name": name, "is_private": is_private })).await?;
+        Ok(response.channel)
+    }
+
+    // With `force`, users who are already members are skipped instead of failing the whole call
+    async fn invite_to_conversation(&self, channel: &str, users: &[String], force: bool) -> Result<Channel> {
+        let payload = json!({ "channel": channel, "users": users.join(","), "force": force });
+        let response: ConversationInfoResponse = call_typed(self, "conversations.invite", &payload).await?;
+        Ok(response.channel)
+    }
+
+    async fn set_topic(&self, channel: &str, topic: &str) -> Result<ApiResponse> {
+        call_typed(self, "conversations.setTopic", &json!({ "channel": channel, "topic": topic })).await
+    }
+
+    async fn set_purpose(&self, channel: &str, purpose: &str) -> Result<ApiResponse> {
+        call_typed(self, "conversations.setPurpose", &json!({ "channel": channel, "purpose": purpose })).await
+    }
+
+    async fn rename_conversation(&self, channel: &str, name: &str) -> Result<Channel> {
+        let response: ConversationInfoResponse =
+            call_typed(self, "conversations.rename", &json!({ "channel": channel, "name": name })).await?;
+        Ok(response.channel)
+    }
+
+    async fn kick_from_conversation(&self, channel: &str, user: &str) -> Result<ApiResponse> {
+        call_typed(self, "conversations.kick", &json!({ "channel": channel, "user": user })).await
+    }
+
+    async fn join_conversation(&self, channel: &str) -> Result<Channel> {
+        let response: ConversationInfoResponse =
+            call_typed(self, "conversations.join", &json!({ "channel": channel })).await?;
+        Ok(response.channel)
+    }
+
+    async fn leave_conversation(&self, channel: &str) -> Result<ApiResponse> {
+        call_typed(self, "conversations.leave", &json!({ "channel": channel })).await
+    }
+
+    // Batches requests over migration.exchange's 400 ID limit and merges the results
+    async fn exchange_user_ids(&self, request: &MigrationExchangeRequest) -> Result<MigrationExchangeResponse> {
+        let mut merged = MigrationExchangeResponse {
+            ok: true,
+            ..MigrationExchangeResponse::default()
+        };
+
+        for users in request.users.chunks(MAX_EXCHANGE_USERS) {
+            let batch = MigrationExchangeRequest {
+                users: users.to_vec(),
+                ..request.clone()
+            };
+            let response: MigrationExchangeResponse = call_typed(self, "migration.exchange", &batch).await?;
+            merged.team_id = response.team_id.or(merged.team_id);
+            merged.enterprise_id = response.enterprise_id.or(merged.enterprise_id);
+            merged.user_id_map.extend(response.user_id_map);
+            merged.invalid_user_ids.extend(response.invalid_user_ids);
+        }
+
+        Ok(merged)
+    }
+
+    async fn create_trigger(&self, request: &CreateTriggerRequest) -> Result<TriggerResponse> {
+        call_typed(self, "workflows.triggers.create", request).await
+    }
+
+    async fn update_trigger(&self, request: &UpdateTriggerRequest) -> Result<TriggerResponse> {
+        call_typed(self, "workflows.triggers.update", request).await
+    }
+
+    async fn delete_trigger(&self, trigger_id: &str) -> Result<ApiResponse> {
+        call_typed(self, "workflows.triggers.delete", &json!({ "trigger_id": trigger_id })).await
+    }
+
+    async fn list_triggers(&self, request: &ListTriggersRequest) -> Result<ListTriggersResponse> {
+        call_typed(self, "workflows.triggers.list", request).await
+    }
+
+    async fn create_list_item(&self, request: &CreateListItemRequest) -> Result<ListItem> {
+        let response: ListItemResponse = call_typed(self, "slackLists.items.create", request).await?;
+        Ok(response.item)
+    }
+
+    async fn update_list_items(&self, request: &UpdateListItemsRequest) -> Result<ApiResponse> {
+        call_typed(self, "slackLists.items.update", request).await
+    }
+
+    async fn delete_list_item(&self, list_id: &str, item_id: &str) -> Result<ApiResponse> {
+        call_typed(self, "slackLists.items.delete", &json!({ "list_id": list_id, "id": item_id })).await
+    }
+
+    async fn delete_list_items(&self, list_id: &str, item_ids: &[String]) -> Result<ApiResponse> {
+        call_typed(self, "slackLists.items.deleteMultiple", &json!({ "list_id": list_id, "ids": item_ids })).await
+    }
+
+    // The item along with the list's metadata and column schema
+    async fn list_item_info(&self, list_id: &str, item_id: &str) -> Result<ListItemInfoResponse> {
+        call_typed(self, "slackLists.items.info", &json!({ "list_id": list_id, "id": item_id })).await
+    }
+
+    async fn list_items(&self, request: &ListItemsRequest) -> Result<ListItemsResponse> {
+        call_typed(self, "slackLists.items.list", request).await
+    }
+
+    // The profile and presence methods act on the token's user, so they need a user token
+    // (`Context::user_client`); bot tokens get `not_allowed_token_type`.
+    async fn user_profile(&self, user: Option<&str>) -> Result<UserProfile> {
+        let payload = user.map(|user| json!({ "user": user })).unwrap_or_else(|| json!({}));
+        let response: UserProfileResponse = call_typed(self, "users.profile.get", &payload).await?;
+        Ok(response.profile)
+    }
+
+    async fn set_user_profile(&self, request: &SetProfileRequest) -> Result<UserProfile> {
+        let response: UserProfileResponse = call_typed(self, "users.profile.set", request).await?;
+        Ok(response.profile)
+    }
+
+    async fn set_status(&self, text: &str, emoji: &str, expires_at: Option<DateTime<Utc>>) -> Result<UserProfile> {
+        let mut profile = UserProfile::new().with_status(text, emoji);
+        profile.status_expiration = Some(expires_at.map(|t| t.timestamp()).unwrap_or(0));
+        self.set_user_profile(&SetProfileRequest::profile(profile)).await
+    }
+
+    async fn clear_status(&self) -> Result<UserProfile> {
+        self.set_status("", "", None).await
+    }
+
+    async fn user_presence(&self, user: Option<&str>) -> Result<UserPresence> {
+        let payload = user.map(|user| json!({ "user": user })).unwrap_or_else(|| json!({}));
+        call_typed(self, "users.getPresence", &payload).await
+    }
+
+    async fn set_presence(&self, presence: Presence) -> Result<ApiResponse> {
+        call_typed(self, "users.setPresence", &json!({ "presence": presence })).await
+    }
+
+    // users.setPhoto takes a multipart upload, which `call` cannot carry
+    async fn delete_photo(&self) -> Result<ApiResponse> {
+        call_typed(self, "users.deletePhoto", &json!({})).await
+    }
+}
+
+pub async fn call_typed<A, T, R>(api: &A, method: &str, request: &T) -> Result<R>
+where
+    A: SlackApi + ?Sized,
+    T: Serialize + Sync + ?Sized,
+    R: DeserializeOwned,
+{
+    let payload = serde_json::to_value(request)?;
+    let response = api.call(method, payload).await?;
+    Ok(serde_json::from_value(response)?)
+}
@@ -0,0 +1,142 @@
+use crate::client::api::SlackApi;
+use crate::client::conversations::Channel;
+use crate::client::PostMessageRequest;
+use crate::error::{Result, SlackError};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::debug;
+
+// conversations.invite takes at most this many users per call
+const INVITE_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct ChannelSetup {
+    pub name: String,
+    pub is_private: bool,
+    pub users: Vec<String>,
+    pub topic: Option<String>,
+    pub purpose: Option<String>,
+    pub welcome_text: Option<String>,
+    pub welcome_blocks: Option<Vec<Value>>,
+}
+
+impl ChannelSetup {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn private(mut self) -> Self {
+        self.is_private = true;
+        self
+    }
+
+    pub fn with_users<I>(mut self, users: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.users = users.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
+    pub fn with_topic<S: Into<String>>(mut self, topic: S) -> Self {
+        self.topic = Some(topic.into());
+        self
+    }
+
+    pub fn with_purpose<S: Into<String>>(mut self, purpose: S) -> Self {
+        self.purpose = Some(purpose.into());
+        self
+    }
+
+    pub fn with_welcome<S: Into<String>>(mut self, text: S) -> Self {
+        self.welcome_text = Some(text.into());
+        self
+    }
+
+    // Blocks for the welcome message; the text becomes the notification fallback
+    pub fn with_welcome_blocks(mut self, blocks: Vec<Value>) -> Self {
+        self.welcome_blocks = Some(blocks);
+        self
+    }
+}
+
+// Common provisioning sequences for onboarding bots, built from the SlackApi conversation methods
+#[derive(Debug, Clone)]
+pub struct ChannelAdmin {
+    client: Arc<dyn SlackApi>,
+}
+
+impl ChannelAdmin {
+    pub fn new(client: Arc<dyn SlackApi>) -> Self {
+        Self { client }
+    }
+
+    // Creates the channel, invites the users, sets topic and purpose, then posts the welcome
+    // message. Stops at the first failing step; the channel is left as far as it got.
+    pub async fn provision(&self, setup: &ChannelSetup) -> Result<Channel> {
+        let mut channel = self.client.create_conversation(&setup.name, setup.is_private).await?;
+        debug!("Created channel {} ({})", setup.name, channel.id);
+
+        if !setup.users.is_empty() {
+            channel = self.invite(&channel.id, &setup.users).await?;
+        }
+
+        self.describe(&channel.id, setup.topic.as_deref(), setup.purpose.as_deref()).await?;
+
+        if setup.welcome_text.is_some() || setup.welcome_blocks.is_some() {
+            self.client
+                .post_message(&PostMessageRequest {
+                    channel: channel.id.clone(),
+                    text: setup.welcome_text.clone(),
+                    blocks: setup.welcome_blocks.clone(),
+                    thread_ts: None,
+                    metadata: None,
+                })
+                .await?;
+        }
+
+        Ok(channel)
+    }
+
+    // Invites in batches, skipping users who are already members instead of failing the batch
+    pub async fn invite(&self, channel: &str, users: &[String]) -> Result<Channel> {
+        let mut result = None;
+        for batch in users.chunks(INVITE_BATCH_SIZE) {
+            result = Some(self.client.invite_to_conversation(channel, batch, true).await?);
+        }
+
+        match result {
+            Some(channel) => Ok(channel),
+            None => self.client.conversation_info(channel).await,
+        }
+    }
+
+    pub async fn describe(&self, channel: &str, topic: Option<&str>, purpose: Option<&str>) -> Result<()> {
+        if let Some(topic) = topic {
+            self.client.set_topic(channel, topic).await?;
+        }
+        if let Some(purpose) = purpose {
+            self.client.set_purpose(channel, purpose).await?;
+        }
+        Ok(())
+    }
+
+    // Removes users one by one (conversations.kick takes a single user), ignoring non-members
+    pub async fn remove(&self, channel: &str, users: &[String]) -> Result<()> {
+        for user in users {
+            match self.client.kick_from_conversation(channel, user).await {
+                Err(SlackError::SlackApi { code, .. }) if code == "not_in_channel" => {
+                    debug!("{} is not a member of {}", user, channel);
+                }
+                result => {
+                    result?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+// The subset of conversations.info needed to tell where a message came from
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Channel {
+    pub id: String,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub is_channel: bool,
+    #[serde(default)]
+    pub is_group: bool,
+    #[serde(default)]
+    pub is_im: bool,
+    #[serde(default)]
+    pub is_mpim: bool,
+    #[serde(default)]
+    pub is_private: bool,
+    #[serde(default)]
+    pub is_archived: bool,
+    // Shared with any other workspace, including within the same Enterprise Grid org
+    #[serde(default)]
+    pub is_shared: bool,
+    // Shared with another organization through Slack Connect
+    #[serde(default)]
+    pub is_ext_shared: bool,
+    #[serde(default)]
+    pub is_org_shared: bool,
+    #[serde(default)]
+    pub is_pending_ext_shared: bool,
+    #[serde(default)]
+    pub shared_team_ids: Vec<String>,
+    #[serde(default)]
+    pub connected_team_ids: Vec<String>,
+    pub context_team_id: Option<String>,
+    pub conversation_host_id: Option<String>,
+}
+
+impl Channel {
+    // Connected or invited to connect with another organization
+    pub fn is_external(&self) -> bool {
+        self.is_ext_shared || self.is_pending_ext_shared
+    }
+
+    // Teams other than the host that can see this channel
+    pub fn external_team_ids(&self) -> impl Iterator<Item = &str> {
+        let host = self.conversation_host_id.as_deref().or(self.context_team_id.as_deref());
+        self.connected_team_ids
+            .iter()
+            .chain(self.shared_team_ids.iter())
+            .map(String::as_str)
+            .filter(move |team_id| Some(*team_id) != host)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConversationInfoResponse {
+    pub ok: bool,
+    pub channel: Channel,
+}
@@ -0,0 +1,138 @@
+use crate::client::api::{call_typed, SlackApi};
+use crate::error::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+// Filter for apps.datastore.query, in DynamoDB expression syntax:
+// `#status = :status` with `#status` -> "status" and `:status` -> "open"
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DatastoreQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub expression_attributes: Map<String, Value>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub expression_values: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl DatastoreQuery {
+    // No expression returns every item
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_expression<S: Into<String>>(mut self, expression: S) -> Self {
+        self.expression = Some(expression.into());
+        self
+    }
+
+    pub fn with_attribute<S: Into<String>, A: Into<String>>(mut self, placeholder: S, attribute: A) -> Self {
+        self.expression_attributes.insert(placeholder.into(), Value::String(attribute.into()));
+        self
+    }
+
+    pub fn with_value<S: Into<String>>(mut self, placeholder: S, value: Value) -> Self {
+        self.expression_values.insert(placeholder.into(), value);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn with_cursor<S: Into<String>>(mut self, cursor: S) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DatastorePage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemResponse {
+    #[serde(default)]
+    item: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryResponse {
+    #[serde(default)]
+    items: Vec<Value>,
+    response_metadata: Option<Value>,
+}
+
+// A Slack-hosted datastore declared in the app manifest (next-gen platform apps only).
+// Items are JSON objects keyed by the datastore's primary key attribute, usually `id`.
+#[derive(Debug, Clone)]
+pub struct Datastore {
+    client: Arc<dyn SlackApi>,
+    name: String,
+}
+
+impl Datastore {
+    pub fn new<S: Into<String>>(client: Arc<dyn SlackApi>, name: S) -> Self {
+        Self {
+            client,
+            name: name.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Creates or replaces the item with the same primary key; returns the item as stored
+    pub async fn put<T: Serialize + DeserializeOwned + Sync>(&self, item: &T) -> Result<T> {
+        let payload = json!({ "datastore": self.name, "item": item });
+        let response: ItemResponse = call_typed(self.client.as_ref(), "apps.datastore.put", &payload).await?;
+        Ok(serde_json::from_value(response.item)?)
+    }
+
+    // Slack answers a missing ID with an empty item rather than an error
+    pub async fn get<T: DeserializeOwned>(&self, id: &str) -> Result<Option<T>> {
+        let payload = json!({ "datastore": self.name, "id": id });
+        let response: ItemResponse = call_typed(self.client.as_ref(), "apps.datastore.get", &payload).await?;
+        match response.item {
+            Value::Object(item) if !item.is_empty() => Ok(Some(serde_json::from_value(Value::Object(item))?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn query<T: DeserializeOwned>(&self, query: &DatastoreQuery) -> Result<DatastorePage<T>> {
+        let mut payload = serde_json::to_value(query)?;
+        payload["datastore"] = json!(self.name);
+        let response: QueryResponse = call_typed(self.client.as_ref(), "apps.datastore.query", &payload).await?;
+
+        let next_cursor = response
+            .response_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("next_cursor"))
+            .and_then(|v| v.as_str())
+            .filter(|cursor| !cursor.is_empty())
+            .map(str::to_string);
+        let items = response
+            .items
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<std::result::Result<_, _>>()?;
+
+        Ok(DatastorePage { items, next_cursor })
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        let payload = json!({ "datastore": self.name, "id": id });
+        let _: Value = call_typed(self.client.as_ref(), "apps.datastore.delete", &payload).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use crate::client::rate_limit::{Bucket, RateLimiter};
+use crate::dynamodb::{ensure_table, TableSpec};
+use crate::error::{Result, SlackError};
+use crate::infrastructure::Resource;
+use async_trait::async_trait;
+use aws_sdk_dynamodb::{types::AttributeValue, Client as DynamoDbClient};
+use chrono::Utc;
+use std::time::Duration;
+
+// Optimistic writes against one item per bucket; concurrent instances that lose the race
+// re-read and try again rather than holding any lock
+const MAX_CONTENTION_RETRIES: u32 = 3;
+
+// Buckets are tiny and rebuilt from full on first use, so idle ones are left to expire
+const BUCKET_TTL_SECONDS: i64 = 3600;
+
+// A bucket's item as last written; `updated_at` is epoch milliseconds
+struct StoredBucket {
+    tokens: f64,
+    updated_at: i64,
+    version: u64,
+}
+
+// Like the conversation lock, conditional writes only serialize within a region, so keep
+// this table regional
+#[derive(Debug, Clone)]
+pub struct DynamoDbRateLimiter {
+    client: DynamoDbClient,
+    table_name: String,
+    wait_timeout: Duration,
+}
+
+impl DynamoDbRateLimiter {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self {
+            client,
+            table_name,
+            wait_timeout: Duration::from_secs(5),
+        }
+    }
+
+    pub fn with_wait_timeout(mut self, wait_timeout: Duration) -> Self {
+        self.wait_timeout = wait_timeout;
+        self
+    }
+
+    // Creates the table with its TTL if it doesn't exist yet
+    pub async fn create_table(&self) -> Result<()> {
+        ensure_table(&self.client, &self.spec(), Duration::from_secs(60)).await.map(|_| ())
+    }
+
+    fn spec(&self) -> TableSpec {
+        TableSpec::new(self.table_name.as_str(), "bucket_id").with_ttl("expires_at")
+    }
+
+    // What the last writer left, or None for a fresh bucket
+    async fn read(&self, bucket: &Bucket) -> Result<Option<StoredBucket>> {
+        let response = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .key("bucket_id", AttributeValue::S(bucket.key.clone()))
+            .consistent_read(true)
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        Ok(response.item.and_then(|item| {
+            let number = |name: &str| item.get(name).and_then(|v| v.as_n().ok()).map(|n| n.to_string());
+            Some(StoredBucket {
+                tokens: number("tokens")?.parse().ok()?,
+                updated_at: number("updated_at")?.parse().ok()?,
+                // Buckets written before versioning start over at 0
+                version: number("version").and_then(|n| n.parse().ok()).unwrap_or(0),
+            })
+        }))
+    }
+
+    // Writes the new token count as long as nobody else has written since `previous` was read.
+    // Clocks can't tell writers apart (two in the same millisecond, or one lagging behind), so
+    // the check is on a version every write bumps; `updated_at` only drives the refill.
+    async fn write(&self, bucket: &Bucket, tokens: f64, now: i64, previous: Option<u64>) -> Result<bool> {
+        let mut request = self
+            .client
+            .put_item()
+            .table_name(&self.table_name)
+            .item("bucket_id", AttributeValue::S(bucket.key.clone()))
+            .item("tokens", AttributeValue::N(tokens.to_string()))
+            .item("updated_at", AttributeValue::N(now.to_string()))
+            .item("version", AttributeValue::N(previous.map_or(1, |version| version + 1).to_string()))
+            .item(
+                "expires_at",
+                AttributeValue::N((now / 1000 + BUCKET_TTL_SECONDS).to_string()),
+            );
+
+        request = match previous {
+            // Also matches a bucket written before versioning, which has no version yet
+            Some(0) => request
+                .condition_expression("attribute_exists(bucket_id) AND (attribute_not_exists(version) OR version = :previous)")
+                .expression_attribute_values(":previous", AttributeValue::N("0".to_string())),
+            Some(previous) => request
+                .condition_expression("version = :previous")
+                .expression_attribute_values(":previous", AttributeValue::N(previous.to_string())),
+            None => request.condition_expression("attribute_not_exists(bucket_id)"),
+        };
+
+        match request.send().await {
+            Ok(_) => Ok(true),
+            Err(e)
+                if e.as_service_error()
+                    .map(|e| e.is_conditional_check_failed_exception())
+                    .unwrap_or(false) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(SlackError::DynamoDb(e.to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimiter for DynamoDbRateLimiter {
+    fn required_resources(&self) -> Vec<Resource> {
+        vec![Resource::Table(self.spec())]
+    }
+
+    async fn try_take(&self, bucket: &Bucket) -> Result<Option<Duration>> {
+        let capacity = bucket.capacity as f64;
+
+        for _ in 0..MAX_CONTENTION_RETRIES {
+            let now = Utc::now().timestamp_millis();
+            let stored = self.read(bucket).await?;
+            let available = match &stored {
+                Some(stored) => {
+                    let elapsed = (now - stored.updated_at).max(0) as f64 / 1000.0;
+                    (stored.tokens + elapsed * bucket.refill_per_second).min(capacity)
+                }
+                None => capacity,
+            };
+
+            if available < 1.0 {
+                let wait = (1.0 - available) / bucket.refill_per_second.max(f64::EPSILON);
+                return Ok(Some(Duration::from_secs_f64(wait)));
+            }
+
+            if self
+                .write(bucket, available - 1.0, now, stored.map(|stored| stored.version))
+                .await?
+            {
+                return Ok(None);
+            }
+        }
+
+        // Heavily contended; back off briefly instead of hammering the item
+        Ok(Some(Duration::from_millis(50)))
+    }
+
+    fn wait_timeout(&self) -> Duration {
+        self.wait_timeout
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct File {
+    pub id: String,
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub mimetype: Option<String>,
+    pub filetype: Option<String>,
+    pub size: Option<u64>,
+    pub user: Option<String>,
+    pub created: Option<i64>,
+    pub url_private: Option<String>,
+    pub url_private_download: Option<String>,
+    pub permalink: Option<String>,
+}
+
+impl File {
+    // `url_private_download` forces an attachment response; older files may only have `url_private`
+    pub fn download_url(&self) -> Option<&str> {
+        self.url_private_download.as_deref().or(self.url_private.as_deref())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileInfoResponse {
+    pub ok: bool,
+    pub file: File,
+}
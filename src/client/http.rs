@@ -0,0 +1,344 @@
+use crate::client::rate_limit::{Bucket, RateLimiter};
+use crate::client::SlackApi;
+use crate::context::correlation::{self, CORRELATION_ID_HEADER};
+use crate::error::{Result, SlackError};
+use crate::metrics::{Metrics, NoopMetrics};
+use crate::secret::SecretString;
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::warn;
+
+// Backoff for retried calls doubles from here; a Retry-After longer than the cap is not
+// waited out, since the invocation would likely time out first
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+// Writes that are safe to send twice: repeating them leaves the same state behind, where a
+// repeated chat.postMessage or files.upload would post a second copy
+const IDEMPOTENT_WRITES: &[&str] = &[
+    "chat.update",
+    "chat.delete",
+    "views.update",
+    "views.publish",
+    "reactions.add",
+    "reactions.remove",
+    "pins.add",
+    "pins.remove",
+    "conversations.join",
+    "conversations.open",
+    "conversations.setTopic",
+    "conversations.setPurpose",
+    "users.profile.set",
+];
+
+// Reads are recognised by the last part of the method name, e.g. conversations.history
+const READ_VERBS: &[&str] = &["list", "info", "history", "replies", "members", "get", "lookup", "test"];
+
+// Whether a call that may have reached Slack can be sent again without side effects
+fn is_idempotent(method: &str) -> bool {
+    let verb = method.rsplit('.').next().unwrap_or_default();
+    IDEMPOTENT_WRITES.contains(&method) || READ_VERBS.iter().any(|read| verb.starts_with(read))
+}
+
+// One attempt at a Web API call. `unprocessed` is set when Slack throttled the request before
+// acting on it, so sending it again can't duplicate anything. A 5xx doesn't count: Slack may
+// have posted before failing, just as with a 200 carrying `internal_error`.
+struct Attempt {
+    result: Result<Value>,
+    retry_after: Option<Duration>,
+    unprocessed: bool,
+}
+
+#[derive(Clone)]
+pub struct SlackClient {
+    client: Client,
+    token: Option<SecretString>,
+    base_url: String,
+    metrics: Arc<dyn Metrics>,
+    max_retries: u32,
+    rate_limiter: Option<Arc<dyn RateLimiter>>,
+}
+
+impl SlackClient {
+    pub fn new(token: Option<SecretString>) -> Self {
+        Self {
+            client: crate::shared::http_client(),
+            token,
+            base_url: "https://slack.com/api".to_string(),
+            metrics: Arc::new(NoopMetrics),
+            max_retries: 2,
+            rate_limiter: None,
+        }
+    }
+
+    // Points the client at another Web API root, e.g. a local mock server in tests
+    pub fn with_base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    // Consulted before every attempt, retries included, so concurrent instances share Slack's
+    // per-workspace limits
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<dyn RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    // How many times a call that is safe to repeat is attempted again; 0 disables retries
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub async fn api_call<T, R>(&self, method: &str, request: &T) -> Result<R>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let started = Instant::now();
+        let result = self.send_api_call(method, request).await;
+
+        let tags = [("Method", method)];
+        self.metrics.counter("SlackApiCalls", 1, &tags);
+        self.metrics.histogram("SlackApiLatency", started.elapsed().as_secs_f64() * 1000.0, &tags);
+        if result.is_err() {
+            self.metrics.counter("SlackApiErrors", 1, &tags);
+        }
+
+        let response_body = result?;
+        Ok(serde_json::from_value(response_body)?)
+    }
+
+    // A limiter that can't be reached doesn't stop the call; Slack still enforces its own limits
+    async fn take_rate_limit_token<T: Serialize + ?Sized>(&self, method: &str, request: &T) -> Result<()> {
+        let (Some(rate_limiter), Some(token)) = (&self.rate_limiter, &self.token) else {
+            return Ok(());
+        };
+
+        let payload = serde_json::to_value(request)?;
+        let bucket = Bucket::for_call(&token_scope(token.expose()), method, &payload);
+        match rate_limiter.acquire(&bucket).await {
+            Err(SlackError::DynamoDb(e)) | Err(SlackError::Storage(e)) => {
+                warn!("Rate limiter unavailable, calling {} unthrottled: {}", method, e);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    // Throttled calls are retried for any method; other transient failures (dropped
+    // connections, timeouts, 5xx responses, internal_error) only for idempotent methods, since the
+    // first attempt may already have posted. Retries back off exponentially, or wait out
+    // Slack's Retry-After, and each attempt takes its own rate limit token.
+    async fn send_api_call<T: Serialize + ?Sized>(&self, method: &str, request: &T) -> Result<Value> {
+        let mut attempt = 0;
+
+        loop {
+            self.take_rate_limit_token(method, request).await?;
+
+            let Attempt { result, retry_after, unprocessed } = self.send_api_call_once(method, request).await;
+            let error = match result {
+                Err(e) if attempt < self.max_retries && (unprocessed || (e.is_retriable() && is_idempotent(method))) => e,
+                result => return result,
+            };
+
+            let delay = retry_after.unwrap_or(RETRY_BASE_DELAY * 2u32.pow(attempt));
+            if delay > MAX_RETRY_DELAY {
+                return Err(error);
+            }
+
+            warn!("{} failed ({}), retrying in {:?}", method, error, delay);
+            self.metrics.counter("SlackApiRetries", 1, &[("Method", method)]);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send_api_call_once<T: Serialize + ?Sized>(&self, method: &str, request: &T) -> Attempt {
+        let failed = |e: SlackError| Attempt {
+            result: Err(e),
+            retry_after: None,
+            unprocessed: false,
+        };
+        let token = match self.get_token() {
+            Ok(token) => token,
+            Err(e) => return failed(e),
+        };
+        let url = format!("{}/{}", self.base_url, method);
+
+        let mut builder = self.client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json");
+        if let Some(correlation_id) = correlation::current() {
+            builder = builder.header(CORRELATION_ID_HEADER, correlation_id);
+        }
+        let response = builder.json(request).send().await;
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => return failed(e.into()),
+        };
+
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+            .map(Duration::from_secs);
+
+        let result = Self::read_api_response(method, response).await;
+        let unprocessed = status.as_u16() == 429
+            || matches!(&result, Err(SlackError::SlackApi { code, .. }) if code == "ratelimited");
+
+        Attempt { result, retry_after, unprocessed }
+    }
+
+    async fn read_api_response(method: &str, response: reqwest::Response) -> Result<Value> {
+        let response_body: Value = response.json().await?;
+
+        if !response_body.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Err(SlackError::SlackApi {
+                code: response_body.get("error").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                message: format!("{} failed", method),
+            });
+        }
+
+        Ok(response_body)
+    }
+
+    // Streams a private file into `writer` instead of buffering it, returning the bytes written
+    pub async fn download_to<W: AsyncWrite + Unpin>(&self, url: &str, writer: &mut W) -> Result<u64> {
+        let mut response = self.send_download(url).await?;
+        let mut written = 0;
+
+        while let Some(chunk) = response.chunk().await? {
+            writer
+                .write_all(&chunk)
+                .await
+                .map_err(|e| SlackError::Internal(format!("Failed to write downloaded file: {}", e)))?;
+            written += chunk.len() as u64;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| SlackError::Internal(format!("Failed to write downloaded file: {}", e)))?;
+
+        Ok(written)
+    }
+
+    async fn send_download(&self, url: &str) -> Result<reqwest::Response> {
+        let response = self.client
+            .get(url)
+            .header("Authorization", format!("Bearer {}", self.get_token()?))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        // Slack answers an unauthorized file request with its HTML sign-in page, not an error status
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/html"));
+        if is_html {
+            return Err(SlackError::SlackApi {
+                code: "file_access_denied".to_string(),
+                message: format!("Download of {} returned a sign-in page; check the token's files:read scope", url),
+            });
+        }
+
+        Ok(response)
+    }
+
+    fn get_token(&self) -> Result<&str> {
+        self.token.as_ref().map(SecretString::expose).ok_or_else(|| {
+            SlackError::Config("Bot token is required for API calls".to_string())
+        })
+    }
+}
+
+#[async_trait]
+impl SlackApi for SlackClient {
+    async fn call(&self, method: &str, payload: Value) -> Result<Value> {
+        self.api_call(method, &payload).await
+    }
+
+    async fn download(&self, url: &str) -> Result<Bytes> {
+        Ok(self.send_download(url).await?.bytes().await?)
+    }
+
+    async fn respond(&self, response_url: &str, payload: Value) -> Result<()> {
+        let response = self.client.post(response_url).json(&payload).send().await?.error_for_status()?;
+
+        // Success is a bare "ok"; failures are JSON like `{"ok": false, "error": "expired_url"}`
+        let body = response.text().await?;
+        match serde_json::from_str::<Value>(&body) {
+            Ok(json) if json.get("ok").and_then(|v| v.as_bool()) == Some(false) => Err(SlackError::SlackApi {
+                code: json.get("error").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                message: "response_url post failed".to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn with_token(&self, token: String) -> Arc<dyn SlackApi> {
+        Arc::new(Self {
+            token: Some(token.into()),
+            ..self.clone()
+        })
+    }
+}
+
+// Buckets are keyed per workspace; a token stands for one, but must not be stored as-is
+fn token_scope(token: &str) -> String {
+    let digest = Sha256::digest(token.as_bytes());
+    hex::encode(&digest[..8])
+}
+
+// Hand-written so the token never ends up in logs
+impl fmt::Debug for SlackClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlackClient")
+            .field("base_url", &self.base_url)
+            .field("has_token", &self.token.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_idempotent;
+
+    #[test]
+    fn reads_and_idempotent_writes_are_repeatable() {
+        assert!(is_idempotent("conversations.history"));
+        assert!(is_idempotent("users.info"));
+        assert!(is_idempotent("users.lookupByEmail"));
+        assert!(is_idempotent("auth.test"));
+        assert!(is_idempotent("chat.update"));
+        assert!(is_idempotent("views.publish"));
+    }
+
+    #[test]
+    fn posting_writes_are_not_repeatable() {
+        assert!(!is_idempotent("chat.postMessage"));
+        assert!(!is_idempotent("chat.postEphemeral"));
+        assert!(!is_idempotent("files.upload"));
+        assert!(!is_idempotent("views.open"));
+    }
+}
@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+// One column's value on a List item. Slack keys the value by the column type
+// (`rich_text`, `user`, `checkbox`, ...), so anything without a helper can use `new`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListCell {
+    pub column_id: String,
+    // Only used by slackLists.items.update, to say which item the cell belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_id: Option<String>,
+    #[serde(flatten)]
+    pub value: Map<String, Value>,
+}
+
+impl ListCell {
+    pub fn new<S: Into<String>, K: Into<String>>(column_id: S, key: K, value: Value) -> Self {
+        let mut map = Map::new();
+        map.insert(key.into(), value);
+        Self {
+            column_id: column_id.into(),
+            row_id: None,
+            value: map,
+        }
+    }
+
+    // Plain text, sent as a single rich text section
+    pub fn text<S: Into<String>, T: Into<String>>(column_id: S, text: T) -> Self {
+        let rich_text = json!([{
+            "type": "rich_text",
+            "elements": [{
+                "type": "rich_text_section",
+                "elements": [{ "type": "text", "text": text.into() }],
+            }],
+        }]);
+        Self::new(column_id, "rich_text", rich_text)
+    }
+
+    pub fn users<S: Into<String>>(column_id: S, user_ids: Vec<String>) -> Self {
+        Self::new(column_id, "user", json!(user_ids))
+    }
+
+    pub fn checkbox<S: Into<String>>(column_id: S, checked: bool) -> Self {
+        Self::new(column_id, "checkbox", json!(checked))
+    }
+
+    pub fn number<S: Into<String>>(column_id: S, number: f64) -> Self {
+        Self::new(column_id, "number", json!([number]))
+    }
+
+    // Option IDs from the column's select definition
+    pub fn select<S: Into<String>>(column_id: S, option_ids: Vec<String>) -> Self {
+        Self::new(column_id, "select", json!(option_ids))
+    }
+
+    // `YYYY-MM-DD`
+    pub fn date<S: Into<String>, D: Into<String>>(column_id: S, date: D) -> Self {
+        Self::new(column_id, "date", json!([date.into()]))
+    }
+
+    pub fn for_row<S: Into<String>>(mut self, row_id: S) -> Self {
+        self.row_id = Some(row_id.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateListItemRequest {
+    pub list_id: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub initial_fields: Vec<ListCell>,
+    // Creates the item as a subtask of another item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_item_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicated_item_id: Option<String>,
+}
+
+impl CreateListItemRequest {
+    pub fn new<S: Into<String>>(list_id: S) -> Self {
+        Self {
+            list_id: list_id.into(),
+            initial_fields: Vec::new(),
+            parent_item_id: None,
+            duplicated_item_id: None,
+        }
+    }
+
+    pub fn with_field(mut self, cell: ListCell) -> Self {
+        self.initial_fields.push(cell);
+        self
+    }
+
+    pub fn with_parent<S: Into<String>>(mut self, parent_item_id: S) -> Self {
+        self.parent_item_id = Some(parent_item_id.into());
+        self
+    }
+
+    pub fn duplicating<S: Into<String>>(mut self, item_id: S) -> Self {
+        self.duplicated_item_id = Some(item_id.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateListItemsRequest {
+    pub list_id: String,
+    // Every cell names its item with `row_id`, so one call can touch several items
+    pub cells: Vec<ListCell>,
+}
+
+impl UpdateListItemsRequest {
+    pub fn new<S: Into<String>>(list_id: S) -> Self {
+        Self {
+            list_id: list_id.into(),
+            cells: Vec::new(),
+        }
+    }
+
+    pub fn with_cell<S: Into<String>>(mut self, row_id: S, cell: ListCell) -> Self {
+        self.cells.push(cell.for_row(row_id));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListItemsRequest {
+    pub list_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+impl ListItemsRequest {
+    pub fn new<S: Into<String>>(list_id: S) -> Self {
+        Self {
+            list_id: list_id.into(),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListItem {
+    pub id: String,
+    pub list_id: Option<String>,
+    pub parent_item_id: Option<String>,
+    pub created_by: Option<String>,
+    pub date_created: Option<i64>,
+    pub updated_timestamp: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<Value>,
+}
+
+impl ListItem {
+    // The raw field object for a column, including its typed value and display `text`
+    pub fn field(&self, column_id: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|field| field.get("column_id").and_then(|v| v.as_str()) == Some(column_id))
+    }
+
+    // Slack's plain-text rendering of a column's value
+    pub fn field_text(&self, column_id: &str) -> Option<&str> {
+        self.field(column_id).and_then(|field| field.get("text")).and_then(|v| v.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListItemResponse {
+    pub ok: bool,
+    pub item: ListItem,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListItemInfoResponse {
+    pub ok: bool,
+    pub record: ListItem,
+    // The list's own metadata, including its column schema
+    pub list: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListItemsResponse {
+    pub ok: bool,
+    #[serde(default)]
+    pub items: Vec<ListItem>,
+    pub response_metadata: Option<Value>,
+}
+
+impl ListItemsResponse {
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.response_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("next_cursor"))
+            .and_then(|v| v.as_str())
+            .filter(|cursor| !cursor.is_empty())
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// migration.exchange takes at most this many IDs per call
+pub const MAX_EXCHANGE_USERS: usize = 400;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationExchangeRequest {
+    // Comma separated on the wire
+    #[serde(serialize_with = "serialize_comma_separated")]
+    pub users: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
+    // Map global W-prefixed IDs back to the local U-prefixed ones
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub to_old: bool,
+}
+
+impl MigrationExchangeRequest {
+    pub fn new<I>(users: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            users: users.into_iter().map(|s| s.into()).collect(),
+            team_id: None,
+            to_old: false,
+        }
+    }
+
+    pub fn with_team_id<S: Into<String>>(mut self, team_id: S) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
+    pub fn to_old(mut self) -> Self {
+        self.to_old = true;
+        self
+    }
+}
+
+fn serialize_comma_separated<S: serde::Serializer>(users: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&users.join(","))
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MigrationExchangeResponse {
+    pub ok: bool,
+    pub team_id: Option<String>,
+    pub enterprise_id: Option<String>,
+    #[serde(default)]
+    pub user_id_map: HashMap<String, String>,
+    #[serde(default)]
+    pub invalid_user_ids: Vec<String>,
+}
+
+impl MigrationExchangeResponse {
+    // The mapped ID, or the ID itself when Slack had nothing to map it to
+    pub fn resolve<'a>(&'a self, user_id: &'a str) -> &'a str {
+        self.user_id_map.get(user_id).map(String::as_str).unwrap_or(user_id)
+    }
+}
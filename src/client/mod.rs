@@ -1,98 +1,64 @@
+pub mod api;
+pub mod channel_admin;
+pub mod conversations;
+#[cfg(feature = "datastores")]
+pub mod datastore;
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb_rate_limit;
+pub mod files;
+#[cfg(feature = "http-client")]
+pub mod http;
+pub mod lists;
+pub mod migration;
+pub mod policy;
+pub mod rate_limit;
+pub mod triggers;
+pub mod unfurl;
+pub mod users;
+pub mod views;
+
+pub use api::SlackApi;
+pub use channel_admin::{ChannelAdmin, ChannelSetup};
+pub use conversations::Channel;
+#[cfg(feature = "datastores")]
+pub use datastore::{Datastore, DatastorePage, DatastoreQuery};
+#[cfg(feature = "dynamodb")]
+pub use dynamodb_rate_limit::DynamoDbRateLimiter;
+pub use files::File;
+#[cfg(feature = "http-client")]
+pub use http::SlackClient;
+pub use lists::{CreateListItemRequest, ListCell, ListItem, ListItemsRequest, UpdateListItemsRequest};
+pub use migration::{MigrationExchangeRequest, MigrationExchangeResponse};
+pub use policy::{ContentPolicy, PolicyClient};
+pub use rate_limit::{Bucket, RateLimiter, Tier};
+pub use triggers::{CreateTriggerRequest, ListTriggersRequest, Trigger, UpdateTriggerRequest};
+pub use unfurl::UnfurlRequest;
+pub use users::{Presence, SetProfileRequest, UserPresence, UserProfile};
+pub use views::ViewHandle;
+
 use crate::error::{Result, SlackError};
-use reqwest::Client;
+use crate::request::payloads::ViewPayload;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
-
-#[derive(Clone)]
-pub struct SlackClient {
-    client: Client,
-    token: Option<String>,
-    base_url: String,
-}
-
-impl SlackClient {
-    pub fn new(token: Option<String>) -> Self {
-        Self {
-            client: Client::new(),
-            token,
-            base_url: "https://slack.com/api".to_string(),
-        }
-    }
-
-    pub async fn post_message(&self, request: &PostMessageRequest) -> Result<PostMessageResponse> {
-        let url = format!("{}/chat.postMessage", self.base_url);
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.get_token()?))
-            .header("Content-Type", "application/json")
-            .json(request)
-            .send()
-            .await?;
-
-        let response_body: PostMessageResponse = response.json().await?;
-        
-        if !response_body.ok {
-            return Err(SlackError::SlackApi {
-                code: response_body.error.clone().unwrap_or_default(),
-                message: "API call failed".to_string(),
-            });
-        }
-
-        Ok(response_body)
-    }
-
-    pub async fn update_message(&self, request: &UpdateMessageRequest) -> Result<UpdateMessageResponse> {
-        let url = format!("{}/chat.update", self.base_url);
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.get_token()?))
-            .header("Content-Type", "application/json")
-            .json(request)
-            .send()
-            .await?;
-
-        let response_body: UpdateMessageResponse = response.json().await?;
-        
-        if !response_body.ok {
-            return Err(SlackError::SlackApi {
-                code: response_body.error.clone().unwrap_or_default(),
-                message: "API call failed".to_string(),
-            });
-        }
-
-        Ok(response_body)
-    }
-
-    pub async fn delete_message(&self, request: &DeleteMessageRequest) -> Result<DeleteMessageResponse> {
-        let url = format!("{}/chat.delete", self.base_url);
-        
-        let response = self.client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.get_token()?))
-            .header("Content-Type", "application/json")
-            .json(request)
-            .send()
-            .await?;
-
-        let response_body: DeleteMessageResponse = response.json().await?;
-        
-        if !response_body.ok {
-            return Err(SlackError::SlackApi {
-                code: response_body.error.clone().unwrap_or_default(),
-                message: "API call failed".to_string(),
-            });
-        }
-
-        Ok(response_body)
+use std::sync::Arc;
+
+// The app's client when the crate is built without `http-client`: every call fails until
+// `AppBuilder::client` supplies a transport of the host's own (e.g. a Workers fetch binding)
+#[derive(Debug, Clone, Default)]
+pub struct UnconfiguredClient;
+
+#[async_trait]
+impl SlackApi for UnconfiguredClient {
+    async fn call(&self, method: &str, _payload: Value) -> Result<Value> {
+        Err(SlackError::Config(format!(
+            "Cannot call {}: built without the http-client feature and no client was configured",
+            method
+        )))
     }
 
-    fn get_token(&self) -> Result<&str> {
-        self.token.as_deref().ok_or_else(|| {
-            SlackError::Config("Bot token is required for API calls".to_string())
-        })
+    fn with_token(&self, _token: String) -> Arc<dyn SlackApi> {
+        Arc::new(self.clone())
     }
 }
 
@@ -105,6 +71,8 @@ pub struct PostMessageRequest {
     pub blocks: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thread_ts: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<MessageMetadata>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -128,6 +96,51 @@ pub struct UpdateMessageRequest {
     pub text: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blocks: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<MessageMetadata>,
+}
+
+// Event type under which `MessageMetadata::external_id` files a message's external reference
+pub const EXTERNAL_REFERENCE_EVENT_TYPE: &str = "external_reference";
+
+// Structured data attached to a message, invisible to users; read back from
+// conversations.history with `include_all_metadata`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageMetadata {
+    pub event_type: String,
+    #[serde(default)]
+    pub event_payload: Value,
+}
+
+impl MessageMetadata {
+    pub fn new<S: Into<String>>(event_type: S, event_payload: Value) -> Self {
+        Self {
+            event_type: event_type.into(),
+            event_payload,
+        }
+    }
+
+    // Tags a message with an ID of the app's own (a ticket or deploy ID) so a later
+    // invocation can find it with `find_message_by_external_id` instead of storing its ts
+    pub fn external_id<S: Into<String>>(external_id: S) -> Self {
+        Self::new(
+            EXTERNAL_REFERENCE_EVENT_TYPE,
+            serde_json::json!({ "external_id": external_id.into() }),
+        )
+    }
+
+    pub fn external_id_value(&self) -> Option<&str> {
+        if self.event_type != EXTERNAL_REFERENCE_EVENT_TYPE {
+            return None;
+        }
+        self.event_payload.get("external_id").and_then(|v| v.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ViewResponse {
+    pub ok: bool,
+    pub view: ViewPayload,
 }
 
 #[derive(Debug, Deserialize)]
@@ -158,4 +171,56 @@ pub struct DeleteMessageResponse {
     pub channel: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ts: Option<String>,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiResponse {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateStepRequest {
+    pub workflow_step_edit_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outputs: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_image_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StepCompletedRequest {
+    pub workflow_step_execute_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outputs: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StepFailedRequest {
+    pub workflow_step_execute_id: String,
+    pub error: StepError,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StepError {
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionCompleteSuccessRequest {
+    pub function_execution_id: String,
+    pub outputs: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionCompleteErrorRequest {
+    pub function_execution_id: String,
+    pub error: String,
+}
@@ -0,0 +1,114 @@
+// Outgoing content policies: functions from text to text (masking, profanity filters) run
+// over every text field of what the app sends to Slack. Registered with
+// `AppBuilder::content_policy`, which wraps the app's client, so Web API calls, `Say` and
+// response_url/webhook posts all pass through them. Ack bodies returned from listeners are
+// not filtered.
+use crate::client::SlackApi;
+use crate::error::Result;
+use async_trait::async_trait;
+use bytes::Bytes;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::Arc;
+
+pub type ContentPolicy = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+// Fields Slack shows to people: message text and text objects in blocks and views,
+// legacy attachment text, and file comments
+const TEXT_KEYS: &[&str] = &["text", "fallback", "pretext", "title", "initial_comment"];
+
+const MASK: &str = "[redacted]";
+
+pub fn content_policy<F>(f: F) -> ContentPolicy
+where
+    F: Fn(&str) -> String + Send + Sync + 'static,
+{
+    Arc::new(f)
+}
+
+pub fn mask_emails() -> impl Fn(&str) -> String + Send + Sync + 'static {
+    mask_pattern(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}")
+}
+
+// Slack tokens, AWS access key IDs and the common `sk-`/`ghp_` style secret keys
+pub fn mask_api_keys() -> impl Fn(&str) -> String + Send + Sync + 'static {
+    mask_pattern(r"\b(?:xox[abposr]-[A-Za-z0-9-]{10,}|xapp-[A-Za-z0-9-]{10,}|AKIA[0-9A-Z]{16}|sk-[A-Za-z0-9_-]{20,}|gh[pousr]_[A-Za-z0-9]{30,})\b")
+}
+
+// Replaces every match of `pattern` with "[redacted]"; panics on an invalid pattern, as
+// policies are set up once at startup
+pub fn mask_pattern(pattern: &str) -> impl Fn(&str) -> String + Send + Sync + 'static {
+    let regex = Regex::new(pattern).expect("invalid content policy pattern");
+    move |text: &str| regex.replace_all(text, MASK).into_owned()
+}
+
+pub fn apply_policies(policies: &[ContentPolicy], value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        Value::String(text) if TEXT_KEYS.contains(&key.as_str()) => {
+                            Value::String(policies.iter().fold(text.clone(), |text, policy| policy(&text)))
+                        }
+                        value => apply_policies(policies, value),
+                    };
+                    (key.clone(), value)
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(|item| apply_policies(policies, item)).collect()),
+        _ => value.clone(),
+    }
+}
+
+// Wraps another client and filters payloads on the way out
+#[derive(Clone)]
+pub struct PolicyClient {
+    inner: Arc<dyn SlackApi>,
+    policies: Arc<Vec<ContentPolicy>>,
+}
+
+impl PolicyClient {
+    pub fn new(inner: Arc<dyn SlackApi>, policies: Vec<ContentPolicy>) -> Self {
+        Self {
+            inner,
+            policies: Arc::new(policies),
+        }
+    }
+}
+
+impl fmt::Debug for PolicyClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyClient")
+            .field("inner", &self.inner)
+            .field("policies", &self.policies.len())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl SlackApi for PolicyClient {
+    async fn call(&self, method: &str, payload: Value) -> Result<Value> {
+        self.inner.call(method, apply_policies(&self.policies, &payload)).await
+    }
+
+    fn with_token(&self, token: String) -> Arc<dyn SlackApi> {
+        Arc::new(Self {
+            inner: self.inner.with_token(token),
+            policies: self.policies.clone(),
+        })
+    }
+
+    async fn download(&self, url: &str) -> Result<Bytes> {
+        self.inner.download(url).await
+    }
+
+    async fn respond(&self, response_url: &str, payload: Value) -> Result<()> {
+        self.inner
+            .respond(response_url, apply_policies(&self.policies, &payload))
+            .await
+    }
+}
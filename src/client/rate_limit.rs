@@ -0,0 +1,110 @@
+use crate::error::{Result, SlackError};
+use crate::infrastructure::Resource;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt::Debug;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+// Slack's published Web API tiers. Limits apply per app per workspace, so every Lambda
+// instance calling on behalf of the same workspace draws from the same allowance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tier {
+    Tier1,
+    Tier2,
+    Tier3,
+    Tier4,
+    // chat.postMessage and friends: about one message per second per channel
+    PerChannel,
+}
+
+impl Tier {
+    pub fn per_minute(&self) -> u32 {
+        match self {
+            Tier::Tier1 => 1,
+            Tier::Tier2 => 20,
+            Tier::Tier3 => 50,
+            Tier::Tier4 => 100,
+            Tier::PerChannel => 60,
+        }
+    }
+
+    // Methods without a known tier are treated as Tier 3, the most common one
+    pub fn for_method(method: &str) -> Tier {
+        match method {
+            "chat.postMessage" | "chat.postEphemeral" | "chat.scheduleMessage" => Tier::PerChannel,
+            "admin.users.list" | "migration.exchange" | "team.accessLogs" | "team.billableInfo" => Tier::Tier1,
+            "conversations.list" | "users.list" | "conversations.create" | "files.list" | "search.messages" => {
+                Tier::Tier2
+            }
+            "chat.update" | "chat.delete" | "reactions.add" | "views.open" | "views.push" | "views.update"
+            | "views.publish" | "users.info" | "users.profile.get" | "conversations.info" | "auth.test" => Tier::Tier4,
+            _ => Tier::Tier3,
+        }
+    }
+}
+
+// One token bucket: `capacity` calls may burst, refilled at `refill_per_second`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bucket {
+    pub key: String,
+    pub capacity: u32,
+    pub refill_per_second: f64,
+}
+
+impl Bucket {
+    pub fn new<S: Into<String>>(key: S, capacity: u32, refill_per_second: f64) -> Self {
+        Self {
+            key: key.into(),
+            capacity: capacity.max(1),
+            refill_per_second,
+        }
+    }
+
+    // `scope` identifies the workspace; per-channel methods get a bucket per channel
+    pub fn for_call(scope: &str, method: &str, payload: &Value) -> Self {
+        let tier = Tier::for_method(method);
+        let per_second = tier.per_minute() as f64 / 60.0;
+
+        match (tier, payload.get("channel").and_then(|v| v.as_str())) {
+            (Tier::PerChannel, Some(channel)) => Self::new(format!("{}:{}:{}", scope, method, channel), 1, per_second),
+            _ => Self::new(format!("{}:{}", scope, method), tier.per_minute(), per_second),
+        }
+    }
+}
+
+// A rate limiter shared by every instance of the app, consulted before each Web API call
+#[async_trait]
+pub trait RateLimiter: Send + Sync + Debug {
+    // Takes a token, or returns how long until the next one is due
+    async fn try_take(&self, bucket: &Bucket) -> Result<Option<Duration>>;
+
+    fn required_resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+
+    // Longer waits are reported as `ratelimited` so the caller's retry policy decides
+    fn wait_timeout(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
+    async fn acquire(&self, bucket: &Bucket) -> Result<()> {
+        let deadline = Instant::now() + self.wait_timeout();
+
+        loop {
+            let wait = match self.try_take(bucket).await? {
+                None => return Ok(()),
+                Some(wait) => wait,
+            };
+
+            if Instant::now() + wait > deadline {
+                return Err(SlackError::SlackApi {
+                    code: "ratelimited".to_string(),
+                    message: format!("Shared rate limit for {} is exhausted", bucket.key),
+                });
+            }
+
+            sleep(wait).await;
+        }
+    }
+}
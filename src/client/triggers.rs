@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateTriggerRequest {
+    #[serde(rename = "type")]
+    pub trigger_type: String,
+    pub name: String,
+    pub workflow: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shortcut: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<Value>,
+}
+
+impl CreateTriggerRequest {
+    pub fn new<S: Into<String>>(trigger_type: S, name: S, workflow: S) -> Self {
+        Self {
+            trigger_type: trigger_type.into(),
+            name: name.into(),
+            workflow: workflow.into(),
+            description: None,
+            inputs: None,
+            event: None,
+            schedule: None,
+            shortcut: None,
+            webhook: None,
+        }
+    }
+
+    // Link triggers are called "shortcut" triggers by the API
+    pub fn link<S: Into<String>>(name: S, workflow: S) -> Self {
+        Self::new("shortcut".into(), name.into(), workflow.into())
+    }
+
+    pub fn event<S: Into<String>>(name: S, workflow: S, event_type: S, channel_ids: Vec<String>) -> Self {
+        let mut request = Self::new("event".into(), name.into(), workflow.into());
+        request.event = Some(json!({
+            "event_type": event_type.into(),
+            "channel_ids": channel_ids,
+        }));
+        request
+    }
+
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_inputs(mut self, inputs: Value) -> Self {
+        self.inputs = Some(inputs);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateTriggerRequest {
+    pub trigger_id: String,
+    #[serde(flatten)]
+    pub trigger: CreateTriggerRequest,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListTriggersRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_owner: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_published: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Trigger {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub trigger_type: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub workflow: Option<Value>,
+    pub shortcut_url: Option<String>,
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub inputs: Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TriggerResponse {
+    pub ok: bool,
+    pub trigger: Trigger,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListTriggersResponse {
+    pub ok: bool,
+    #[serde(default)]
+    pub triggers: Vec<Trigger>,
+    pub response_metadata: Option<Value>,
+}
+
+impl ListTriggersResponse {
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.response_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("next_cursor"))
+            .and_then(|v| v.as_str())
+            .filter(|cursor| !cursor.is_empty())
+    }
+}
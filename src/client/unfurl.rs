@@ -0,0 +1,54 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+// chat.unfurl for the links in a link_shared event. Links shared in a posted message are
+// addressed by channel and ts; links still in the composer (`source: "composer"`) only by
+// the event's `unfurl_id` and `source`, which is all a later invocation needs to keep.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UnfurlRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfurl_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // URL -> `{ "blocks": [...] }`
+    pub unfurls: Map<String, Value>,
+}
+
+impl UnfurlRequest {
+    pub fn for_message<S: Into<String>>(channel: S, ts: S) -> Self {
+        Self {
+            channel: Some(channel.into()),
+            ts: Some(ts.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn for_unfurl_id<S: Into<String>>(unfurl_id: S, source: S) -> Self {
+        Self {
+            unfurl_id: Some(unfurl_id.into()),
+            source: Some(source.into()),
+            ..Self::default()
+        }
+    }
+
+    // Picks whichever addressing the link_shared event offers, preferring unfurl_id
+    pub fn for_link_shared(event: &Value) -> Self {
+        let field = |name: &str| event.get(name).and_then(|v| v.as_str());
+        match (field("unfurl_id"), field("source")) {
+            (Some(unfurl_id), Some(source)) => Self::for_unfurl_id(unfurl_id, source),
+            _ => Self::for_message(
+                field("channel").unwrap_or_default(),
+                field("message_ts").unwrap_or_default(),
+            ),
+        }
+    }
+
+    pub fn with_unfurl<S: Into<String>>(mut self, url: S, blocks: Vec<Value>) -> Self {
+        self.unfurls.insert(url.into(), serde_json::json!({ "blocks": blocks }));
+        self
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+// users.profile.get/set. Only the fields that are set get sent, so a partial profile updates
+// just those fields and leaves the rest alone.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct UserProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub real_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pronouns: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_emoji: Option<String>,
+    // Epoch seconds; 0 means the status never expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_expiration: Option<i64>,
+    // Custom profile fields keyed by field ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing)]
+    pub image_original: Option<String>,
+    #[serde(skip_serializing)]
+    pub image_192: Option<String>,
+}
+
+impl UserProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_status<T: Into<String>, E: Into<String>>(mut self, text: T, emoji: E) -> Self {
+        self.status_text = Some(text.into());
+        self.status_emoji = Some(emoji.into());
+        self
+    }
+
+    pub fn with_status_expiration(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.status_expiration = Some(expires_at.timestamp());
+        self
+    }
+
+    pub fn with_display_name<S: Into<String>>(mut self, display_name: S) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    // Clearing a status means setting both parts to empty strings
+    pub fn cleared_status() -> Self {
+        Self::new().with_status("", "")
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SetProfileRequest {
+    // Another user's profile; only admins on paid plans may set this
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<UserProfile>,
+    // A single field, as an alternative to `profile`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl SetProfileRequest {
+    pub fn profile(profile: UserProfile) -> Self {
+        Self {
+            user: None,
+            profile: Some(profile),
+            name: None,
+            value: None,
+        }
+    }
+
+    pub fn field<N: Into<String>, V: Into<String>>(name: N, value: V) -> Self {
+        Self {
+            user: None,
+            profile: None,
+            name: Some(name.into()),
+            value: Some(value.into()),
+        }
+    }
+
+    pub fn with_user<S: Into<String>>(mut self, user: S) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserProfileResponse {
+    pub ok: bool,
+    pub profile: UserProfile,
+}
+
+// users.setPresence only accepts these two; "away" overrides activity until set back to "auto"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Presence {
+    Auto,
+    Away,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserPresence {
+    // "active" or "away"
+    pub presence: String,
+    // The remaining fields are only returned for the calling user
+    pub online: Option<bool>,
+    pub auto_away: Option<bool>,
+    pub manual_away: Option<bool>,
+    pub connection_count: Option<u32>,
+    pub last_activity: Option<i64>,
+}
+
+impl UserPresence {
+    pub fn is_active(&self) -> bool {
+        self.presence == "active"
+    }
+}
@@ -0,0 +1,63 @@
+use crate::blocks::View;
+use crate::client::SlackApi;
+use crate::error::{Result, SlackError};
+use crate::request::payloads::ViewPayload;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+// A modal or home tab the app has shown, with the hash of its latest version. Updates send
+// the hash so Slack rejects them if the view changed in between (`hash_conflict`); the
+// handle keeps itself current from every view Slack returns.
+//
+//     let mut handle = client.open_view(trigger_id, &loading).await?.handle();
+//     handle.update(client.as_ref(), &results).await?;
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewHandle {
+    pub view_id: String,
+    pub hash: Option<String>,
+}
+
+impl ViewHandle {
+    pub fn new<S: Into<String>>(view_id: S, hash: Option<String>) -> Self {
+        Self {
+            view_id: view_id.into(),
+            hash,
+        }
+    }
+
+    // Adopts a newer version seen elsewhere, e.g. the view in a block_actions payload
+    pub fn observe(&mut self, view: &ViewPayload) {
+        if view.id == self.view_id {
+            self.hash = view.hash.clone();
+        }
+    }
+
+    // A `hash_conflict` is returned as is and the hash kept: Slack has no call to read a view
+    // back, so only the caller can know what the newer version holds. `observe` it (e.g. from
+    // the next block_actions payload) and build the update from that.
+    pub async fn update(&mut self, client: &dyn SlackApi, view: &View) -> Result<ViewPayload> {
+        let updated = client
+            .update_view(&self.view_id, view, self.hash.as_deref())
+            .await
+            .inspect_err(|e| {
+                if matches!(e, SlackError::SlackApi { code, .. } if code == "hash_conflict") {
+                    warn!("View {} changed since hash {:?}, not updating it", self.view_id, self.hash);
+                }
+            })?;
+        self.observe(&updated);
+        Ok(updated)
+    }
+}
+
+impl From<&ViewPayload> for ViewHandle {
+    fn from(view: &ViewPayload) -> Self {
+        Self::new(view.id.clone(), view.hash.clone())
+    }
+}
+
+impl ViewPayload {
+    // For views returned by views.open, views.push and views.publish
+    pub fn handle(&self) -> ViewHandle {
+        ViewHandle::from(self)
+    }
+}
@@ -1,13 +1,18 @@
 use crate::request::SlackRequest;
 use crate::response::{SlackResponse, SlackResponseBody, TextResponse, BlocksResponse};
+use crate::blocks::options::{validate_option_groups, validate_options};
+use crate::blocks::{OptionGroup, OptionObject};
 use crate::error::Result;
-use serde_json::Value;
-use std::sync::Arc;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
 
 #[derive(Clone)]
 pub struct Ack {
+    #[allow(dead_code)]
     request: Arc<SlackRequest>,
     acknowledged: std::sync::Arc<std::sync::Mutex<bool>>,
+    responder: Arc<Mutex<Option<oneshot::Sender<SlackResponse>>>>,
 }
 
 impl Ack {
@@ -15,24 +20,22 @@ impl Ack {
         Self {
             request,
             acknowledged: Arc::new(std::sync::Mutex::new(false)),
+            responder: Arc::new(Mutex::new(None)),
         }
     }
 
     pub async fn empty(&self) -> Result<SlackResponse> {
-        self.mark_acknowledged();
-        Ok(SlackResponse::empty())
+        Ok(self.acknowledge(SlackResponse::empty()))
     }
 
     pub async fn text<S: Into<String>>(&self, text: S) -> Result<SlackResponse> {
-        self.mark_acknowledged();
-        Ok(SlackResponse::text(text))
+        Ok(self.acknowledge(SlackResponse::text(text)))
     }
 
     pub async fn blocks(&self, blocks: Vec<Value>) -> Result<SlackResponse> {
-        self.mark_acknowledged();
-        Ok(SlackResponse {
+        Ok(self.acknowledge(SlackResponse {
             status_code: 200,
-            headers: std::collections::HashMap::new(),
+            headers: http::HeaderMap::new(),
             body: SlackResponseBody::Blocks(BlocksResponse {
                 blocks,
                 text: None,
@@ -40,41 +43,67 @@ impl Ack {
                 replace_original: None,
                 delete_original: None,
             }),
-        })
+        }))
     }
 
     pub async fn ephemeral<S: Into<String>>(&self, text: S) -> Result<SlackResponse> {
-        self.mark_acknowledged();
-        Ok(SlackResponse {
+        Ok(self.acknowledge(SlackResponse {
             status_code: 200,
-            headers: std::collections::HashMap::new(),
+            headers: http::HeaderMap::new(),
             body: SlackResponseBody::Text(TextResponse {
                 text: text.into(),
                 response_type: Some("ephemeral".to_string()),
                 replace_original: None,
                 delete_original: None,
             }),
-        })
+        }))
     }
 
     pub async fn in_channel<S: Into<String>>(&self, text: S) -> Result<SlackResponse> {
-        self.mark_acknowledged();
-        Ok(SlackResponse {
+        Ok(self.acknowledge(SlackResponse {
             status_code: 200,
-            headers: std::collections::HashMap::new(),
+            headers: http::HeaderMap::new(),
             body: SlackResponseBody::Text(TextResponse {
                 text: text.into(),
                 response_type: Some("in_channel".to_string()),
                 replace_original: None,
                 delete_original: None,
             }),
-        })
+        }))
     }
 
-    fn mark_acknowledged(&self) {
+    // Any other acknowledgement body, e.g. a view_submission `response_action`
+    pub async fn json(&self, body: Value) -> Result<SlackResponse> {
+        Ok(self.acknowledge(SlackResponse::json(body)))
+    }
+
+    // Answers a block_suggestion (external select) request. Over-long lists are an error here
+    // rather than a silently empty menu on the user's side.
+    pub async fn options(&self, options: Vec<OptionObject>) -> Result<SlackResponse> {
+        validate_options(&options)?;
+        Ok(self.acknowledge(SlackResponse::json(json!({ "options": options }))))
+    }
+
+    pub async fn option_groups(&self, option_groups: Vec<OptionGroup>) -> Result<SlackResponse> {
+        validate_option_groups(&option_groups)?;
+        Ok(self.acknowledge(SlackResponse::json(json!({ "option_groups": option_groups }))))
+    }
+
+    // With process_before_response off, the first ack is what the adapter returns to Slack
+    // while the listener keeps running
+    pub(crate) fn with_responder(mut self, responder: oneshot::Sender<SlackResponse>) -> Self {
+        self.responder = Arc::new(Mutex::new(Some(responder)));
+        self
+    }
+
+    fn acknowledge(&self, response: SlackResponse) -> SlackResponse {
         if let Ok(mut acked) = self.acknowledged.lock() {
             *acked = true;
         }
+        if let Some(responder) = self.responder.lock().ok().and_then(|mut responder| responder.take()) {
+            let _ = responder.send(response.clone());
+        }
+        response
     }
 
     pub fn is_acknowledged(&self) -> bool {
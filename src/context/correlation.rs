@@ -0,0 +1,63 @@
+use http::HeaderMap;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+// Sent on outgoing Web API calls and every HTTP response, and read from incoming requests
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+// Upstream headers worth adopting, in order of preference
+const INCOMING_HEADERS: &[&str] = &[CORRELATION_ID_HEADER, "x-request-id"];
+
+tokio::task_local! {
+    // Set while a request is being routed so the client can tag the calls it makes
+    pub(crate) static CURRENT: String;
+}
+
+pub(crate) fn current() -> Option<String> {
+    CURRENT.try_with(|correlation_id| correlation_id.clone()).ok()
+}
+
+pub fn generate() -> String {
+    unique_id()
+}
+
+// Random with the `uuid` feature. Without it, distinct per process from the clock, process ID
+// and a counter: fine for IDs that only have to be unique, never for secrets such as OAuth state.
+#[cfg(feature = "uuid")]
+pub(crate) fn unique_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+#[cfg(not(feature = "uuid"))]
+pub(crate) fn unique_id() -> String {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+    format!(
+        "{:016x}-{:08x}-{:08x}",
+        nanos as u64,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+// An ID set by a caller or proxy in front of the function. Anything that couldn't safely be
+// echoed into logs and response headers is ignored.
+pub fn from_headers(headers: &HeaderMap) -> Option<String> {
+    INCOMING_HEADERS
+        .iter()
+        .filter_map(|name| headers.get(*name).and_then(|v| v.to_str().ok()))
+        .map(str::trim)
+        .find(|id| is_valid(id))
+        .map(str::to_string)
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/'))
+}
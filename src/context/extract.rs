@@ -0,0 +1,120 @@
+use crate::context::Context;
+use crate::error::{Result, SlackError};
+use crate::request::payloads::{BlockAction, GlobalShortcut, MessageShortcut, TypedEvent, ViewPayload};
+use crate::request::{CommandRequest, InteractiveRequest, SlackRequestBody};
+
+impl Context {
+    pub fn event<T: TypedEvent>(&self) -> Result<T> {
+        let SlackRequestBody::Event(event) = &self.request.body else {
+            return Err(self.unexpected(T::EVENT_TYPE));
+        };
+
+        let event_type = event.event.get("type").and_then(|v| v.as_str()).unwrap_or_default();
+        if event_type != T::EVENT_TYPE {
+            return Err(SlackError::UnexpectedPayload {
+                expected: format!("{} event", T::EVENT_TYPE),
+                found: format!("{} event", event_type),
+            });
+        }
+
+        serde_json::from_value(event.event.clone()).map_err(|e| SlackError::UnexpectedPayload {
+            expected: format!("{} event", T::EVENT_TYPE),
+            found: format!("malformed {} event ({})", event_type, e),
+        })
+    }
+
+    pub fn command(&self) -> Result<&CommandRequest> {
+        match &self.request.body {
+            SlackRequestBody::Command(command) => Ok(command),
+            _ => Err(self.unexpected("slash command")),
+        }
+    }
+
+    // The first action of a block_actions payload
+    pub fn action(&self) -> Result<BlockAction> {
+        let action = match &self.request.body {
+            SlackRequestBody::Interactive(interactive) => interactive.actions.first(),
+            _ => None,
+        }
+        .ok_or_else(|| self.unexpected("block action"))?;
+
+        serde_json::from_value(action.clone()).map_err(|e| SlackError::UnexpectedPayload {
+            expected: "block action".to_string(),
+            found: format!("malformed action ({})", e),
+        })
+    }
+
+    // The view of a view_submission/view_closed payload, or of an app_home_opened event
+    pub fn view(&self) -> Result<ViewPayload> {
+        let view = match &self.request.body {
+            SlackRequestBody::Interactive(interactive) => interactive.view.as_ref(),
+            SlackRequestBody::Event(event) => event.event.get("view"),
+            _ => None,
+        }
+        .filter(|view| !view.is_null())
+        .ok_or_else(|| self.unexpected("view"))?;
+
+        serde_json::from_value(view.clone()).map_err(|e| SlackError::UnexpectedPayload {
+            expected: "view".to_string(),
+            found: format!("malformed view ({})", e),
+        })
+    }
+
+    pub fn global_shortcut(&self) -> Result<GlobalShortcut> {
+        let shortcut = self.shortcut_payload("shortcut", "global shortcut")?;
+
+        Ok(GlobalShortcut {
+            callback_id: shortcut.callback_id.clone().unwrap_or_default(),
+            trigger_id: shortcut.trigger_id.clone(),
+            action_ts: shortcut.action_ts.clone(),
+            user_id: string_field(&shortcut.user, "id").unwrap_or_default(),
+            team_id: string_field(&shortcut.team, "id"),
+        })
+    }
+
+    pub fn message_shortcut(&self) -> Result<MessageShortcut> {
+        let shortcut = self.shortcut_payload("message_action", "message shortcut")?;
+        let channel = shortcut.channel.as_ref().unwrap_or(&serde_json::Value::Null);
+        let message = shortcut.message.clone().unwrap_or_default();
+
+        Ok(MessageShortcut {
+            callback_id: shortcut.callback_id.clone().unwrap_or_default(),
+            trigger_id: shortcut.trigger_id.clone(),
+            action_ts: shortcut.action_ts.clone(),
+            user_id: string_field(&shortcut.user, "id").unwrap_or_default(),
+            team_id: string_field(&shortcut.team, "id"),
+            channel_id: string_field(channel, "id").unwrap_or_default(),
+            channel_name: string_field(channel, "name"),
+            message_ts: shortcut
+                .message_ts
+                .clone()
+                .or_else(|| string_field(&message, "ts"))
+                .unwrap_or_default(),
+            message,
+            response_url: shortcut.response_url.clone(),
+        })
+    }
+
+    fn shortcut_payload(&self, payload_type: &str, expected: &str) -> Result<&InteractiveRequest> {
+        match &self.request.body {
+            SlackRequestBody::Interactive(interactive) if interactive.payload_type == payload_type => Ok(interactive),
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> SlackError {
+        let found = match &self.request.body {
+            SlackRequestBody::Interactive(interactive) => format!("{} payload", interactive.payload_type),
+            body => format!("{} request", body.kind()),
+        };
+
+        SlackError::UnexpectedPayload {
+            expected: expected.to_string(),
+            found,
+        }
+    }
+}
+
+fn string_field(value: &serde_json::Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
+}
@@ -0,0 +1,49 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Metadata about the platform invocation serving the current request
+#[derive(Debug, Clone, Default)]
+pub struct Invocation {
+    pub request_id: String,
+    pub function_arn: Option<String>,
+    pub xray_trace_id: Option<String>,
+    pub deadline: Option<SystemTime>,
+}
+
+impl Invocation {
+    pub fn new<S: Into<String>>(request_id: S) -> Self {
+        Self {
+            request_id: request_id.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_function_arn<S: Into<String>>(mut self, function_arn: S) -> Self {
+        self.function_arn = Some(function_arn.into());
+        self
+    }
+
+    pub fn with_xray_trace_id<S: Into<String>>(mut self, xray_trace_id: S) -> Self {
+        self.xray_trace_id = Some(xray_trace_id.into());
+        self
+    }
+
+    pub fn with_deadline_ms(mut self, deadline_ms: u64) -> Self {
+        self.deadline = Some(UNIX_EPOCH + Duration::from_millis(deadline_ms));
+        self
+    }
+
+    // None when the platform gave no deadline; zero once the deadline has passed
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+    }
+}
+
+tokio::task_local! {
+    // Set by the adapter for the duration of a platform invocation
+    pub(crate) static CURRENT: Invocation;
+}
+
+pub(crate) fn current() -> Option<Invocation> {
+    CURRENT.try_with(|invocation| invocation.clone()).ok()
+}
@@ -1,46 +1,382 @@
 pub mod ack;
+pub mod correlation;
+pub mod extract;
+pub mod invocation;
+pub mod progress;
+pub mod respond;
+pub mod webhook;
+pub mod workflow;
 
 pub use ack::Ack;
+pub use invocation::Invocation;
+pub use progress::Progress;
+pub use webhook::TeamWebhook;
 
-use crate::client::SlackClient;
-use crate::request::SlackRequest;
-use crate::error::Result;
-use serde_json::Value;
+use crate::adapter::RequestQueue;
+use crate::app::{AppState, HomeTab};
+use crate::client::{Channel, SlackApi};
+use crate::metrics::{Metrics, NoopMetrics};
+use crate::conversation::{ConversationKey, ConversationState, ConversationStore};
+use crate::i18n::Localizer;
+use crate::oauth::Installation;
+use crate::request::{EventAuthorization, EventRequest, GatewayContext, SlackRequest, SlackRequestBody};
+use crate::settings::{TeamSettings, TeamSettingsStore};
+use crate::error::{Result, SlackError};
+use chrono::Duration;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::sync::OnceCell;
 
 #[derive(Clone)]
 pub struct Context {
     pub request: Arc<SlackRequest>,
-    pub client: Arc<SlackClient>,
+    pub client: Arc<dyn SlackApi>,
     pub ack: Ack,
     pub say: Say,
     pub body: Value,
     pub payload: Value,
+    // The request's span, or inside a listener that listener's child span, already entered.
+    // Work moved onto `tokio::spawn` needs `.instrument(context.logger.clone())` to keep it.
     pub logger: tracing::Span,
     pub custom: HashMap<String, Value>,
+    pub conversation: Option<Value>,
+    pub metrics: Arc<dyn Metrics>,
+    invocation: Invocation,
+    state: Arc<AppState>,
+    installation: Option<Installation>,
+    user_client: Option<Arc<dyn SlackApi>>,
+    locale: Option<String>,
+    localizer: Option<Arc<dyn Localizer>>,
+    channel_info: Arc<OnceCell<Channel>>,
+    team_settings: Arc<OnceCell<TeamSettings>>,
+    team_settings_store: Option<Arc<dyn TeamSettingsStore>>,
+    home: Option<Arc<HomeTab>>,
+    conversation_store: Option<(Arc<dyn ConversationStore>, ConversationKey)>,
+    request_queue: Option<Arc<dyn RequestQueue>>,
 }
 
 impl Context {
     pub fn new(
         request: SlackRequest,
-        client: SlackClient,
+        client: Arc<dyn SlackApi>,
     ) -> Self {
+        let body = serde_json::to_value(&request.body).unwrap_or(Value::Null);
+        let payload = Self::extract_payload(&request.body, &body);
         let request_arc = Arc::new(request);
-        let client_arc = Arc::new(client);
         
         Self {
             ack: Ack::new(request_arc.clone()),
-            say: Say::new(client_arc.clone(), request_arc.clone()),
-            body: Value::Null,
-            payload: Value::Null,
-            logger: tracing::span!(tracing::Level::INFO, "slack_request"),
+            say: Say::new(client.clone(), request_arc.clone()),
+            body,
+            payload,
+            logger: Self::request_span(&request_arc),
             custom: HashMap::new(),
+            conversation: None,
+            metrics: Arc::new(NoopMetrics),
+            invocation: Invocation::default(),
+            state: Arc::new(AppState::new()),
+            installation: None,
+            user_client: None,
+            locale: request_arc.locale().map(str::to_string),
+            localizer: None,
+            channel_info: Arc::new(OnceCell::new()),
+            team_settings: Arc::new(OnceCell::new()),
+            team_settings_store: None,
+            home: None,
+            conversation_store: None,
+            request_queue: None,
             request: request_arc,
-            client: client_arc,
+            client,
         }
     }
 
+    fn request_span(request: &SlackRequest) -> tracing::Span {
+        let span = tracing::info_span!(
+            "slack_request",
+            kind = request.body.kind(),
+            correlation_id = tracing::field::Empty,
+            team_id = tracing::field::Empty,
+            enterprise_id = tracing::field::Empty,
+            user_id = tracing::field::Empty,
+            channel_id = tracing::field::Empty,
+            routing_key = tracing::field::Empty,
+        );
+
+        if let Some(correlation_id) = &request.correlation_id {
+            span.record("correlation_id", correlation_id.as_str());
+        }
+        if let Some(team_id) = request.team_id() {
+            span.record("team_id", team_id);
+        }
+        if let Some(enterprise_id) = request.enterprise_id() {
+            span.record("enterprise_id", enterprise_id);
+        }
+        if let Some(user_id) = request.user_id() {
+            span.record("user_id", user_id);
+        }
+        if let Some(channel_id) = request.channel_id() {
+            span.record("channel_id", channel_id);
+        }
+        if let Some(routing_key) = request.routing_key() {
+            span.record("routing_key", routing_key);
+        }
+
+        span
+    }
+
+    fn extract_payload(request_body: &SlackRequestBody, body: &Value) -> Value {
+        match request_body {
+            SlackRequestBody::Event(event) => event.event.clone(),
+            SlackRequestBody::Interactive(interactive) => match interactive.payload_type.as_str() {
+                "block_actions" | "interactive_message" => {
+                    interactive.actions.first().cloned().unwrap_or(Value::Null)
+                }
+                "view_submission" | "view_closed" => interactive.view.clone().unwrap_or(Value::Null),
+                _ => body.clone(),
+            },
+            _ => body.clone(),
+        }
+    }
+
+    // Acts as the bot: the installation's bot token, or the app's token for single-workspace apps
+    pub fn client(&self) -> Arc<dyn SlackApi> {
+        self.client.clone()
+    }
+
+    // Acts as the user behind this request, when they authorized the app with user scopes
+    pub fn user_client(&self) -> Option<Arc<dyn SlackApi>> {
+        self.user_client.clone()
+    }
+
+    pub fn installation(&self) -> Option<&Installation> {
+        self.installation.as_ref()
+    }
+
+    pub fn set_installation(&mut self, installation: Installation) {
+        if let Some(bot_token) = &installation.bot_token {
+            self.client = self.client.with_token(bot_token.expose().to_string());
+        }
+
+        // A user token only speaks for the user who granted it
+        self.user_client = match (&installation.user_token, installation.user_id.as_deref()) {
+            (Some(user_token), Some(user_id)) if self.request.user_id() == Some(user_id) => {
+                Some(self.client.with_token(user_token.expose().to_string()))
+            }
+            _ => None,
+        };
+        self.say = Say::new(self.client.clone(), self.request.clone());
+        self.installation = Some(installation);
+    }
+
+    // The outer Events API envelope; None for commands and interactions
+    fn event_envelope(&self) -> Option<&EventRequest> {
+        match &self.request.body {
+            SlackRequestBody::Event(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    // Stable across Slack's retries of the same event, so the key to deduplicate on
+    pub fn event_id(&self) -> Option<&str> {
+        self.event_envelope()?.event_id.as_deref()
+    }
+
+    pub fn event_context(&self) -> Option<&str> {
+        self.event_envelope()?.event_context.as_deref()
+    }
+
+    // Slack sends only the receiving installation here; the full list of installations that
+    // can see the event comes from apps.event.authorizations.list with `event_context`
+    pub fn authorizations(&self) -> &[EventAuthorization] {
+        self.event_envelope()
+            .map(|event| event.authorizations.as_slice())
+            .unwrap_or_default()
+    }
+
+    // Whether the event came from a Slack Connect channel, without a conversations.info call
+    pub fn is_ext_shared_channel(&self) -> bool {
+        self.event_envelope().is_some_and(|event| event.is_ext_shared_channel)
+    }
+
+    // Which of Slack's retries this is, None for the first delivery. Listeners only see
+    // timed-out duplicates when registered with `receive_retries(true)`.
+    pub fn retry_num(&self) -> Option<u32> {
+        self.request.retry_num()
+    }
+
+    pub fn retry_reason(&self) -> Option<&str> {
+        self.request.retry_reason()
+    }
+
+    // The external_id the app gave the view this request came from (a submission, a block
+    // action inside a modal, or app_home_opened)
+    pub fn view_external_id(&self) -> Option<&str> {
+        let view = match &self.request.body {
+            SlackRequestBody::Interactive(interactive) => interactive.view.as_ref(),
+            SlackRequestBody::Event(event) => event.event.get("view"),
+            _ => None,
+        };
+        view?.get("external_id").and_then(|v| v.as_str()).filter(|id| !id.is_empty())
+    }
+
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    // Falls back to users.info with include_locale when the payload didn't carry a locale
+    pub async fn resolve_locale(&mut self) -> Result<Option<&str>> {
+        if self.locale.is_none() {
+            if let Some(user_id) = self.request.user_id() {
+                let response = self
+                    .client
+                    .call("users.info", json!({ "user": user_id, "include_locale": true }))
+                    .await?;
+                self.locale = response
+                    .get("user")
+                    .and_then(|user| user.get("locale"))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+            }
+        }
+        Ok(self.locale.as_deref())
+    }
+
+    pub fn set_localizer(&mut self, localizer: Arc<dyn Localizer>) {
+        self.localizer = Some(localizer);
+    }
+
+    // Translated string for the user's locale; the key itself when no translation exists
+    pub fn t(&self, key: &str) -> String {
+        self.localizer
+            .as_ref()
+            .and_then(|localizer| localizer.localize(self.locale(), key))
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    // conversations.info for the request's channel, fetched at most once per request;
+    // check `is_shared`/`is_ext_shared` before echoing data into Slack Connect channels
+    pub async fn channel_info(&self) -> Result<Channel> {
+        let channel_id = self.request.channel_id().ok_or_else(|| SlackError::UnexpectedPayload {
+            expected: "request in a channel".to_string(),
+            found: format!("{} request without a channel", self.request.body.kind()),
+        })?;
+
+        self.channel_info
+            .get_or_try_init(|| self.client.conversation_info(channel_id))
+            .await
+            .cloned()
+    }
+
+    pub fn set_team_settings_store(&mut self, store: Arc<dyn TeamSettingsStore>) {
+        self.team_settings_store = Some(store);
+        self.team_settings = Arc::new(OnceCell::new());
+    }
+
+    // The requesting workspace's settings, loaded at most once per request; empty settings
+    // when the workspace has never saved any
+    pub async fn team_settings(&self) -> Result<TeamSettings> {
+        let (store, team_id) = self.team_settings_target()?;
+
+        self.team_settings
+            .get_or_try_init(|| store.find_or_default(team_id, self.request.enterprise_id()))
+            .await
+            .cloned()
+    }
+
+    // `team_settings()` keeps returning the copy loaded earlier in this request
+    pub async fn save_team_settings(&self, settings: &TeamSettings) -> Result<()> {
+        let (store, _) = self.team_settings_target()?;
+        store.save(settings).await
+    }
+
+    fn team_settings_target(&self) -> Result<(&Arc<dyn TeamSettingsStore>, &str)> {
+        let store = self.team_settings_store.as_ref().ok_or_else(|| {
+            SlackError::Config("No team settings store configured for this request".to_string())
+        })?;
+        let team_id = self.request.team_id().ok_or_else(|| SlackError::UnexpectedPayload {
+            expected: "request from a workspace".to_string(),
+            found: format!("{} request without a team", self.request.body.kind()),
+        })?;
+        Ok((store, team_id))
+    }
+
+    pub fn set_home_tab(&mut self, home: Arc<HomeTab>) {
+        self.home = Some(home);
+    }
+
+    // Re-renders and publishes a user's home tab, e.g. after a button changed what it shows
+    pub async fn refresh_home(&self, user_id: &str) -> Result<()> {
+        let home = self
+            .home
+            .as_ref()
+            .ok_or_else(|| SlackError::Config("No home tab registered with app.home".to_string()))?;
+        home.publish(self.client.as_ref(), self.request.team_id(), user_id).await?;
+        Ok(())
+    }
+
+    // How the request reached the function (stage, source IP, authorizer claims) when it
+    // came through API Gateway
+    pub fn gateway(&self) -> Option<&GatewayContext> {
+        self.request.gateway.as_ref()
+    }
+
+    // Set for every dispatched request; follows it into deferred work and outgoing API calls
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.request.correlation_id.as_deref()
+    }
+
+    pub fn set_request_queue(&mut self, queue: Arc<dyn RequestQueue>) {
+        self.request_queue = Some(queue);
+    }
+
+    // Hands this request to the app's `request_queue` so an `SqsHandler` runs the listeners
+    // again, to completion, outside this invocation. Defer before acking, as Lambda may freeze
+    // the invocation once Slack has its response; the deferred run sees `is_deferred()` and
+    // does the slow part:
+    //
+    //     if !context.is_deferred() {
+    //         context.defer().await?;
+    //         return context.ack.text("Working on it...").await;
+    //     }
+    pub async fn defer(&self) -> Result<()> {
+        if self.is_deferred() {
+            return Err(SlackError::Internal("Request is already deferred".to_string()));
+        }
+        let queue = self
+            .request_queue
+            .as_ref()
+            .ok_or_else(|| SlackError::Config("No request queue configured with request_queue".to_string()))?;
+
+        let mut request = SlackRequest::clone(&self.request);
+        request.deferred = true;
+        queue.send(serde_json::to_string(&request)?).await
+    }
+
+    pub fn is_deferred(&self) -> bool {
+        self.request.deferred
+    }
+
+    pub fn source_ip(&self) -> Option<&str> {
+        self.gateway().and_then(|gateway| gateway.source_ip.as_deref())
+    }
+
+    pub fn invocation(&self) -> &Invocation {
+        &self.invocation
+    }
+
+    pub fn set_invocation(&mut self, invocation: Invocation) {
+        self.invocation = invocation;
+    }
+
+    pub fn state<T: Send + Sync + 'static>(&self) -> Result<Arc<T>> {
+        self.state.get::<T>()
+    }
+
+    pub fn set_state(&mut self, state: Arc<AppState>) {
+        self.state = state;
+    }
+
     pub fn set_custom<K: Into<String>>(&mut self, key: K, value: Value) {
         self.custom.insert(key.into(), value);
     }
@@ -48,28 +384,66 @@ impl Context {
     pub fn get_custom<K: AsRef<str>>(&self, key: K) -> Option<&Value> {
         self.custom.get(key.as_ref())
     }
+
+    pub async fn load_conversation(&mut self, store: Arc<dyn ConversationStore>) -> Result<()> {
+        // Requests without a team or channel have no conversation to attach
+        if let Some(key) = ConversationKey::from_request(&self.request) {
+            self.conversation = store.get(&key).await?;
+            self.conversation_store = Some((store, key));
+        }
+        Ok(())
+    }
+
+    pub async fn update_conversation(&mut self, value: Value, ttl: Option<Duration>) -> Result<()> {
+        let (store, key) = self.conversation_store.as_ref().ok_or_else(|| {
+            SlackError::Config("No conversation store configured for this request".to_string())
+        })?;
+
+        let mut state = ConversationState::new(value.clone());
+        if let Some(ttl) = ttl {
+            state = state.with_ttl(ttl);
+        }
+
+        store.update(key, &state).await?;
+        self.conversation = Some(value);
+        Ok(())
+    }
+
+    pub async fn expire_conversation(&mut self) -> Result<()> {
+        if let Some((store, key)) = &self.conversation_store {
+            store.expire(key).await?;
+        }
+        self.conversation = None;
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
 pub struct Say {
-    client: Arc<SlackClient>,
+    client: Arc<dyn SlackApi>,
     request: Arc<SlackRequest>,
 }
 
 impl Say {
-    pub fn new(client: Arc<SlackClient>, request: Arc<SlackRequest>) -> Self {
+    pub fn new(client: Arc<dyn SlackApi>, request: Arc<SlackRequest>) -> Self {
         Self { client, request }
     }
 
+    // Posts to the channel the request came from
     pub async fn text<S: Into<String>>(&self, text: S) -> Result<()> {
-        // Extract channel from request and send message
-        // This is a placeholder implementation
-        Ok(())
+        self.post(json!({ "text": text.into() })).await
     }
 
     pub async fn blocks(&self, blocks: Vec<Value>) -> Result<()> {
-        // Send message with blocks
-        // This is a placeholder implementation
-        Ok(())
+        self.post(json!({ "blocks": blocks })).await
+    }
+
+    async fn post(&self, mut payload: Value) -> Result<()> {
+        let channel = self.request.channel_id().ok_or_else(|| SlackError::UnexpectedPayload {
+            expected: "request in a channel".to_string(),
+            found: format!("{} request without a channel", self.request.body.kind()),
+        })?;
+        payload["channel"] = json!(channel);
+        self.client.call("chat.postMessage", payload).await.map(|_| ())
     }
 }
\ No newline at end of file
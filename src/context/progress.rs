@@ -0,0 +1,71 @@
+use crate::client::SlackApi;
+use crate::context::Context;
+use crate::error::{Result, SlackError};
+use crate::request::SlackRequestBody;
+use crate::response::SlackResponse;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+// A placeholder message shown while a slow command or interaction runs, replaced in place
+// through the request's response_url. Slack accepts five response_url posts per request,
+// so keep `update` calls to a minimum.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    client: Arc<dyn SlackApi>,
+    response_url: String,
+    ack: SlackResponse,
+}
+
+impl Context {
+    // Acks right away and shows `text` as a placeholder: ephemeral for slash commands, in
+    // place of the source message for interactions. Return `Progress::ack_response` from the
+    // handler. The work itself must still fit the invocation, so pair this with
+    // `process_before_response(false)` or deferred processing for anything near 3 seconds.
+    pub async fn progress<S: Into<String>>(&self, text: S) -> Result<Progress> {
+        let response_url = self
+            .response_url()
+            .ok_or_else(|| SlackError::UnexpectedPayload {
+                expected: "request with a response_url".to_string(),
+                found: format!("{} request", self.request.body.kind()),
+            })?
+            .to_string();
+
+        let ack = self.ack.empty().await?;
+        let placeholder = match &self.request.body {
+            SlackRequestBody::Command(_) => json!({ "response_type": "ephemeral", "text": text.into() }),
+            _ => json!({ "replace_original": true, "text": text.into() }),
+        };
+        self.client.respond(&response_url, placeholder).await?;
+
+        Ok(Progress {
+            client: self.client.clone(),
+            response_url,
+            ack,
+        })
+    }
+}
+
+impl Progress {
+    // What the handler should return; the placeholder has already been posted
+    pub fn ack_response(&self) -> SlackResponse {
+        self.ack.clone()
+    }
+
+    pub async fn update<S: Into<String>>(&self, text: S) -> Result<()> {
+        self.replace(json!({ "replace_original": true, "text": text.into() }))
+            .await
+    }
+
+    pub async fn finish(&self, blocks: Vec<Value>) -> Result<()> {
+        self.replace(json!({ "replace_original": true, "blocks": blocks }))
+            .await
+    }
+
+    pub async fn finish_text<S: Into<String>>(&self, text: S) -> Result<()> {
+        self.update(text).await
+    }
+
+    async fn replace(&self, payload: Value) -> Result<()> {
+        self.client.respond(&self.response_url, payload).await
+    }
+}
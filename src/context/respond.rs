@@ -0,0 +1,88 @@
+use crate::client::{DeleteMessageRequest, UpdateMessageRequest};
+use crate::context::Context;
+use crate::error::{Result, SlackError};
+use crate::request::SlackRequestBody;
+use serde_json::{json, Value};
+
+impl Context {
+    // The response_url of a slash command or interaction, if Slack sent one
+    pub fn response_url(&self) -> Option<&str> {
+        match &self.request.body {
+            SlackRequestBody::Interactive(interactive) => Some(interactive.response_url.as_str()),
+            SlackRequestBody::Command(command) => Some(command.response_url.as_str()),
+            _ => None,
+        }
+        .filter(|url| !url.is_empty())
+    }
+
+    pub async fn respond(&self, payload: Value) -> Result<()> {
+        let response_url = self.response_url().ok_or_else(|| SlackError::UnexpectedPayload {
+            expected: "request with a response_url".to_string(),
+            found: format!("{} request", self.request.body.kind()),
+        })?;
+        self.client.respond(response_url, payload).await
+    }
+
+    // Replaces the blocks of the message a button or menu was used on. Goes through chat.update
+    // when the payload names the message, and through response_url for ephemeral messages or
+    // messages the bot did not post itself.
+    pub async fn update_source_message(&self, blocks: Vec<Value>) -> Result<()> {
+        if let Some((channel, ts)) = self.source_message() {
+            let update = self
+                .client
+                .update_message(&UpdateMessageRequest {
+                    channel,
+                    ts,
+                    text: None,
+                    blocks: Some(blocks.clone()),
+                    metadata: None,
+                })
+                .await;
+            match update {
+                Err(SlackError::SlackApi { code, .. })
+                    if is_foreign_message(&code) && self.response_url().is_some() => {}
+                result => return result.map(|_| ()),
+            }
+        }
+
+        self.respond(json!({ "replace_original": true, "blocks": blocks }))
+            .await
+    }
+
+    pub async fn delete_source_message(&self) -> Result<()> {
+        if let Some((channel, ts)) = self.source_message() {
+            let delete = self.client.delete_message(&DeleteMessageRequest { channel, ts }).await;
+            match delete {
+                Err(SlackError::SlackApi { code, .. })
+                    if is_foreign_message(&code) && self.response_url().is_some() => {}
+                result => return result.map(|_| ()),
+            }
+        }
+
+        self.respond(json!({ "delete_original": true })).await
+    }
+
+    // Channel and ts of the message an interaction came from; ephemeral messages have neither
+    fn source_message(&self) -> Option<(String, String)> {
+        let SlackRequestBody::Interactive(interactive) = &self.request.body else {
+            return None;
+        };
+        let channel = interactive.channel.as_ref()?.get("id")?.as_str()?;
+        let ts = interactive
+            .message
+            .as_ref()
+            .and_then(|message| message.get("ts"))
+            .and_then(|v| v.as_str())
+            .or(interactive.message_ts.as_deref())?;
+        Some((channel.to_string(), ts.to_string()))
+    }
+}
+
+// Errors for messages chat.update/chat.delete cannot touch but response_url can, such as
+// ones posted in response to a slash command
+fn is_foreign_message(code: &str) -> bool {
+    matches!(
+        code,
+        "cant_update_message" | "cant_delete_message" | "message_not_found"
+    )
+}
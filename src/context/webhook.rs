@@ -0,0 +1,52 @@
+use crate::client::SlackApi;
+use crate::context::Context;
+use crate::error::Result;
+use crate::oauth::IncomingWebhook;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+// The incoming webhook the workspace granted at install time. Posts land in the channel the
+// installer picked, without the bot having to be a member of it.
+#[derive(Debug, Clone)]
+pub struct TeamWebhook {
+    client: Arc<dyn SlackApi>,
+    webhook: IncomingWebhook,
+}
+
+impl TeamWebhook {
+    pub fn channel_id(&self) -> Option<&str> {
+        self.webhook.channel_id.as_deref()
+    }
+
+    // "#general" style, as shown to the installer
+    pub fn channel(&self) -> Option<&str> {
+        self.webhook.channel.as_deref()
+    }
+
+    pub fn url(&self) -> &str {
+        &self.webhook.url
+    }
+
+    pub async fn post(&self, message: Value) -> Result<()> {
+        self.client.respond(&self.webhook.url, message).await
+    }
+
+    pub async fn text<S: Into<String>>(&self, text: S) -> Result<()> {
+        self.post(json!({ "text": text.into() })).await
+    }
+
+    pub async fn blocks(&self, blocks: Vec<Value>) -> Result<()> {
+        self.post(json!({ "blocks": blocks })).await
+    }
+}
+
+impl Context {
+    // None unless the installation was granted the `incoming-webhook` scope
+    pub fn team_webhook(&self) -> Option<TeamWebhook> {
+        let webhook = self.installation()?.incoming_webhook.clone()?;
+        Some(TeamWebhook {
+            client: self.client.clone(),
+            webhook,
+        })
+    }
+}
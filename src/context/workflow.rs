@@ -0,0 +1,111 @@
+use crate::client::{
+    FunctionCompleteErrorRequest, FunctionCompleteSuccessRequest, SlackApi, StepCompletedRequest, StepError,
+    StepFailedRequest, UpdateStepRequest,
+};
+use crate::context::Context;
+use crate::error::{Result, SlackError};
+use crate::event_types;
+use crate::listener::FunctionExecutedEvent;
+use serde_json::Value;
+use std::sync::Arc;
+
+impl Context {
+    pub fn workflow_step(&self) -> Option<&Value> {
+        // Edit/save payloads carry it at the top level, execute events inside the event
+        self.body
+            .get("workflow_step")
+            .filter(|step| !step.is_null())
+            .or_else(|| self.payload.get("workflow_step"))
+    }
+
+    pub async fn update_step(&self, inputs: Value, outputs: Vec<Value>) -> Result<()> {
+        let workflow_step_edit_id = self.workflow_step_id("workflow_step_edit_id")?;
+
+        self.client
+            .update_step(&UpdateStepRequest {
+                workflow_step_edit_id,
+                inputs: Some(inputs),
+                outputs: Some(outputs),
+                step_name: None,
+                step_image_url: None,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn complete_step(&self, outputs: Value) -> Result<()> {
+        let workflow_step_execute_id = self.workflow_step_id("workflow_step_execute_id")?;
+
+        self.client
+            .step_completed(&StepCompletedRequest {
+                workflow_step_execute_id,
+                outputs: Some(outputs),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn fail_step<S: Into<String>>(&self, message: S) -> Result<()> {
+        let workflow_step_execute_id = self.workflow_step_id("workflow_step_execute_id")?;
+
+        self.client
+            .step_failed(&StepFailedRequest {
+                workflow_step_execute_id,
+                error: StepError { message: message.into() },
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn function_executed(&self) -> Result<FunctionExecutedEvent> {
+        if self.payload.get("type").and_then(|v| v.as_str()) != Some(event_types::FUNCTION_EXECUTED) {
+            return Err(SlackError::Internal("Request is not a function_executed event".to_string()));
+        }
+        Ok(serde_json::from_value(self.payload.clone())?)
+    }
+
+    pub async fn complete<T: serde::Serialize>(&self, outputs: T) -> Result<()> {
+        let event = self.function_executed()?;
+
+        self.function_client(&event)
+            .complete_function_success(&FunctionCompleteSuccessRequest {
+                function_execution_id: event.function_execution_id.clone(),
+                outputs: serde_json::to_value(outputs)?,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn fail<S: Into<String>>(&self, error: S) -> Result<()> {
+        let event = self.function_executed()?;
+
+        self.function_client(&event)
+            .complete_function_error(&FunctionCompleteErrorRequest {
+                function_execution_id: event.function_execution_id.clone(),
+                error: error.into(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    // Function executions come with a short-lived token scoped to the execution
+    fn function_client(&self, event: &FunctionExecutedEvent) -> Arc<dyn SlackApi> {
+        match &event.bot_access_token {
+            Some(token) => self.client.with_token(token.expose().to_string()),
+            None => self.client.clone(),
+        }
+    }
+
+    fn workflow_step_id(&self, field: &str) -> Result<String> {
+        self.workflow_step()
+            .and_then(|step| step.get(field))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| SlackError::Internal(format!("Request has no workflow step {}", field)))
+    }
+}
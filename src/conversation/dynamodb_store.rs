@@ -0,0 +1,248 @@
+use crate::conversation::{ConversationKey, ConversationLock, ConversationState, ConversationStore};
+use crate::dynamodb::{ensure_table, TableSpec};
+use crate::error::{Result, SlackError};
+use crate::infrastructure::Resource;
+use async_trait::async_trait;
+use aws_sdk_dynamodb::{Client as DynamoDbClient, types::AttributeValue};
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct DynamoDbConversationStore {
+    client: DynamoDbClient,
+    table_name: String,
+}
+
+impl DynamoDbConversationStore {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+
+    fn item(&self, key: &ConversationKey, state: &ConversationState) -> Result<HashMap<String, AttributeValue>> {
+        let mut item = HashMap::new();
+        item.insert("conversation_id".to_string(), AttributeValue::S(key.as_storage_key()));
+        item.insert("value".to_string(), AttributeValue::S(serde_json::to_string(&state.value)?));
+        item.insert("updated_at".to_string(), AttributeValue::S(state.updated_at.to_rfc3339()));
+        item.insert("version".to_string(), AttributeValue::N(state.version.to_string()));
+
+        if let Some(expires_at) = state.expires_at {
+            item.insert("expires_at".to_string(), AttributeValue::N(expires_at.timestamp().to_string()));
+        }
+
+        Ok(item)
+    }
+
+    // Creates the table with its TTL if it doesn't exist yet
+    pub async fn create_table(&self) -> Result<()> {
+        ensure_table(&self.client, &self.spec(), Duration::from_secs(60)).await.map(|_| ())
+    }
+
+    fn spec(&self) -> TableSpec {
+        TableSpec::new(self.table_name.as_str(), "conversation_id").with_ttl("expires_at")
+    }
+}
+
+#[async_trait]
+impl ConversationStore for DynamoDbConversationStore {
+    fn required_resources(&self) -> Vec<Resource> {
+        vec![Resource::Table(self.spec())]
+    }
+
+    async fn find(&self, key: &ConversationKey) -> Result<Option<ConversationState>> {
+        let response = self.client
+            .get_item()
+            .table_name(&self.table_name)
+            .key("conversation_id", AttributeValue::S(key.as_storage_key()))
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        if let Some(item) = response.item {
+            let value = item.get("value")
+                .and_then(|v| v.as_s().ok())
+                .ok_or_else(|| SlackError::Internal("Missing conversation value".to_string()))?;
+
+            let updated_at = item.get("updated_at")
+                .and_then(|v| v.as_s().ok())
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(Utc::now);
+
+            // Stored as epoch seconds so the attribute can double as the table's TTL
+            let expires_at = item.get("expires_at")
+                .and_then(|v| v.as_n().ok())
+                .and_then(|n| n.parse::<i64>().ok())
+                .and_then(|secs| Utc.timestamp_opt(secs, 0).single());
+
+            // Items written before versioning read as version 0
+            let version = item.get("version")
+                .and_then(|v| v.as_n().ok())
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+
+            Ok(Some(ConversationState {
+                value: serde_json::from_str(value)?,
+                updated_at,
+                expires_at,
+                version,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update(&self, key: &ConversationKey, state: &ConversationState) -> Result<()> {
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(self.item(key, state)?))
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn update_versioned(
+        &self,
+        key: &ConversationKey,
+        state: &ConversationState,
+        expected: Option<u64>,
+    ) -> Result<bool> {
+        let request = self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(self.item(key, state)?));
+
+        let request = match expected {
+            Some(expected) => {
+                let condition = if expected == 0 {
+                    "attribute_exists(conversation_id) AND (attribute_not_exists(#version) OR #version = :expected)"
+                } else {
+                    "#version = :expected"
+                };
+                request
+                    .condition_expression(condition)
+                    .expression_attribute_names("#version", "version")
+                    .expression_attribute_values(":expected", AttributeValue::N(expected.to_string()))
+            }
+            None => request.condition_expression("attribute_not_exists(conversation_id)"),
+        };
+
+        match request.send().await {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_service_error().map(|e| e.is_conditional_check_failed_exception()).unwrap_or(false) => {
+                Ok(false)
+            }
+            Err(e) => Err(SlackError::DynamoDb(e.to_string())),
+        }
+    }
+
+    async fn expire(&self, key: &ConversationKey) -> Result<()> {
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key("conversation_id", AttributeValue::S(key.as_storage_key()))
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+// Conditional writes only serialize within a region (global tables resolve conflicts last-writer-wins),
+// so keep the lock table regional even when the other stores are replicated
+#[derive(Debug, Clone)]
+pub struct DynamoDbConversationLock {
+    client: DynamoDbClient,
+    table_name: String,
+    lease_duration: chrono::Duration,
+    wait_timeout: Duration,
+}
+
+impl DynamoDbConversationLock {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self {
+            client,
+            table_name,
+            lease_duration: chrono::Duration::seconds(30),
+            wait_timeout: Duration::from_secs(2),
+        }
+    }
+
+    // Upper bound on how long a crashed holder can block the conversation
+    pub fn with_lease_duration(mut self, lease_duration: chrono::Duration) -> Self {
+        self.lease_duration = lease_duration;
+        self
+    }
+
+    pub fn with_wait_timeout(mut self, wait_timeout: Duration) -> Self {
+        self.wait_timeout = wait_timeout;
+        self
+    }
+
+    // Creates the table with TTL on the lease expiry if it doesn't exist yet
+    pub async fn create_table(&self) -> Result<()> {
+        ensure_table(&self.client, &self.spec(), Duration::from_secs(60)).await.map(|_| ())
+    }
+
+    fn spec(&self) -> TableSpec {
+        TableSpec::new(self.table_name.as_str(), "lock_id").with_ttl("expires_at")
+    }
+}
+
+#[async_trait]
+impl ConversationLock for DynamoDbConversationLock {
+    fn required_resources(&self) -> Vec<Resource> {
+        vec![Resource::Table(self.spec())]
+    }
+
+    async fn try_acquire(&self, key: &ConversationKey, owner: &str) -> Result<bool> {
+        let now = Utc::now();
+        let expires_at = now + self.lease_duration;
+
+        let result = self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .item("lock_id", AttributeValue::S(key.as_storage_key()))
+            .item("owner", AttributeValue::S(owner.to_string()))
+            .item("expires_at", AttributeValue::N(expires_at.timestamp().to_string()))
+            .condition_expression("attribute_not_exists(lock_id) OR expires_at < :now")
+            .expression_attribute_values(":now", AttributeValue::N(now.timestamp().to_string()))
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_service_error().map(|e| e.is_conditional_check_failed_exception()).unwrap_or(false) => {
+                Ok(false)
+            }
+            Err(e) => Err(SlackError::DynamoDb(e.to_string())),
+        }
+    }
+
+    async fn release(&self, key: &ConversationKey, owner: &str) -> Result<()> {
+        let result = self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key("lock_id", AttributeValue::S(key.as_storage_key()))
+            .condition_expression("#owner = :owner")
+            .expression_attribute_names("#owner", "owner")
+            .expression_attribute_values(":owner", AttributeValue::S(owner.to_string()))
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            // The lease expired and someone else holds the lock now; nothing to release
+            Err(e) if e.as_service_error().map(|e| e.is_conditional_check_failed_exception()).unwrap_or(false) => Ok(()),
+            Err(e) => Err(SlackError::DynamoDb(e.to_string())),
+        }
+    }
+
+    fn wait_timeout(&self) -> Duration {
+        self.wait_timeout
+    }
+}
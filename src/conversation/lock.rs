@@ -0,0 +1,42 @@
+use crate::conversation::ConversationKey;
+use crate::error::{Result, SlackError};
+use async_trait::async_trait;
+use crate::infrastructure::Resource;
+use std::fmt::Debug;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+#[async_trait]
+pub trait ConversationLock: Send + Sync + Debug {
+    async fn try_acquire(&self, key: &ConversationKey, owner: &str) -> Result<bool>;
+
+    async fn release(&self, key: &ConversationKey, owner: &str) -> Result<()>;
+
+    fn required_resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+
+    fn wait_timeout(&self) -> Duration {
+        Duration::from_secs(2)
+    }
+
+    fn retry_interval(&self) -> Duration {
+        Duration::from_millis(100)
+    }
+
+    async fn acquire(&self, key: &ConversationKey, owner: &str) -> Result<()> {
+        let deadline = Instant::now() + self.wait_timeout();
+
+        loop {
+            if self.try_acquire(key, owner).await? {
+                return Ok(());
+            }
+
+            if Instant::now() + self.retry_interval() > deadline {
+                return Err(SlackError::LockTimeout(key.as_storage_key()));
+            }
+
+            sleep(self.retry_interval()).await;
+        }
+    }
+}
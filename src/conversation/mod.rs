@@ -0,0 +1,125 @@
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb_store;
+pub mod lock;
+
+#[cfg(feature = "dynamodb")]
+pub use dynamodb_store::{DynamoDbConversationLock, DynamoDbConversationStore};
+pub use lock::ConversationLock;
+
+use crate::error::{Result, SlackError};
+use crate::infrastructure::Resource;
+use crate::request::SlackRequest;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ConversationKey {
+    pub team_id: String,
+    pub channel_id: String,
+    pub thread_ts: Option<String>,
+}
+
+impl ConversationKey {
+    pub fn new<S: Into<String>>(team_id: S, channel_id: S) -> Self {
+        Self {
+            team_id: team_id.into(),
+            channel_id: channel_id.into(),
+            thread_ts: None,
+        }
+    }
+
+    pub fn with_thread_ts<S: Into<String>>(mut self, thread_ts: S) -> Self {
+        self.thread_ts = Some(thread_ts.into());
+        self
+    }
+
+    pub fn from_request(request: &SlackRequest) -> Option<Self> {
+        let key = Self::new(request.team_id()?, request.channel_id()?);
+
+        Some(match request.thread_ts() {
+            Some(thread_ts) => key.with_thread_ts(thread_ts),
+            None => key,
+        })
+    }
+
+    pub fn as_storage_key(&self) -> String {
+        match &self.thread_ts {
+            Some(thread_ts) => format!("{}:{}:{}", self.team_id, self.channel_id, thread_ts),
+            None => format!("{}:{}", self.team_id, self.channel_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationState {
+    pub value: Value,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    // Bumped by every `update_versioned`; plain `update`s keep whatever the caller set
+    #[serde(default)]
+    pub version: u64,
+}
+
+impl ConversationState {
+    pub fn new(value: Value) -> Self {
+        Self {
+            value,
+            updated_at: Utc::now(),
+            expires_at: None,
+            version: 0,
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.expires_at = Some(self.updated_at + ttl);
+        self
+    }
+
+    pub fn is_expired(&self) -> bool {
+        if let Some(expires_at) = self.expires_at {
+            expires_at < Utc::now()
+        } else {
+            false
+        }
+    }
+}
+
+#[async_trait]
+pub trait ConversationStore: Send + Sync + Debug {
+    async fn find(&self, key: &ConversationKey) -> Result<Option<ConversationState>>;
+
+    async fn update(&self, key: &ConversationKey, state: &ConversationState) -> Result<()>;
+
+    async fn expire(&self, key: &ConversationKey) -> Result<()>;
+
+    // Writes `state` only if the stored version is still `expected` (None: nothing stored),
+    // for read-modify-write callers such as `Digest`. Returns false when someone else wrote
+    // first; re-read and try again.
+    async fn update_versioned(
+        &self,
+        key: &ConversationKey,
+        state: &ConversationState,
+        expected: Option<u64>,
+    ) -> Result<bool> {
+        let _ = (key, state, expected);
+        Err(SlackError::Config(format!("{:?} does not support conditional writes", self)))
+    }
+
+    fn required_resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+
+    async fn get(&self, key: &ConversationKey) -> Result<Option<Value>> {
+        match self.find(key).await? {
+            Some(state) if state.is_expired() => {
+                self.expire(key).await?; // Stores without native TTL clean up lazily
+                Ok(None)
+            }
+            Some(state) => Ok(Some(state.value)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,375 @@
+// Table provisioning shared by the DynamoDB-backed stores
+use crate::error::{Result, SlackError};
+use aws_sdk_dynamodb::types::{
+    AttributeDefinition, AttributeValue, BillingMode, CreateReplicationGroupMemberAction, KeySchemaElement, KeyType,
+    Put, PutRequest, ReplicationGroupUpdate, ScalarAttributeType, StreamSpecification, StreamViewType, TableStatus,
+    TimeToLiveSpecification, TimeToLiveStatus, TransactWriteItem, WriteRequest,
+};
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+pub use crate::infrastructure::TableSpec;
+
+pub type Item = HashMap<String, AttributeValue>;
+
+// Most items DynamoDB takes in one BatchWriteItem / TransactWriteItems call
+pub const BATCH_WRITE_LIMIT: usize = 25;
+pub const TRANSACT_WRITE_LIMIT: usize = 100;
+
+// Most item data DynamoDB takes in one BatchWriteItem / TransactWriteItems call
+pub const BATCH_WRITE_BYTES: usize = 16 * 1024 * 1024;
+pub const TRANSACT_WRITE_BYTES: usize = 4 * 1024 * 1024;
+
+// Resends of the items a BatchWriteItem call left unprocessed, backing off from 50ms
+const UNPROCESSED_RETRIES: u32 = 5;
+const UNPROCESSED_BASE_DELAY: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableOutcome {
+    Created,
+    AlreadyExisted,
+}
+
+// Idempotent: an existing table is left as is, apart from enabling TTL and adding replicas that are missing.
+// Returns once the table is ACTIVE or fails after `timeout`.
+pub async fn ensure_table(client: &DynamoDbClient, spec: &TableSpec, timeout: Duration) -> Result<TableOutcome> {
+    let (key_schema, attribute_definitions) = key_schema(spec)?;
+
+    // Replication needs a stream carrying both images
+    let stream_specification = if spec.is_global() {
+        Some(
+            StreamSpecification::builder()
+                .stream_enabled(true)
+                .stream_view_type(StreamViewType::NewAndOldImages)
+                .build()
+                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
+        )
+    } else {
+        None
+    };
+
+    let outcome = match client
+        .create_table()
+        .table_name(&spec.table_name)
+        .set_key_schema(Some(key_schema))
+        .set_attribute_definitions(Some(attribute_definitions))
+        .billing_mode(BillingMode::PayPerRequest)
+        .set_stream_specification(stream_specification)
+        .send()
+        .await
+    {
+        Ok(_) => TableOutcome::Created,
+        Err(e) => {
+            let error = e.into_service_error();
+            if !error.is_resource_in_use_exception() {
+                return Err(SlackError::DynamoDb(error.to_string()));
+            }
+            TableOutcome::AlreadyExisted
+        }
+    };
+
+    wait_until_active(client, &spec.table_name, timeout).await?;
+
+    if let Some(attribute) = &spec.ttl_attribute {
+        enable_ttl(client, &spec.table_name, attribute).await?;
+    }
+
+    if spec.is_global() {
+        add_replicas(client, &spec.table_name, &spec.replica_regions, timeout).await?;
+    }
+
+    Ok(outcome)
+}
+
+pub async fn wait_until_active(client: &DynamoDbClient, table_name: &str, timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let response = client
+            .describe_table()
+            .table_name(table_name)
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        if response.table().and_then(|table| table.table_status()) == Some(&TableStatus::Active) {
+            return Ok(());
+        }
+
+        if Instant::now() >= deadline {
+            return Err(SlackError::DynamoDb(format!(
+                "Timed out waiting for table {} to become ACTIVE",
+                table_name
+            )));
+        }
+
+        sleep(Duration::from_secs(1)).await;
+    }
+}
+
+pub async fn enable_ttl(client: &DynamoDbClient, table_name: &str, attribute: &str) -> Result<()> {
+    let response = client
+        .describe_time_to_live()
+        .table_name(table_name)
+        .send()
+        .await
+        .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+    // Updating TTL while it is enabled (or being enabled) is rejected by DynamoDB
+    let status = response.time_to_live_description().and_then(|d| d.time_to_live_status());
+    if matches!(status, Some(TimeToLiveStatus::Enabled) | Some(TimeToLiveStatus::Enabling)) {
+        return Ok(());
+    }
+
+    let specification = TimeToLiveSpecification::builder()
+        .attribute_name(attribute)
+        .enabled(true)
+        .build()
+        .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+    client
+        .update_time_to_live()
+        .table_name(table_name)
+        .time_to_live_specification(specification)
+        .send()
+        .await
+        .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+    Ok(())
+}
+
+// Adds the regions that are not yet replicas, one at a time since DynamoDB allows a single replica
+// update per UpdateTable call. TTL settings replicate along with the table.
+pub async fn add_replicas(
+    client: &DynamoDbClient,
+    table_name: &str,
+    regions: &[String],
+    timeout: Duration,
+) -> Result<()> {
+    let response = client
+        .describe_table()
+        .table_name(table_name)
+        .send()
+        .await
+        .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+    let home_region = client.config().region().map(|region| region.to_string());
+    let existing: Vec<&str> = response
+        .table()
+        .map(|table| table.replicas().iter().filter_map(|replica| replica.region_name()).collect())
+        .unwrap_or_default();
+
+    for region in regions {
+        if home_region.as_deref() == Some(region.as_str()) || existing.contains(&region.as_str()) {
+            continue;
+        }
+
+        let action = CreateReplicationGroupMemberAction::builder()
+            .region_name(region)
+            .build()
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        client
+            .update_table()
+            .table_name(table_name)
+            .replica_updates(ReplicationGroupUpdate::builder().create(action).build())
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        wait_until_active(client, table_name, timeout).await?;
+    }
+
+    Ok(())
+}
+
+// Puts every item with BatchWriteItem, up to 25 items and 16 MB to a call. DynamoDB hands back
+// the items it could not write when throttled; those are resent with exponential backoff, and
+// the error says how many were still unwritten when the retries ran out. Like PutItem without
+// a condition, existing items are overwritten.
+pub async fn batch_put_items(client: &DynamoDbClient, table_name: &str, items: Vec<Item>) -> Result<()> {
+    for chunk in chunk_items(items, BATCH_WRITE_LIMIT, BATCH_WRITE_BYTES) {
+        let mut requests = chunk
+            .into_iter()
+            .map(|item| {
+                let put = PutRequest::builder()
+                    .set_item(Some(item))
+                    .build()
+                    .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+                Ok(WriteRequest::builder().put_request(put).build())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut attempt = 0;
+        loop {
+            let output = client
+                .batch_write_item()
+                .request_items(table_name, requests)
+                .send()
+                .await
+                .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+            requests = output
+                .unprocessed_items
+                .and_then(|mut unprocessed| unprocessed.remove(table_name))
+                .unwrap_or_default();
+            if requests.is_empty() {
+                break;
+            }
+            if attempt == UNPROCESSED_RETRIES {
+                return Err(SlackError::DynamoDb(format!(
+                    "{} items left unprocessed in {} after {} retries",
+                    requests.len(),
+                    table_name,
+                    UNPROCESSED_RETRIES
+                )));
+            }
+
+            sleep(UNPROCESSED_BASE_DELAY * 2u32.pow(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    Ok(())
+}
+
+// Puts every item with TransactWriteItems, up to 100 items and 4 MB to a call, each checked
+// against `condition` (e.g. "attribute_not_exists(pk)"). A chunk is written all or nothing: one
+// failed condition cancels the whole chunk, and chunks already written stay written.
+pub async fn transact_put_items(
+    client: &DynamoDbClient,
+    table_name: &str,
+    items: Vec<Item>,
+    condition: Option<&str>,
+) -> Result<()> {
+    for chunk in chunk_items(items, TRANSACT_WRITE_LIMIT, TRANSACT_WRITE_BYTES) {
+        let transact_items = chunk
+            .into_iter()
+            .map(|item| {
+                let put = Put::builder()
+                    .table_name(table_name)
+                    .set_item(Some(item))
+                    .set_condition_expression(condition.map(str::to_string))
+                    .build()
+                    .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+                Ok(TransactWriteItem::builder().put(put).build())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        client
+            .transact_write_items()
+            .set_transact_items(Some(transact_items))
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+// Splits items into calls of at most `count` items and `bytes` of item data. An item too big
+// for any call gets a chunk of its own, for DynamoDB to reject.
+fn chunk_items(items: Vec<Item>, count: usize, bytes: usize) -> Vec<Vec<Item>> {
+    let mut chunks = Vec::new();
+    let mut chunk = Vec::new();
+    let mut chunk_bytes = 0;
+
+    for item in items {
+        let size = item_size(&item);
+        if !chunk.is_empty() && (chunk.len() == count || chunk_bytes + size > bytes) {
+            chunks.push(std::mem::take(&mut chunk));
+            chunk_bytes = 0;
+        }
+        chunk_bytes += size;
+        chunk.push(item);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+
+    chunks
+}
+
+// DynamoDB's item size: attribute names plus values. Numbers are counted as their decimal text,
+// which is never less than what DynamoDB charges for them.
+fn item_size(item: &Item) -> usize {
+    item.iter().map(|(name, value)| name.len() + value_size(value)).sum()
+}
+
+fn value_size(value: &AttributeValue) -> usize {
+    match value {
+        AttributeValue::S(s) | AttributeValue::N(s) => s.len(),
+        AttributeValue::B(b) => b.as_ref().len(),
+        AttributeValue::Ss(values) | AttributeValue::Ns(values) => values.iter().map(String::len).sum(),
+        AttributeValue::Bs(values) => values.iter().map(|b| b.as_ref().len()).sum(),
+        AttributeValue::L(values) => 3 + values.iter().map(|value| 1 + value_size(value)).sum::<usize>(),
+        AttributeValue::M(item) => 3 + item.iter().map(|(name, value)| 1 + name.len() + value_size(value)).sum::<usize>(),
+        _ => 1,
+    }
+}
+
+fn key_schema(spec: &TableSpec) -> Result<(Vec<KeySchemaElement>, Vec<AttributeDefinition>)> {
+    let keys = std::iter::once((&spec.partition_key, KeyType::Hash))
+        .chain(spec.sort_key.iter().map(|key| (key, KeyType::Range)));
+
+    let mut key_schema = Vec::new();
+    let mut attribute_definitions = Vec::new();
+    for (name, key_type) in keys {
+        key_schema.push(
+            KeySchemaElement::builder()
+                .attribute_name(name)
+                .key_type(key_type)
+                .build()
+                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
+        );
+        attribute_definitions.push(
+            AttributeDefinition::builder()
+                .attribute_name(name)
+                .attribute_type(ScalarAttributeType::S)
+                .build()
+                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
+        );
+    }
+
+    Ok((key_schema, attribute_definitions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(payload: usize) -> Item {
+        HashMap::from([
+            ("pk".to_string(), AttributeValue::S("T1".to_string())),
+            ("payload".to_string(), AttributeValue::S("x".repeat(payload))),
+        ])
+    }
+
+    #[test]
+    fn chunks_by_count() {
+        let chunks = chunk_items((0..60).map(|_| item(10)).collect(), BATCH_WRITE_LIMIT, BATCH_WRITE_BYTES);
+
+        assert_eq!(chunks.iter().map(Vec::len).collect::<Vec<_>>(), vec![25, 25, 10]);
+    }
+
+    #[test]
+    fn chunks_by_size() {
+        // 100 items of ~100 KB are ~10 MB, well over a transaction's 4 MB
+        let chunks = chunk_items((0..100).map(|_| item(100 * 1024)).collect(), TRANSACT_WRITE_LIMIT, TRANSACT_WRITE_BYTES);
+
+        assert!(chunks.len() >= 3);
+        assert_eq!(chunks.iter().map(Vec::len).sum::<usize>(), 100);
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.iter().map(item_size).sum::<usize>() <= TRANSACT_WRITE_BYTES));
+    }
+
+    #[test]
+    fn oversized_item_gets_its_own_chunk() {
+        let chunks = chunk_items(vec![item(10), item(5 * 1024 * 1024), item(10)], TRANSACT_WRITE_LIMIT, TRANSACT_WRITE_BYTES);
+
+        assert_eq!(chunks.iter().map(Vec::len).collect::<Vec<_>>(), vec![1, 1, 1]);
+    }
+}
@@ -4,6 +4,7 @@ pub type Result<T> = std::result::Result<T, SlackError>;
 
 #[derive(Error, Debug)]
 pub enum SlackError {
+    #[cfg(feature = "http-client")]
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
@@ -13,12 +14,28 @@ pub enum SlackError {
     #[error("AWS DynamoDB error: {0}")]
     DynamoDb(String),
 
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    #[cfg(feature = "lambda")]
     #[error("Lambda runtime error: {0}")]
     Lambda(#[from] lambda_runtime::Error),
 
     #[error("Invalid request signature")]
     InvalidSignature,
 
+    #[error(
+        "Requests from workspace {} are not accepted",
+        team_id.as_deref().or(enterprise_id.as_deref()).unwrap_or("unknown")
+    )]
+    WorkspaceNotAllowed {
+        team_id: Option<String>,
+        enterprise_id: Option<String>,
+    },
+
+    #[error("Source address {0:?} is outside the IP allowlist")]
+    IpNotAllowed(String),
+
     #[error("Missing environment variable: {0}")]
     MissingEnvVar(String),
 
@@ -34,6 +51,149 @@ pub enum SlackError {
     #[error("URL parsing error: {0}")]
     UrlParse(#[from] url::ParseError),
 
+    #[error("Timed out waiting for conversation lock: {0}")]
+    LockTimeout(String),
+
+    #[error("Request body of {size} bytes exceeds the {limit} byte limit")]
+    PayloadTooLarge { size: usize, limit: usize },
+
+    #[error("Request carries {count} blocks, more than the {limit} allowed")]
+    TooManyBlocks { count: usize, limit: usize },
+
+    #[error("Malformed form body: {0}")]
+    InvalidForm(String),
+
+    #[error("Expected {expected}, got {found}")]
+    UnexpectedPayload { expected: String, found: String },
+
+    #[error("Invalid view: {0}")]
+    InvalidView(String),
+
+    #[error("Invalid option: {0}")]
+    InvalidOption(String),
+
+    #[error("Handler timed out after {0:?}")]
+    HandlerTimeout(std::time::Duration),
+
+    #[error("Listener panicked: {0}")]
+    HandlerPanic(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
-}
\ No newline at end of file
+
+    #[error("User error: {}", .0.message)]
+    User(UserError),
+
+    // Anything a listener or middleware returned once routing started. The kind is the inner
+    // error's, so deferred (SQS) processing still retries it, but by default Slack is answered
+    // with a 200: a redelivery would run the listener's side effects again.
+    #[error(transparent)]
+    Listener(Box<SlackError>),
+}
+
+// A failure meant for the person who triggered the request, worded for them rather than for
+// the logs. Unless `surface_user_errors` is turned off, the message is shown to them as an
+// ephemeral reply, or next to a field when a modal submission fails, and the cause is logged.
+//
+//     let ticket = find_ticket(id).await.map_err(|e| {
+//         UserError::new(format!("Ticket {} doesn't exist", id)).caused_by(e)
+//     })?;
+#[derive(Debug)]
+pub struct UserError {
+    pub message: String,
+    // For view_submission: the input block the message is shown under. Without one the first
+    // input block of the modal is used.
+    pub block_id: Option<String>,
+    pub cause: Option<Box<SlackError>>,
+}
+
+impl UserError {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+            block_id: None,
+            cause: None,
+        }
+    }
+
+    pub fn for_block<S: Into<String>>(mut self, block_id: S) -> Self {
+        self.block_id = Some(block_id.into());
+        self
+    }
+
+    pub fn caused_by<E: Into<SlackError>>(mut self, cause: E) -> Self {
+        self.cause = Some(Box::new(cause.into()));
+        self
+    }
+}
+
+impl From<UserError> for SlackError {
+    fn from(error: UserError) -> Self {
+        SlackError::User(error)
+    }
+}
+
+// Broad classes of failure, for deciding whether to retry, redeliver or give up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    // Likely to succeed if tried again: network errors, throttling, storage hiccups
+    Transient,
+    // The incoming request itself is bad; retrying the same bytes fails the same way
+    InvalidRequest,
+    // The deployment is broken (missing configuration, a panicking listener)
+    Fatal,
+    // Anything else that would fail identically on every attempt
+    Permanent,
+}
+
+// Slack API error codes worth another attempt
+const TRANSIENT_API_ERRORS: &[&str] =
+    &["ratelimited", "internal_error", "fatal_error", "service_unavailable", "request_timeout"];
+
+impl SlackError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            SlackError::Listener(inner) => inner.kind(),
+            #[cfg(feature = "http-client")]
+            SlackError::Http(e) if e.is_status() => match e.status() {
+                Some(status) if status.as_u16() == 429 || status.is_server_error() => ErrorKind::Transient,
+                _ => ErrorKind::Permanent,
+            },
+            #[cfg(feature = "http-client")]
+            SlackError::Http(_) => ErrorKind::Transient,
+            SlackError::DynamoDb(_)
+            | SlackError::Storage(_)
+            | SlackError::LockTimeout(_)
+            | SlackError::HandlerTimeout(_) => ErrorKind::Transient,
+            SlackError::SlackApi { code, .. } if TRANSIENT_API_ERRORS.contains(&code.as_str()) => ErrorKind::Transient,
+            SlackError::InvalidSignature
+            | SlackError::WorkspaceNotAllowed { .. }
+            | SlackError::IpNotAllowed(_)
+            | SlackError::Json(_)
+            | SlackError::UrlParse(_)
+            | SlackError::InvalidForm(_)
+            | SlackError::PayloadTooLarge { .. }
+            | SlackError::TooManyBlocks { .. } => ErrorKind::InvalidRequest,
+            SlackError::Config(_) | SlackError::MissingEnvVar(_) | SlackError::HandlerPanic(_) => ErrorKind::Fatal,
+            #[cfg(feature = "lambda")]
+            SlackError::Lambda(_) => ErrorKind::Fatal,
+            _ => ErrorKind::Permanent,
+        }
+    }
+
+    pub fn user<S: Into<String>>(message: S) -> Self {
+        SlackError::User(UserError::new(message))
+    }
+
+    // The error a listener returned, for errors wrapped in `Listener`
+    pub fn root(&self) -> &SlackError {
+        match self {
+            SlackError::Listener(inner) => inner.root(),
+            error => error,
+        }
+    }
+
+    pub fn is_retriable(&self) -> bool {
+        self.kind() == ErrorKind::Transient
+    }
+}
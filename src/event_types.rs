@@ -0,0 +1,146 @@
+// Events API `event.type` values, usable anywhere an event type string is expected:
+// `app.event(event_types::APP_MENTION, ...)` or `app.event(EventType::AppMention, ...)`
+use std::fmt;
+
+pub const APP_HOME_OPENED: &str = "app_home_opened";
+pub const APP_MENTION: &str = "app_mention";
+pub const APP_RATE_LIMITED: &str = "app_rate_limited";
+pub const APP_UNINSTALLED: &str = "app_uninstalled";
+pub const CHANNEL_ARCHIVE: &str = "channel_archive";
+pub const CHANNEL_CREATED: &str = "channel_created";
+pub const CHANNEL_DELETED: &str = "channel_deleted";
+pub const CHANNEL_RENAME: &str = "channel_rename";
+pub const CHANNEL_UNARCHIVE: &str = "channel_unarchive";
+pub const EMOJI_CHANGED: &str = "emoji_changed";
+pub const FILE_CREATED: &str = "file_created";
+pub const FILE_DELETED: &str = "file_deleted";
+pub const FILE_SHARED: &str = "file_shared";
+pub const FUNCTION_EXECUTED: &str = "function_executed";
+pub const LINK_SHARED: &str = "link_shared";
+pub const MEMBER_JOINED_CHANNEL: &str = "member_joined_channel";
+pub const MEMBER_LEFT_CHANNEL: &str = "member_left_channel";
+pub const MESSAGE: &str = "message";
+pub const PIN_ADDED: &str = "pin_added";
+pub const PIN_REMOVED: &str = "pin_removed";
+pub const REACTION_ADDED: &str = "reaction_added";
+pub const REACTION_REMOVED: &str = "reaction_removed";
+pub const TEAM_JOIN: &str = "team_join";
+pub const TOKENS_REVOKED: &str = "tokens_revoked";
+pub const USER_CHANGE: &str = "user_change";
+pub const WORKFLOW_STEP_EXECUTE: &str = "workflow_step_execute";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EventType {
+    AppHomeOpened,
+    AppMention,
+    AppRateLimited,
+    AppUninstalled,
+    ChannelArchive,
+    ChannelCreated,
+    ChannelDeleted,
+    ChannelRename,
+    ChannelUnarchive,
+    EmojiChanged,
+    FileCreated,
+    FileDeleted,
+    FileShared,
+    FunctionExecuted,
+    LinkShared,
+    MemberJoinedChannel,
+    MemberLeftChannel,
+    Message,
+    PinAdded,
+    PinRemoved,
+    ReactionAdded,
+    ReactionRemoved,
+    TeamJoin,
+    TokensRevoked,
+    UserChange,
+    WorkflowStepExecute,
+    // Anything this crate doesn't have a name for yet
+    Other(String),
+}
+
+impl EventType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EventType::AppHomeOpened => APP_HOME_OPENED,
+            EventType::AppMention => APP_MENTION,
+            EventType::AppRateLimited => APP_RATE_LIMITED,
+            EventType::AppUninstalled => APP_UNINSTALLED,
+            EventType::ChannelArchive => CHANNEL_ARCHIVE,
+            EventType::ChannelCreated => CHANNEL_CREATED,
+            EventType::ChannelDeleted => CHANNEL_DELETED,
+            EventType::ChannelRename => CHANNEL_RENAME,
+            EventType::ChannelUnarchive => CHANNEL_UNARCHIVE,
+            EventType::EmojiChanged => EMOJI_CHANGED,
+            EventType::FileCreated => FILE_CREATED,
+            EventType::FileDeleted => FILE_DELETED,
+            EventType::FileShared => FILE_SHARED,
+            EventType::FunctionExecuted => FUNCTION_EXECUTED,
+            EventType::LinkShared => LINK_SHARED,
+            EventType::MemberJoinedChannel => MEMBER_JOINED_CHANNEL,
+            EventType::MemberLeftChannel => MEMBER_LEFT_CHANNEL,
+            EventType::Message => MESSAGE,
+            EventType::PinAdded => PIN_ADDED,
+            EventType::PinRemoved => PIN_REMOVED,
+            EventType::ReactionAdded => REACTION_ADDED,
+            EventType::ReactionRemoved => REACTION_REMOVED,
+            EventType::TeamJoin => TEAM_JOIN,
+            EventType::TokensRevoked => TOKENS_REVOKED,
+            EventType::UserChange => USER_CHANGE,
+            EventType::WorkflowStepExecute => WORKFLOW_STEP_EXECUTE,
+            EventType::Other(event_type) => event_type,
+        }
+    }
+}
+
+impl From<&str> for EventType {
+    fn from(event_type: &str) -> Self {
+        match event_type {
+            APP_HOME_OPENED => EventType::AppHomeOpened,
+            APP_MENTION => EventType::AppMention,
+            APP_RATE_LIMITED => EventType::AppRateLimited,
+            APP_UNINSTALLED => EventType::AppUninstalled,
+            CHANNEL_ARCHIVE => EventType::ChannelArchive,
+            CHANNEL_CREATED => EventType::ChannelCreated,
+            CHANNEL_DELETED => EventType::ChannelDeleted,
+            CHANNEL_RENAME => EventType::ChannelRename,
+            CHANNEL_UNARCHIVE => EventType::ChannelUnarchive,
+            EMOJI_CHANGED => EventType::EmojiChanged,
+            FILE_CREATED => EventType::FileCreated,
+            FILE_DELETED => EventType::FileDeleted,
+            FILE_SHARED => EventType::FileShared,
+            FUNCTION_EXECUTED => EventType::FunctionExecuted,
+            LINK_SHARED => EventType::LinkShared,
+            MEMBER_JOINED_CHANNEL => EventType::MemberJoinedChannel,
+            MEMBER_LEFT_CHANNEL => EventType::MemberLeftChannel,
+            MESSAGE => EventType::Message,
+            PIN_ADDED => EventType::PinAdded,
+            PIN_REMOVED => EventType::PinRemoved,
+            REACTION_ADDED => EventType::ReactionAdded,
+            REACTION_REMOVED => EventType::ReactionRemoved,
+            TEAM_JOIN => EventType::TeamJoin,
+            TOKENS_REVOKED => EventType::TokensRevoked,
+            USER_CHANGE => EventType::UserChange,
+            WORKFLOW_STEP_EXECUTE => EventType::WorkflowStepExecute,
+            other => EventType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<EventType> for String {
+    fn from(event_type: EventType) -> Self {
+        match event_type {
+            EventType::Other(event_type) => event_type,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
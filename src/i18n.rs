@@ -0,0 +1,118 @@
+// Translation hook for handler replies and the built-in pages (OAuth success/failure).
+// Locales are BCP 47-ish tags as Slack reports them ("en-US", "ja-JP"); lookups fall back
+// from the full tag to its language ("en") and then to the localizer's default locale.
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+pub const OAUTH_SUCCESS: &str = "oauth.success";
+pub const OAUTH_ERROR: &str = "oauth.error";
+
+pub const DEFAULT_LOCALE: &str = "en-US";
+
+pub trait Localizer: Send + Sync + Debug {
+    // None when there is no translation for the exact locale given
+    fn translate(&self, locale: &str, key: &str) -> Option<String>;
+
+    fn default_locale(&self) -> &str {
+        DEFAULT_LOCALE
+    }
+
+    fn localize(&self, locale: Option<&str>, key: &str) -> Option<String> {
+        let mut candidates = Vec::new();
+        if let Some(locale) = locale {
+            candidates.push(locale);
+            if let Some((language, _)) = locale.split_once(['-', '_']) {
+                candidates.push(language);
+            }
+        }
+        candidates.push(self.default_locale());
+
+        candidates.into_iter().find_map(|locale| self.translate(locale, key))
+    }
+}
+
+// Translations held in memory, e.g. loaded from bundled JSON at init
+#[derive(Debug, Clone)]
+pub struct StaticLocalizer {
+    default_locale: String,
+    translations: HashMap<String, HashMap<String, String>>,
+}
+
+impl Default for StaticLocalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StaticLocalizer {
+    pub fn new() -> Self {
+        Self {
+            default_locale: DEFAULT_LOCALE.to_string(),
+            translations: HashMap::new(),
+        }
+        .with_translation(DEFAULT_LOCALE, OAUTH_SUCCESS, "Installation successful!")
+        .with_translation(DEFAULT_LOCALE, OAUTH_ERROR, "OAuth error")
+    }
+
+    pub fn with_default_locale<S: Into<String>>(mut self, locale: S) -> Self {
+        self.default_locale = locale.into();
+        self
+    }
+
+    pub fn with_translation<L, K, V>(mut self, locale: L, key: K, value: V) -> Self
+    where
+        L: Into<String>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.translations
+            .entry(locale.into())
+            .or_default()
+            .insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_translations<L, I, K, V>(mut self, locale: L, translations: I) -> Self
+    where
+        L: Into<String>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.translations
+            .entry(locale.into())
+            .or_default()
+            .extend(translations.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+}
+
+impl Localizer for StaticLocalizer {
+    fn translate(&self, locale: &str, key: &str) -> Option<String> {
+        self.translations.get(locale).and_then(|keys| keys.get(key)).cloned()
+    }
+
+    fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+}
+
+// Highest-weighted tag of an Accept-Language header, for browser-facing pages
+pub fn preferred_locale(accept_language: &str) -> Option<&str> {
+    accept_language
+        .split(',')
+        .filter_map(|part| {
+            let mut fields = part.split(';');
+            let tag = fields.next()?.trim();
+            let weight = fields
+                .find_map(|field| field.trim().strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (!tag.is_empty() && tag != "*").then_some((tag, weight))
+        })
+        .fold(None, |best: Option<(&str, f32)>, (tag, weight)| match best {
+            Some((_, best_weight)) if best_weight >= weight => best,
+            _ => Some((tag, weight)),
+        })
+        .map(|(tag, _)| tag)
+}
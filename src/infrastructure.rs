@@ -0,0 +1,279 @@
+// What an app needs from its environment, for keeping IaC in sync with the code.
+// Stores and sinks report their own resources; `App::describe_infrastructure` collects them.
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Resource {
+    Table(TableSpec),
+    Queue(QueueSpec),
+    Bucket { bucket_name: String },
+}
+
+// Pay-per-request table with string keys, optionally expiring items through a numeric TTL attribute.
+// Replica regions turn it into a global table (version 2019.11.21) replicated from the client's region.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TableSpec {
+    pub table_name: String,
+    pub partition_key: String,
+    pub sort_key: Option<String>,
+    pub ttl_attribute: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub replica_regions: Vec<String>,
+}
+
+impl TableSpec {
+    pub fn new<T: Into<String>, K: Into<String>>(table_name: T, partition_key: K) -> Self {
+        Self {
+            table_name: table_name.into(),
+            partition_key: partition_key.into(),
+            sort_key: None,
+            ttl_attribute: None,
+            replica_regions: Vec::new(),
+        }
+    }
+
+    pub fn with_sort_key<S: Into<String>>(mut self, sort_key: S) -> Self {
+        self.sort_key = Some(sort_key.into());
+        self
+    }
+
+    pub fn with_ttl<S: Into<String>>(mut self, attribute: S) -> Self {
+        self.ttl_attribute = Some(attribute.into());
+        self
+    }
+
+    pub fn with_replicas<I, S>(mut self, regions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.replica_regions = regions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn is_global(&self) -> bool {
+        !self.replica_regions.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueueSpec {
+    pub queue_name: String,
+    pub dead_letter_queue: Option<String>,
+    pub max_receive_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EnvVar {
+    pub name: String,
+    pub required: bool,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Route {
+    pub method: String,
+    pub path: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InfrastructureDescriptor {
+    pub resources: Vec<Resource>,
+    pub env_vars: Vec<EnvVar>,
+    pub routes: Vec<Route>,
+}
+
+impl InfrastructureDescriptor {
+    pub fn add_resource(&mut self, resource: Resource) {
+        // Several stores can share a table
+        if !self.resources.contains(&resource) {
+            self.resources.push(resource);
+        }
+    }
+
+    pub fn add_env_var<N: Into<String>, D: Into<String>>(&mut self, name: N, required: bool, description: D) {
+        self.env_vars.push(EnvVar {
+            name: name.into(),
+            required,
+            description: description.into(),
+        });
+    }
+
+    pub fn add_route<M: Into<String>, P: Into<String>, D: Into<String>>(&mut self, method: M, path: P, description: D) {
+        self.routes.push(Route {
+            method: method.into(),
+            path: path.into(),
+            description: description.into(),
+        });
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    // A CloudFormation `Resources` section for the tables, queues and buckets
+    pub fn to_cloudformation(&self) -> Value {
+        let mut resources = Map::new();
+
+        for resource in &self.resources {
+            match resource {
+                Resource::Table(table) => {
+                    let keys: Vec<(&String, &str)> = std::iter::once((&table.partition_key, "HASH"))
+                        .chain(table.sort_key.iter().map(|key| (key, "RANGE")))
+                        .collect();
+                    let mut properties = json!({
+                        "TableName": table.table_name,
+                        "BillingMode": "PAY_PER_REQUEST",
+                        "AttributeDefinitions": keys.iter()
+                            .map(|(name, _)| json!({ "AttributeName": name, "AttributeType": "S" }))
+                            .collect::<Vec<_>>(),
+                        "KeySchema": keys.iter()
+                            .map(|(name, key_type)| json!({ "AttributeName": name, "KeyType": key_type }))
+                            .collect::<Vec<_>>(),
+                    });
+                    if let Some(ttl) = &table.ttl_attribute {
+                        properties["TimeToLiveSpecification"] = json!({ "AttributeName": ttl, "Enabled": true });
+                    }
+                    let resource_type = if table.is_global() {
+                        // Global tables list every region, including the one the stack deploys to
+                        let replicas: Vec<Value> = std::iter::once(json!({ "Region": { "Ref": "AWS::Region" } }))
+                            .chain(table.replica_regions.iter().map(|region| json!({ "Region": region })))
+                            .collect();
+                        properties["StreamSpecification"] = json!({ "StreamViewType": "NEW_AND_OLD_IMAGES" });
+                        properties["Replicas"] = json!(replicas);
+                        "AWS::DynamoDB::GlobalTable"
+                    } else {
+                        "AWS::DynamoDB::Table"
+                    };
+                    resources.insert(
+                        logical_id(&table.table_name),
+                        json!({ "Type": resource_type, "Properties": properties }),
+                    );
+                }
+                Resource::Queue(queue) => {
+                    let mut properties = json!({ "QueueName": queue.queue_name });
+                    if let Some(dead_letter_queue) = &queue.dead_letter_queue {
+                        properties["RedrivePolicy"] = json!({
+                            "deadLetterTargetArn": { "Fn::GetAtt": [logical_id(dead_letter_queue), "Arn"] },
+                            "maxReceiveCount": queue.max_receive_count,
+                        });
+                        resources.insert(
+                            logical_id(dead_letter_queue),
+                            json!({ "Type": "AWS::SQS::Queue", "Properties": { "QueueName": dead_letter_queue } }),
+                        );
+                    }
+                    resources.insert(
+                        logical_id(&queue.queue_name),
+                        json!({ "Type": "AWS::SQS::Queue", "Properties": properties }),
+                    );
+                }
+                Resource::Bucket { bucket_name } => {
+                    resources.insert(
+                        logical_id(bucket_name),
+                        json!({ "Type": "AWS::S3::Bucket", "Properties": { "BucketName": bucket_name } }),
+                    );
+                }
+            }
+        }
+
+        json!({ "Resources": resources })
+    }
+
+    pub fn to_terraform(&self) -> String {
+        let mut blocks = Vec::new();
+
+        for resource in &self.resources {
+            match resource {
+                Resource::Table(table) => {
+                    let name = terraform_name(&table.table_name);
+                    let mut block = format!(
+                        "resource \"aws_dynamodb_table\" \"{}\" {{\n  name         = \"{}\"\n  billing_mode = \"PAY_PER_REQUEST\"\n  hash_key     = \"{}\"\n",
+                        name, table.table_name, table.partition_key
+                    );
+                    if let Some(sort_key) = &table.sort_key {
+                        block.push_str(&format!("  range_key    = \"{}\"\n", sort_key));
+                    }
+                    for key in std::iter::once(&table.partition_key).chain(table.sort_key.iter()) {
+                        block.push_str(&format!(
+                            "\n  attribute {{\n    name = \"{}\"\n    type = \"S\"\n  }}\n",
+                            key
+                        ));
+                    }
+                    if let Some(ttl) = &table.ttl_attribute {
+                        block.push_str(&format!(
+                            "\n  ttl {{\n    attribute_name = \"{}\"\n    enabled        = true\n  }}\n",
+                            ttl
+                        ));
+                    }
+                    if table.is_global() {
+                        block.push_str("\n  stream_enabled   = true\n  stream_view_type = \"NEW_AND_OLD_IMAGES\"\n");
+                        for region in &table.replica_regions {
+                            block.push_str(&format!("\n  replica {{\n    region_name = \"{}\"\n  }}\n", region));
+                        }
+                    }
+                    block.push('}');
+                    blocks.push(block);
+                }
+                Resource::Queue(queue) => {
+                    let mut block = format!(
+                        "resource \"aws_sqs_queue\" \"{}\" {{\n  name = \"{}\"\n",
+                        terraform_name(&queue.queue_name),
+                        queue.queue_name
+                    );
+                    if let Some(dead_letter_queue) = &queue.dead_letter_queue {
+                        blocks.push(format!(
+                            "resource \"aws_sqs_queue\" \"{}\" {{\n  name = \"{}\"\n}}",
+                            terraform_name(dead_letter_queue),
+                            dead_letter_queue
+                        ));
+                        block.push_str(&format!(
+                            "  redrive_policy = jsonencode({{\n    deadLetterTargetArn = aws_sqs_queue.{}.arn\n    maxReceiveCount     = {}\n  }})\n",
+                            terraform_name(dead_letter_queue),
+                            queue.max_receive_count
+                        ));
+                    }
+                    block.push('}');
+                    blocks.push(block);
+                }
+                Resource::Bucket { bucket_name } => {
+                    blocks.push(format!(
+                        "resource \"aws_s3_bucket\" \"{}\" {{\n  bucket = \"{}\"\n}}",
+                        terraform_name(bucket_name),
+                        bucket_name
+                    ));
+                }
+            }
+        }
+
+        blocks.join("\n\n") + "\n"
+    }
+}
+
+// CloudFormation logical IDs are alphanumeric: slack_oauth_states -> SlackOauthStates
+fn logical_id(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn terraform_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -1,18 +1,41 @@
+#[cfg(feature = "admin")]
+pub mod admin;
 pub mod app;
 pub mod adapter;
+pub mod audit;
+pub mod blocks;
+#[cfg(feature = "bolt_compat")]
+pub mod bolt_compat;
 pub mod client;
 pub mod context;
+pub mod conversation;
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb;
 pub mod error;
+pub mod event_types;
+pub mod i18n;
+pub mod infrastructure;
 pub mod listener;
+pub mod metrics;
 pub mod middleware;
 pub mod oauth;
 pub mod request;
 pub mod response;
+pub mod secret;
+pub mod settings;
+pub mod shared;
+pub mod templates;
+pub mod testing;
 
-pub use app::{App, AppBuilder};
+pub use app::{App, AppBuilder, AppState};
+pub use client::SlackApi;
+#[cfg(feature = "http-client")]
 pub use client::SlackClient;
 pub use context::{Context, Say, Ack};
-pub use error::{SlackError, Result};
+pub use error::{SlackError, Result, UserError};
+pub use secret::SecretString;
+pub use event_types::EventType;
+pub use i18n::{Localizer, StaticLocalizer};
 
 #[cfg(feature = "lambda")]
 pub use adapter::aws_lambda::LambdaHandler;
\ No newline at end of file
@@ -1 +1,61 @@
-// Event listener implementations
\ No newline at end of file
+// Event listener implementations
+use crate::context::Context;
+use crate::error::Result;
+use crate::listener::{handler, ListenerHandler};
+use crate::request::payloads::{AppMentionEvent, Mention, TypedEvent};
+use crate::request::SlackRequestBody;
+use crate::response::SlackResponse;
+use serde_json::Value;
+use std::future::Future;
+use std::sync::Arc;
+
+// Decides from the inner `event` object whether a filtered listener matches
+pub type EventFilter = Arc<dyn Fn(&Value) -> bool + Send + Sync>;
+
+// Hands the listener its event already deserialized; a payload that doesn't fit `T` fails
+// the listener with `UnexpectedPayload`
+pub fn typed_handler<T, F, Fut>(f: F) -> ListenerHandler
+where
+    T: TypedEvent + Send + 'static,
+    F: Fn(Context, T) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+{
+    let f = Arc::new(f);
+    handler(move |context: Context| {
+        let f = f.clone();
+        async move {
+            let event = context.event::<T>()?;
+            f(context, event).await
+        }
+    })
+}
+
+// Matches a reaction by emoji name, with or without colons. Skin tone variants
+// (`thumbsup::skin-tone-2`) match their base name, and "*" matches every reaction.
+pub fn reaction_filter<S: Into<String>>(name: S) -> EventFilter {
+    let name = name.into().trim_matches(':').to_string();
+    Arc::new(move |event| {
+        let reaction = event.get("reaction").and_then(|v| v.as_str()).unwrap_or_default();
+        name == "*" || reaction == name || reaction.split("::").next() == Some(name.as_str())
+    })
+}
+
+// Like `typed_handler`, with the bot's own mention stripped from the text. The bot user comes
+// from the installation when there is one, otherwise from the envelope's authorizations.
+pub fn mention_handler<F, Fut>(f: F) -> ListenerHandler
+where
+    F: Fn(Context, Mention) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+{
+    typed_handler(move |context: Context, event: AppMentionEvent| {
+        let bot_user_id = context
+            .installation()
+            .and_then(|installation| installation.bot_user_id.clone())
+            .or_else(|| match &context.request.body {
+                SlackRequestBody::Event(event_req) => event_req.bot_user_id().map(str::to_string),
+                _ => None,
+            });
+        let text = event.text_without_mention(bot_user_id.as_deref());
+        f(context, Mention { event, text })
+    })
+}
@@ -0,0 +1,233 @@
+use crate::context::Context;
+use crate::error::{Result, SlackError};
+use crate::response::{SlackResponse, SlackResponseBody};
+use futures::FutureExt;
+use std::any::Any;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+use tracing::error;
+
+// How handlers registered for the same request are run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExecutionMode {
+    // One after another in registration order, stopping at the first error
+    #[default]
+    Sequential,
+    // All at once; every handler runs to completion even if another one fails
+    Parallel,
+}
+
+// Which handler response is sent back to Slack when several handlers ran
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponsePolicy {
+    #[default]
+    First,
+    FirstNonEmpty,
+    Last,
+}
+
+impl ResponsePolicy {
+    // `responses` must be in registration order, regardless of completion order
+    pub fn select(&self, responses: Vec<SlackResponse>) -> Option<SlackResponse> {
+        match self {
+            ResponsePolicy::First => responses.into_iter().next(),
+            ResponsePolicy::FirstNonEmpty => {
+                let index = responses
+                    .iter()
+                    .position(|r| !matches!(r.body, SlackResponseBody::Empty))
+                    .unwrap_or(0);
+                responses.into_iter().nth(index)
+            }
+            ResponsePolicy::Last => responses.into_iter().last(),
+        }
+    }
+}
+
+// Upper bound on how long a single handler may run: the fixed limit if any, capped by
+// whatever is left of the Lambda deadline minus the margin needed to still answer Slack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutBudget {
+    pub limit: Option<Duration>,
+    pub safety_margin: Duration,
+}
+
+impl Default for TimeoutBudget {
+    fn default() -> Self {
+        Self {
+            limit: None,
+            safety_margin: Duration::from_millis(500),
+        }
+    }
+}
+
+impl TimeoutBudget {
+    pub fn for_context(&self, context: &Context) -> Option<Duration> {
+        let remaining = context
+            .invocation()
+            .remaining_time()
+            .map(|remaining| remaining.saturating_sub(self.safety_margin));
+
+        match (self.limit, remaining) {
+            (Some(limit), Some(remaining)) => Some(limit.min(remaining)),
+            (limit, remaining) => limit.or(remaining),
+        }
+    }
+
+    pub async fn run<F>(&self, context: &Context, future: F) -> Result<SlackResponse>
+    where
+        F: Future<Output = Result<SlackResponse>>,
+    {
+        match self.for_context(context) {
+            Some(budget) => tokio::time::timeout(budget, future)
+                .await
+                .unwrap_or(Err(SlackError::HandlerTimeout(budget))),
+            None => future.await,
+        }
+    }
+}
+
+// A panicking listener becomes a `HandlerPanic` error for the error policy to answer, instead of
+// unwinding through the runtime and taking the invocation (or a whole SQS batch) down with it
+pub(crate) async fn isolate<F>(future: F) -> Result<SlackResponse>
+where
+    F: Future<Output = Result<SlackResponse>>,
+{
+    match AssertUnwindSafe(future).catch_unwind().await {
+        Ok(result) => result,
+        Err(panic) => {
+            let message = panic_message(panic.as_ref());
+            error!("Listener panicked: {}", message);
+            Err(SlackError::HandlerPanic(message))
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Invocation;
+    use crate::testing::{CommandRequestBuilder, MockSlackClient};
+    use std::sync::Arc;
+    use std::time::SystemTime;
+
+    // A context whose invocation ends `remaining` from now, or never
+    fn context(remaining: Option<Duration>) -> Context {
+        let mut context = Context::new(CommandRequestBuilder::new("/deploy").build(), Arc::new(MockSlackClient::new()));
+        if let Some(remaining) = remaining {
+            let mut invocation = Invocation::new("request-1");
+            invocation.deadline = Some(SystemTime::now() + remaining);
+            context.set_invocation(invocation);
+        }
+        context
+    }
+
+    fn budget(limit: Option<Duration>) -> TimeoutBudget {
+        TimeoutBudget {
+            limit,
+            safety_margin: Duration::from_millis(500),
+        }
+    }
+
+    fn text(response: &Option<SlackResponse>) -> Option<&str> {
+        match response.as_ref().map(|response| &response.body) {
+            Some(SlackResponseBody::Text(text)) => Some(text.text.as_str()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn first_non_empty_skips_empty_responses() {
+        let responses = vec![SlackResponse::empty(), SlackResponse::text("second"), SlackResponse::text("third")];
+
+        assert_eq!(text(&ResponsePolicy::FirstNonEmpty.select(responses)), Some("second"));
+    }
+
+    #[test]
+    fn first_non_empty_falls_back_to_the_first_response() {
+        let responses = vec![SlackResponse::empty(), SlackResponse::empty()];
+
+        let selected = ResponsePolicy::FirstNonEmpty.select(responses).unwrap();
+
+        assert!(matches!(selected.body, SlackResponseBody::Empty));
+    }
+
+    #[test]
+    fn first_and_last_follow_registration_order() {
+        let responses = || vec![SlackResponse::text("first"), SlackResponse::empty(), SlackResponse::text("last")];
+
+        assert_eq!(text(&ResponsePolicy::First.select(responses())), Some("first"));
+        assert_eq!(text(&ResponsePolicy::Last.select(responses())), Some("last"));
+        assert!(ResponsePolicy::First.select(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn budget_is_the_limit_without_a_deadline() {
+        assert_eq!(budget(Some(Duration::from_secs(2))).for_context(&context(None)), Some(Duration::from_secs(2)));
+        assert_eq!(budget(None).for_context(&context(None)), None);
+    }
+
+    #[test]
+    fn budget_is_capped_by_the_remaining_deadline() {
+        let context = context(Some(Duration::from_secs(3)));
+
+        // ~2.5s left after the margin, so the 10s limit doesn't apply but the 1s one does
+        let capped = budget(Some(Duration::from_secs(10))).for_context(&context).unwrap();
+        assert!(capped <= Duration::from_millis(2500) && capped > Duration::from_secs(2));
+        assert_eq!(budget(Some(Duration::from_secs(1))).for_context(&context), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn budget_is_zero_once_the_margin_is_used_up() {
+        let context = context(Some(Duration::from_millis(100)));
+
+        assert_eq!(budget(None).for_context(&context), Some(Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn handler_past_its_budget_times_out() {
+        let budget = budget(Some(Duration::from_millis(20)));
+
+        let result = budget
+            .run(&context(None), async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(SlackResponse::empty())
+            })
+            .await;
+
+        assert!(matches!(result, Err(SlackError::HandlerTimeout(budget)) if budget == Duration::from_millis(20)));
+    }
+
+    async fn panics() -> Result<SlackResponse> {
+        panic!("lookup table missing")
+    }
+
+    #[tokio::test]
+    async fn panicking_handler_becomes_handler_panic() {
+        let result = isolate(panics()).await;
+
+        assert!(matches!(result, Err(SlackError::HandlerPanic(ref message)) if message == "lookup table missing"));
+    }
+
+    #[tokio::test]
+    async fn formatted_panic_message_is_kept() {
+        let result = isolate(async { panic!("no settings for {}", "T1") }).await;
+
+        assert!(matches!(result, Err(SlackError::HandlerPanic(ref message)) if message == "no settings for T1"));
+    }
+
+    #[tokio::test]
+    async fn handler_that_does_not_panic_is_untouched() {
+        let result = isolate(async { Ok(SlackResponse::text("done")) }).await;
+
+        assert_eq!(text(&result.ok()), Some("done"));
+    }
+}
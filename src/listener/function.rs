@@ -0,0 +1,141 @@
+use crate::secret::SecretString;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FunctionExecutedEvent {
+    pub function: FunctionInfo,
+    #[serde(default)]
+    pub inputs: HashMap<String, Value>,
+    pub function_execution_id: String,
+    pub workflow_execution_id: Option<String>,
+    pub event_ts: Option<String>,
+    // Scoped to this execution; redacted in Debug like the other tokens
+    pub bot_access_token: Option<SecretString>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FunctionInfo {
+    pub id: String,
+    pub callback_id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub function_type: Option<String>,
+    pub app_id: Option<String>,
+    #[serde(default)]
+    pub input_parameters: Vec<Value>,
+    #[serde(default)]
+    pub output_parameters: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionParameter {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub parameter_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip)]
+    pub required: bool,
+}
+
+impl FunctionParameter {
+    pub fn new<S: Into<String>>(name: S, parameter_type: S) -> Self {
+        Self {
+            name: name.into(),
+            parameter_type: parameter_type.into(),
+            title: None,
+            description: None,
+            required: false,
+        }
+    }
+
+    pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+}
+
+// Declaration of a custom function, used to generate the app manifest's `functions` section
+#[derive(Debug, Clone)]
+pub struct CustomFunction {
+    pub callback_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub input_parameters: Vec<FunctionParameter>,
+    pub output_parameters: Vec<FunctionParameter>,
+}
+
+impl CustomFunction {
+    pub fn new<S: Into<String>>(callback_id: S, title: S) -> Self {
+        Self {
+            callback_id: callback_id.into(),
+            title: title.into(),
+            description: None,
+            input_parameters: Vec::new(),
+            output_parameters: Vec::new(),
+        }
+    }
+
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn input(mut self, parameter: FunctionParameter) -> Self {
+        self.input_parameters.push(parameter);
+        self
+    }
+
+    pub fn output(mut self, parameter: FunctionParameter) -> Self {
+        self.output_parameters.push(parameter);
+        self
+    }
+
+    pub fn manifest_entry(&self) -> Value {
+        let mut entry = json!({
+            "title": self.title,
+            "input_parameters": Self::parameters_manifest(&self.input_parameters),
+            "output_parameters": Self::parameters_manifest(&self.output_parameters),
+        });
+
+        if let Some(description) = &self.description {
+            entry["description"] = json!(description);
+        }
+
+        entry
+    }
+
+    fn parameters_manifest(parameters: &[FunctionParameter]) -> Value {
+        let mut properties = Map::new();
+        for parameter in parameters {
+            properties.insert(parameter.name.clone(), json!(parameter));
+        }
+
+        let required: Vec<&str> = parameters
+            .iter()
+            .filter(|p| p.required)
+            .map(|p| p.name.as_str())
+            .collect();
+
+        json!({
+            "properties": properties,
+            "required": required,
+        })
+    }
+}
@@ -1 +1,88 @@
-// Message listener implementations
\ No newline at end of file
+// Message listener implementations
+use crate::listener::EventFilter;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Anywhere,
+    Start,
+}
+
+// Plain-text match on a message's `text`, for listeners that don't need a regex.
+// Case-insensitive unless `case_sensitive` is called.
+#[derive(Debug, Clone)]
+pub struct TextPattern {
+    text: String,
+    case_sensitive: bool,
+    whole_word: bool,
+}
+
+impl TextPattern {
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            case_sensitive: false,
+            whole_word: false,
+        }
+    }
+
+    pub fn case_sensitive(mut self) -> Self {
+        self.case_sensitive = true;
+        self
+    }
+
+    // "deploy" then matches "deploy now" but not "redeployed"
+    pub fn whole_word(mut self) -> Self {
+        self.whole_word = true;
+        self
+    }
+
+    fn find(&self, text: &str, position: Position) -> bool {
+        let (text, needle) = if self.case_sensitive {
+            (text.to_string(), self.text.clone())
+        } else {
+            (text.to_lowercase(), self.text.to_lowercase())
+        };
+        if needle.is_empty() {
+            return false;
+        }
+
+        let text = match position {
+            Position::Start => text.trim_start(),
+            Position::Anywhere => text.as_str(),
+        };
+        let is_word_char = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+
+        text.match_indices(needle.as_str())
+            .take_while(|(index, _)| position == Position::Anywhere || *index == 0)
+            .any(|(index, _)| {
+                !self.whole_word
+                    || (!is_word_char(text[..index].chars().next_back())
+                        && !is_word_char(text[index + needle.len()..].chars().next()))
+            })
+    }
+
+    pub(crate) fn contains_filter(self) -> EventFilter {
+        Arc::new(move |event| self.find(message_text(event), Position::Anywhere))
+    }
+
+    pub(crate) fn starts_with_filter(self) -> EventFilter {
+        Arc::new(move |event| self.find(message_text(event), Position::Start))
+    }
+}
+
+impl From<&str> for TextPattern {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for TextPattern {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+fn message_text(event: &serde_json::Value) -> &str {
+    event.get("text").and_then(|v| v.as_str()).unwrap_or_default()
+}
@@ -3,70 +3,561 @@ pub mod command;
 pub mod action;
 pub mod shortcut;
 pub mod message;
+pub mod execution;
+pub mod function;
+pub mod step;
+
+pub use event::{typed_handler, EventFilter};
+pub use execution::{ExecutionMode, ResponsePolicy, TimeoutBudget};
+pub use function::{CustomFunction, FunctionExecutedEvent, FunctionParameter};
+pub use message::TextPattern;
+pub use step::WorkflowStep;
 
 use crate::error::Result;
-use crate::request::SlackRequest;
+use crate::event_types;
+use crate::request::{SlackRequest, SlackRequestBody};
 use crate::response::SlackResponse;
 use crate::context::Context;
-use std::collections::HashMap;
+use futures::future::join_all;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use tracing::{debug, Instrument};
+
+pub type HandlerFuture = Pin<Box<dyn Future<Output = Result<SlackResponse>> + Send>>;
+pub type ListenerHandler = Arc<dyn Fn(Context) -> HandlerFuture + Send + Sync>;
 
-pub type ListenerHandler = Arc<dyn Fn(Context) -> Result<SlackResponse> + Send + Sync>;
+pub fn handler<F, Fut>(f: F) -> ListenerHandler
+where
+    F: Fn(Context) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+{
+    Arc::new(move |context| Box::pin(f(context)))
+}
 
+#[derive(Clone)]
 pub struct EventRouter {
     event_handlers: HashMap<String, Vec<ListenerHandler>>,
+    // "*", "message.*", "message.im": run after the handlers registered for the exact type
+    event_pattern_handlers: Vec<(String, ListenerHandler)>,
+    // Handlers for an exact type that only match when their filter accepts the inner event
+    filtered_event_handlers: HashMap<String, Vec<(EventFilter, ListenerHandler)>>,
     command_handlers: HashMap<String, Vec<ListenerHandler>>,
     action_handlers: HashMap<String, Vec<ListenerHandler>>,
     shortcut_handlers: HashMap<String, Vec<ListenerHandler>>,
+    global_shortcut_handlers: HashMap<String, Vec<ListenerHandler>>,
+    message_shortcut_handlers: HashMap<String, Vec<ListenerHandler>>,
+    // view_submission listeners, by the view's callback_id
+    view_handlers: HashMap<String, Vec<ListenerHandler>>,
     message_handlers: Vec<ListenerHandler>,
+    step_handlers: HashMap<String, WorkflowStep>,
+    function_handlers: HashMap<String, Vec<ListenerHandler>>,
+    functions: Vec<CustomFunction>,
+    any_handlers: Vec<ListenerHandler>,
+    // Listeners that opted into Slack's duplicate retries, and the one `receive_retries` applies to
+    retry_receivers: Vec<ListenerHandler>,
+    last_added: Option<ListenerHandler>,
+    execution_mode: ExecutionMode,
+    response_policy: ResponsePolicy,
+    timeout_budget: TimeoutBudget,
+}
+
+impl Default for EventRouter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventRouter {
     pub fn new() -> Self {
         Self {
             event_handlers: HashMap::new(),
+            event_pattern_handlers: Vec::new(),
+            filtered_event_handlers: HashMap::new(),
             command_handlers: HashMap::new(),
             action_handlers: HashMap::new(),
             shortcut_handlers: HashMap::new(),
+            global_shortcut_handlers: HashMap::new(),
+            message_shortcut_handlers: HashMap::new(),
+            view_handlers: HashMap::new(),
             message_handlers: Vec::new(),
+            step_handlers: HashMap::new(),
+            function_handlers: HashMap::new(),
+            functions: Vec::new(),
+            any_handlers: Vec::new(),
+            retry_receivers: Vec::new(),
+            last_added: None,
+            execution_mode: ExecutionMode::default(),
+            response_policy: ResponsePolicy::default(),
+            timeout_budget: TimeoutBudget::default(),
         }
     }
 
     pub fn add_event_handler<S: Into<String>>(&mut self, event_type: S, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
+        let event_type = event_type.into();
+        if event_type.contains('*') || event_type.contains('.') {
+            self.event_pattern_handlers.push((event_type, handler));
+            return;
+        }
+
         self.event_handlers
-            .entry(event_type.into())
-            .or_insert_with(Vec::new)
+            .entry(event_type)
+            .or_default()
             .push(handler);
     }
 
+    pub fn add_filtered_event_handler<S: Into<String>>(
+        &mut self,
+        event_type: S,
+        filter: EventFilter,
+        handler: ListenerHandler,
+    ) {
+        self.last_added = Some(handler.clone());
+        self.filtered_event_handlers
+            .entry(event_type.into())
+            .or_default()
+            .push((filter, handler));
+    }
+
     pub fn add_command_handler<S: Into<String>>(&mut self, command: S, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
         self.command_handlers
             .entry(command.into())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(handler);
     }
 
     pub fn add_action_handler<S: Into<String>>(&mut self, action_id: S, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
         self.action_handlers
             .entry(action_id.into())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(handler);
     }
 
     pub fn add_shortcut_handler<S: Into<String>>(&mut self, callback_id: S, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
         self.shortcut_handlers
             .entry(callback_id.into())
-            .or_insert_with(Vec::new)
+            .or_default()
+            .push(handler);
+    }
+
+    pub fn add_global_shortcut_handler<S: Into<String>>(&mut self, callback_id: S, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
+        self.global_shortcut_handlers
+            .entry(callback_id.into())
+            .or_default()
+            .push(handler);
+    }
+
+    pub fn add_message_shortcut_handler<S: Into<String>>(&mut self, callback_id: S, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
+        self.message_shortcut_handlers
+            .entry(callback_id.into())
+            .or_default()
+            .push(handler);
+    }
+
+    pub fn add_view_handler<S: Into<String>>(&mut self, callback_id: S, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
+        self.view_handlers
+            .entry(callback_id.into())
+            .or_default()
             .push(handler);
     }
 
     pub fn add_message_handler(&mut self, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
         self.message_handlers.push(handler);
     }
 
-    pub async fn route_request(&self, request: &SlackRequest) -> Result<Option<SlackResponse>> {
-        // Route based on request type
-        // This is a placeholder implementation
-        Ok(None)
+    pub fn add_step_handler<S: Into<String>>(&mut self, callback_id: S, step: WorkflowStep) {
+        self.step_handlers.insert(callback_id.into(), step);
+    }
+
+    pub fn add_function_handler(&mut self, function: CustomFunction, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
+        self.function_handlers
+            .entry(function.callback_id.clone())
+            .or_default()
+            .push(handler);
+
+        if !self.functions.iter().any(|f| f.callback_id == function.callback_id) {
+            self.functions.push(function);
+        }
+    }
+
+    // Catch-all listeners run for any request no other listener matched
+    pub fn add_any_handler(&mut self, handler: ListenerHandler) {
+        self.last_added = Some(handler.clone());
+        self.any_handlers.push(handler);
+    }
+
+    // Lets the most recently added listener run for retries that are otherwise skipped
+    pub fn set_receive_retries(&mut self, receive: bool) {
+        let Some(handler) = self.last_added.clone() else {
+            return;
+        };
+        self.retry_receivers.retain(|receiver| !Arc::ptr_eq(receiver, &handler));
+        if receive {
+            self.retry_receivers.push(handler);
+        }
+    }
+
+    pub fn has_any_handlers(&self) -> bool {
+        !self.any_handlers.is_empty()
+    }
+
+    pub fn set_execution_mode(&mut self, mode: ExecutionMode) {
+        self.execution_mode = mode;
+    }
+
+    pub fn set_response_policy(&mut self, policy: ResponsePolicy) {
+        self.response_policy = policy;
+    }
+
+    pub fn set_timeout_budget(&mut self, budget: TimeoutBudget) {
+        self.timeout_budget = budget;
+    }
+
+    // Listeners `route_request` would run for this request, before any catch-all fallback
+    pub fn matched_listener_count(&self, request: &SlackRequest) -> usize {
+        self.matching_handlers(request).len()
+    }
+
+    pub fn any_handler_count(&self) -> usize {
+        self.any_handlers.len()
+    }
+
+    pub fn functions(&self) -> &[CustomFunction] {
+        &self.functions
+    }
+
+    // Registered listeners by kind, for the health report
+    pub fn listener_counts(&self) -> BTreeMap<&'static str, usize> {
+        fn total(handlers: &HashMap<String, Vec<ListenerHandler>>) -> usize {
+            handlers.values().map(Vec::len).sum()
+        }
+
+        let events = total(&self.event_handlers)
+            + self.event_pattern_handlers.len()
+            + self.filtered_event_handlers.values().map(Vec::len).sum::<usize>();
+        let shortcuts = total(&self.shortcut_handlers)
+            + total(&self.global_shortcut_handlers)
+            + total(&self.message_shortcut_handlers);
+
+        BTreeMap::from([
+            ("events", events),
+            ("commands", total(&self.command_handlers)),
+            ("actions", total(&self.action_handlers)),
+            ("shortcuts", shortcuts),
+            ("views", total(&self.view_handlers)),
+            ("messages", self.message_handlers.len()),
+            ("workflow_steps", self.step_handlers.len()),
+            ("functions", total(&self.function_handlers)),
+            ("any", self.any_handlers.len()),
+        ])
+    }
+
+    pub async fn route_request(&self, context: Context) -> Result<Option<SlackResponse>> {
+        let mut handlers = self.matching_handlers(&context.request);
+        if handlers.is_empty() {
+            handlers = self.any_handlers.clone();
+        }
+        let kind = context.request.body.kind();
+        if context.request.is_duplicate_retry() {
+            let before = handlers.len();
+            handlers.retain(|handler| self.retry_receivers.iter().any(|receiver| Arc::ptr_eq(receiver, handler)));
+            debug!(
+                "Skipping {} of {} listeners for retry {:?} ({:?})",
+                before - handlers.len(),
+                before,
+                context.request.retry_num(),
+                context.request.retry_reason(),
+            );
+        }
+        let mut responses = Vec::with_capacity(handlers.len());
+
+        if handlers.is_empty() && !context.request.is_duplicate_retry() {
+            debug!("No listener matched {} request", kind);
+            context.metrics.counter("UnhandledRequests", 1, &[("RequestType", kind)]);
+        }
+
+        match self.execution_mode {
+            ExecutionMode::Sequential => {
+                for (index, handler) in handlers.into_iter().enumerate() {
+                    context.metrics.counter("ListenerInvocations", 1, &[("RequestType", kind)]);
+                    let listener_context = listener_context(&context, index);
+                    let span = listener_context.logger.clone();
+                    let future = invoke(handler, listener_context).instrument(span);
+                    responses.push(self.timeout_budget.run(&context, future).await?);
+                }
+            }
+            ExecutionMode::Parallel => {
+                let futures = handlers.into_iter().enumerate().map(|(index, handler)| {
+                    context.metrics.counter("ListenerInvocations", 1, &[("RequestType", kind)]);
+                    let listener_context = listener_context(&context, index);
+                    let span = listener_context.logger.clone();
+                    let future = invoke(handler, listener_context).instrument(span);
+                    self.timeout_budget.run(&context, future)
+                });
+
+                // join_all keeps registration order; the first error wins once all have finished
+                for result in join_all(futures).await {
+                    responses.push(result?);
+                }
+            }
+        }
+
+        Ok(self.response_policy.select(responses))
+    }
+
+    fn matching_handlers(&self, request: &SlackRequest) -> Vec<ListenerHandler> {
+        let mut handlers = Vec::new();
+
+        match &request.body {
+            SlackRequestBody::Event(event_req) => {
+                let event_type = event_req.event.get("type").and_then(|v| v.as_str()).unwrap_or_default();
+
+                if let Some(registered) = self.event_handlers.get(event_type) {
+                    handlers.extend(registered.iter().cloned());
+                }
+
+                if let Some(registered) = self.filtered_event_handlers.get(event_type) {
+                    handlers.extend(
+                        registered
+                            .iter()
+                            .filter(|(filter, _)| filter(&event_req.event))
+                            .map(|(_, handler)| handler.clone()),
+                    );
+                }
+
+                if event_type == event_types::MESSAGE {
+                    handlers.extend(self.message_handlers.iter().cloned());
+                }
+
+                if event_type == event_types::FUNCTION_EXECUTED {
+                    let callback_id = event_req
+                        .event
+                        .get("function")
+                        .and_then(|f| f.get("callback_id"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default();
+                    if let Some(registered) = self.function_handlers.get(callback_id) {
+                        handlers.extend(registered.iter().cloned());
+                    }
+                }
+
+                if event_type == event_types::WORKFLOW_STEP_EXECUTE {
+                    let callback_id = event_req.event.get("callback_id").and_then(|v| v.as_str());
+                    handlers.extend(self.step_handler(callback_id, |step| step.execute.clone()));
+                }
+
+                if !self.event_pattern_handlers.is_empty() {
+                    let names = subscription_names(event_type, &event_req.event);
+                    handlers.extend(
+                        self.event_pattern_handlers
+                            .iter()
+                            .filter(|(pattern, _)| names.iter().any(|name| pattern_matches(pattern, name)))
+                            .map(|(_, handler)| handler.clone()),
+                    );
+                }
+            }
+            SlackRequestBody::Command(command_req) => {
+                if let Some(registered) = self.command_handlers.get(&command_req.command) {
+                    handlers.extend(registered.iter().cloned());
+                }
+            }
+            SlackRequestBody::Interactive(interactive_req) => match interactive_req.payload_type.as_str() {
+                "block_actions" | "interactive_message" => {
+                    for action in &interactive_req.actions {
+                        let action_id = action.get("action_id").and_then(|v| v.as_str()).unwrap_or_default();
+                        if let Some(registered) = self.action_handlers.get(action_id) {
+                            handlers.extend(registered.iter().cloned());
+                        }
+                    }
+                }
+                payload_type @ ("shortcut" | "message_action") => {
+                    // Global and message shortcuts share a callback_id namespace only for `App::shortcut`
+                    let typed = if payload_type == "shortcut" {
+                        &self.global_shortcut_handlers
+                    } else {
+                        &self.message_shortcut_handlers
+                    };
+                    if let Some(callback_id) = interactive_req.callback_id.as_ref() {
+                        for registered in [typed.get(callback_id), self.shortcut_handlers.get(callback_id)]
+                            .into_iter()
+                            .flatten()
+                        {
+                            handlers.extend(registered.iter().cloned());
+                        }
+                    }
+                }
+                "workflow_step_edit" => {
+                    let callback_id = interactive_req.callback_id.as_deref();
+                    handlers.extend(self.step_handler(callback_id, |step| step.edit.clone()));
+                }
+                "view_submission" => {
+                    let view = interactive_req.view.as_ref();
+                    let callback_id = view.and_then(|v| v.get("callback_id")).and_then(|v| v.as_str());
+                    if view.and_then(|v| v.get("type")).and_then(|v| v.as_str()) == Some("workflow_step") {
+                        handlers.extend(self.step_handler(callback_id, |step| step.save.clone()));
+                    } else if let Some(registered) = callback_id.and_then(|id| self.view_handlers.get(id)) {
+                        handlers.extend(registered.iter().cloned());
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+
+        handlers
+    }
+
+    fn step_handler<F>(&self, callback_id: Option<&str>, select: F) -> Option<ListenerHandler>
+    where
+        F: Fn(&WorkflowStep) -> Option<ListenerHandler>,
+    {
+        callback_id
+            .and_then(|callback_id| self.step_handlers.get(callback_id))
+            .and_then(select)
+    }
+}
+
+// Calling the handler happens inside the isolated future too, so a listener that panics
+// before its first await is caught like one that panics later
+async fn invoke(handler: ListenerHandler, context: Context) -> Result<SlackResponse> {
+    execution::isolate(async move { handler(context).await }).await
+}
+
+// Each listener runs in its own child of the request span, entered for as long as the listener
+// runs, so `tracing::info!` in a handler is attributed to it without touching spans
+fn listener_context(context: &Context, index: usize) -> Context {
+    let mut listener_context = context.clone();
+    listener_context.logger = tracing::info_span!(
+        parent: &context.logger,
+        "listener",
+        listener = %listener_name(&context.request),
+        index,
+    );
+    listener_context
+}
+
+// "command:/deploy", "event:app_mention", "action:approve_button", ...
+fn listener_name(request: &SlackRequest) -> String {
+    let kind = match &request.body {
+        SlackRequestBody::Interactive(interactive) => match interactive.payload_type.as_str() {
+            "block_actions" | "interactive_message" => "action",
+            "view_submission" | "view_closed" => "view",
+            "shortcut" | "message_action" => "shortcut",
+            "block_suggestion" => "options",
+            other => other,
+        },
+        body => body.kind(),
+    };
+    match request.routing_key() {
+        Some(key) => format!("{}:{}", kind, key),
+        None => kind.to_string(),
     }
-}
\ No newline at end of file
+}
+
+// The names an event can be subscribed under: its type, plus the Events API subscription
+// name for messages ("message.channels", "message.im", ...)
+fn subscription_names(event_type: &str, event: &serde_json::Value) -> Vec<String> {
+    let mut names = vec![event_type.to_string()];
+    if event_type == event_types::MESSAGE {
+        let channel_type = event.get("channel_type").and_then(|v| v.as_str());
+        let subscription = match channel_type {
+            Some("channel") => Some("channels"),
+            Some("group") => Some("groups"),
+            Some(other) => Some(other),
+            None => None,
+        };
+        if let Some(subscription) = subscription {
+            names.push(format!("{}.{}", event_type, subscription));
+        }
+    }
+    names
+}
+
+// `*` matches any run of characters; "message.*" also matches a bare "message"
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    if let Some(prefix) = pattern.strip_suffix(".*") {
+        if name == prefix {
+            return true;
+        }
+    }
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    for (index, part) in parts.iter().enumerate() {
+        if index == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::EventRequestBuilder;
+
+    fn noop() -> ListenerHandler {
+        handler(|_context| async { Ok(SlackResponse::empty()) })
+    }
+
+    #[test]
+    fn star_matches_everything() {
+        assert!(pattern_matches("*", "app_mention"));
+        assert!(pattern_matches("*", "message.im"));
+    }
+
+    #[test]
+    fn prefix_pattern_matches_subtypes() {
+        assert!(pattern_matches("message.*", "message.channels"));
+        assert!(pattern_matches("message.*", "message.im"));
+        assert!(pattern_matches("app_*", "app_home_opened"));
+    }
+
+    #[test]
+    fn prefix_pattern_matches_bare_parent() {
+        assert!(pattern_matches("message.*", "message"));
+    }
+
+    #[test]
+    fn pattern_without_match() {
+        assert!(!pattern_matches("message.*", "messages"));
+        assert!(!pattern_matches("message.*", "app_mention"));
+        assert!(!pattern_matches("message.im", "message.channels"));
+        assert!(!pattern_matches("app_*_opened", "app_home_closed"));
+    }
+
+    #[test]
+    fn exact_handlers_run_before_pattern_handlers() {
+        let mut router = EventRouter::new();
+        let (pattern, exact) = (noop(), noop());
+        // Registered first, but still matched after the exact type
+        router.add_event_handler("*", pattern.clone());
+        router.add_event_handler("app_mention", exact.clone());
+
+        let handlers = router.matching_handlers(&EventRequestBuilder::new("app_mention").build());
+
+        assert_eq!(handlers.len(), 2);
+        assert!(Arc::ptr_eq(&handlers[0], &exact));
+        assert!(Arc::ptr_eq(&handlers[1], &pattern));
+    }
+}
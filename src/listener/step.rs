@@ -0,0 +1,46 @@
+use crate::context::Context;
+use crate::error::Result;
+use crate::listener::{handler, ListenerHandler};
+use crate::response::SlackResponse;
+use std::future::Future;
+
+// Handlers for a Workflow Builder step from apps (the legacy steps platform)
+#[derive(Clone, Default)]
+pub struct WorkflowStep {
+    pub edit: Option<ListenerHandler>,
+    pub save: Option<ListenerHandler>,
+    pub execute: Option<ListenerHandler>,
+}
+
+impl WorkflowStep {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn edit<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.edit = Some(handler(f));
+        self
+    }
+
+    pub fn save<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.save = Some(handler(f));
+        self
+    }
+
+    pub fn execute<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+    {
+        self.execute = Some(handler(f));
+        self
+    }
+}
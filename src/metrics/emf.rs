@@ -0,0 +1,107 @@
+use crate::metrics::Metrics;
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Count,
+    Milliseconds,
+    None,
+}
+
+impl Unit {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Unit::Count => "Count",
+            Unit::Milliseconds => "Milliseconds",
+            Unit::None => "None",
+        }
+    }
+}
+
+// Writes CloudWatch Embedded Metric Format records to stdout, which Lambda ships to
+// CloudWatch Logs where they are extracted into metrics without any API calls.
+#[derive(Debug, Clone)]
+pub struct EmfMetrics {
+    namespace: String,
+    dimensions: Vec<(String, String)>,
+}
+
+impl EmfMetrics {
+    pub fn new<S: Into<String>>(namespace: S) -> Self {
+        let mut dimensions = Vec::new();
+        if let Ok(function_name) = std::env::var("AWS_LAMBDA_FUNCTION_NAME") {
+            dimensions.push(("FunctionName".to_string(), function_name));
+        }
+
+        Self {
+            namespace: namespace.into(),
+            dimensions,
+        }
+    }
+
+    pub fn with_dimension<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.dimensions.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn emit(&self, dimensions: &[(&str, &str)], metrics: &[(&str, f64, Unit)]) {
+        println!("{}", self.format(dimensions, metrics));
+    }
+
+    pub fn format(&self, dimensions: &[(&str, &str)], metrics: &[(&str, f64, Unit)]) -> Value {
+        let mut record = Map::new();
+        let mut dimension_names = Vec::new();
+
+        for (name, value) in &self.dimensions {
+            dimension_names.push(name.clone());
+            record.insert(name.clone(), json!(value));
+        }
+        for (name, value) in dimensions {
+            dimension_names.push(name.to_string());
+            record.insert(name.to_string(), json!(value));
+        }
+
+        let definitions: Vec<Value> = metrics
+            .iter()
+            .map(|(name, _, unit)| json!({ "Name": name, "Unit": unit.as_str() }))
+            .collect();
+
+        for (name, value, _) in metrics {
+            record.insert(name.to_string(), json!(value));
+        }
+
+        record.insert(
+            "_aws".to_string(),
+            json!({
+                "Timestamp": Utc::now().timestamp_millis(),
+                "CloudWatchMetrics": [{
+                    "Namespace": self.namespace,
+                    "Dimensions": [dimension_names],
+                    "Metrics": definitions,
+                }],
+            }),
+        );
+
+        Value::Object(record)
+    }
+}
+
+impl Metrics for EmfMetrics {
+    fn counter(&self, name: &str, value: u64, tags: &[(&str, &str)]) {
+        self.emit(tags, &[(name, value as f64, Unit::Count)]);
+    }
+
+    fn histogram(&self, name: &str, value: f64, tags: &[(&str, &str)]) {
+        self.emit(tags, &[(name, value, Unit::Milliseconds)]);
+    }
+
+    fn gauge(&self, name: &str, value: f64, tags: &[(&str, &str)]) {
+        self.emit(tags, &[(name, value, Unit::None)]);
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stdout().flush();
+    }
+}
@@ -0,0 +1,30 @@
+pub mod emf;
+pub mod statsd;
+
+pub use emf::{EmfMetrics, Unit};
+pub use statsd::StatsdMetrics;
+
+use std::fmt::Debug;
+
+pub trait Metrics: Send + Sync + Debug {
+    fn counter(&self, name: &str, value: u64, tags: &[(&str, &str)]);
+
+    // Histograms are used for timings, recorded in milliseconds
+    fn histogram(&self, name: &str, value: f64, tags: &[(&str, &str)]);
+
+    fn gauge(&self, name: &str, value: f64, tags: &[(&str, &str)]);
+
+    // Called before an invocation returns, while the environment can still be frozen afterwards
+    fn flush(&self) {}
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {
+    fn counter(&self, _name: &str, _value: u64, _tags: &[(&str, &str)]) {}
+
+    fn histogram(&self, _name: &str, _value: f64, _tags: &[(&str, &str)]) {}
+
+    fn gauge(&self, _name: &str, _value: f64, _tags: &[(&str, &str)]) {}
+}
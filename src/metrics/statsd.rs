@@ -0,0 +1,61 @@
+use crate::error::{Result, SlackError};
+use crate::metrics::Metrics;
+use std::net::UdpSocket;
+use tracing::debug;
+
+// Sends DogStatsD-style datagrams (`name:value|type|#tag:value`) over UDP
+#[derive(Debug)]
+pub struct StatsdMetrics {
+    socket: UdpSocket,
+    prefix: String,
+}
+
+impl StatsdMetrics {
+    pub fn new<S: AsRef<str>>(address: S) -> Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")
+            .and_then(|socket| socket.connect(address.as_ref()).map(|_| socket))
+            .map_err(|e| SlackError::Config(format!("Invalid StatsD address: {}", e)))?;
+        socket
+            .set_nonblocking(true)
+            .map_err(|e| SlackError::Config(format!("Failed to configure StatsD socket: {}", e)))?;
+
+        Ok(Self {
+            socket,
+            prefix: String::new(),
+        })
+    }
+
+    pub fn with_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn send(&self, name: &str, value: &str, metric_type: &str, tags: &[(&str, &str)]) {
+        let mut line = format!("{}{}:{}|{}", self.prefix, name, value, metric_type);
+
+        if !tags.is_empty() {
+            let tags: Vec<String> = tags.iter().map(|(k, v)| format!("{}:{}", k, v)).collect();
+            line.push_str("|#");
+            line.push_str(&tags.join(","));
+        }
+
+        // Metrics are best effort; a dropped datagram must never fail a request
+        if let Err(e) = self.socket.send(line.as_bytes()) {
+            debug!("Failed to send StatsD metric {}: {}", name, e);
+        }
+    }
+}
+
+impl Metrics for StatsdMetrics {
+    fn counter(&self, name: &str, value: u64, tags: &[(&str, &str)]) {
+        self.send(name, &value.to_string(), "c", tags);
+    }
+
+    fn histogram(&self, name: &str, value: f64, tags: &[(&str, &str)]) {
+        self.send(name, &value.to_string(), "ms", tags);
+    }
+
+    fn gauge(&self, name: &str, value: f64, tags: &[(&str, &str)]) {
+        self.send(name, &value.to_string(), "g", tags);
+    }
+}
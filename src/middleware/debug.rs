@@ -0,0 +1,97 @@
+// Verbose request tracing for development, enabled with `AppBuilder::debug_mode(true)`.
+// Everything is logged at debug level with credentials and response URLs redacted.
+use crate::error::Result;
+use crate::request::SlackRequest;
+use crate::response::{SlackResponse, SlackResponseBody};
+use serde_json::{Map, Value};
+use tracing::debug;
+
+pub const REDACTED: &str = "[redacted]";
+
+// Keys whose values grant access to a workspace or act on behalf of the app
+const SECRET_KEYS: &[&str] = &[
+    "token",
+    "bot_token",
+    "user_token",
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "signing_secret",
+    "code",
+    "response_url",
+    "bot_access_token",
+];
+
+pub fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| {
+                    let value = if SECRET_KEYS.contains(&key.as_str()) && !value.is_null() {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        _ => value.clone(),
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(&redact(value)).unwrap_or_default()
+}
+
+pub(crate) fn log_request(request: &SlackRequest) {
+    let body = serde_json::to_value(&request.body).unwrap_or(Value::Null);
+    debug!(
+        "[debug] received {} request {} {} (routing key: {})\n{}",
+        request.body.kind(),
+        request.method,
+        request.path,
+        request.routing_key().unwrap_or("none"),
+        pretty(&body)
+    );
+}
+
+pub(crate) fn log_match(request: &SlackRequest, matched: usize, catch_all: usize) {
+    let routing_key = request.routing_key().unwrap_or("none");
+    match (matched, catch_all) {
+        (0, 0) => debug!(
+            "[debug] no listener matched {} {:?}; register one with the matching app.event/command/action/shortcut",
+            request.body.kind(),
+            routing_key
+        ),
+        (0, n) => debug!("[debug] no listener matched {:?}, falling back to {} catch-all listener(s)", routing_key, n),
+        (n, _) => debug!("[debug] {} listener(s) matched {} {:?}", n, request.body.kind(), routing_key),
+    }
+}
+
+pub(crate) fn log_middleware(index: usize, called_next: bool) {
+    if called_next {
+        debug!("[debug] middleware #{} called next", index);
+    } else {
+        debug!("[debug] middleware #{} returned without calling next; listeners were skipped", index);
+    }
+}
+
+pub(crate) fn log_result(result: &Result<SlackResponse>) {
+    match result {
+        Ok(response) => {
+            let body = match &response.body {
+                SlackResponseBody::Empty => "(empty)".to_string(),
+                SlackResponseBody::Content(content) => content.clone(),
+                SlackResponseBody::Binary(bytes) => format!("({} bytes of binary data)", bytes.len()),
+                #[cfg(feature = "streaming")]
+                SlackResponseBody::Stream(_) => "(streamed body)".to_string(),
+                body => pretty(&serde_json::to_value(body).unwrap_or(Value::Null)),
+            };
+            debug!("[debug] responding {} {:?}\n{}", response.status_code, response.headers, body);
+        }
+        Err(e) => debug!("[debug] request failed: {}", e),
+    }
+}
@@ -0,0 +1,169 @@
+use crate::context::Context;
+use crate::error::SlackError;
+use crate::middleware::{MiddlewareFuture, Next};
+use crate::request::SlackRequest;
+use std::net::IpAddr;
+use std::sync::Arc;
+use tracing::warn;
+
+// An address range such as "10.0.0.0/8" or "2600:1f18::/32"; a bare address is a /32 or /128
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    pub fn parse(cidr: &str) -> Option<Self> {
+        let (address, prefix) = match cidr.trim().split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (cidr.trim(), None),
+        };
+        let network: IpAddr = address.parse().ok()?;
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse().ok().filter(|prefix| *prefix <= max)?,
+            None => max,
+        };
+        Some(Self { network, prefix })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        // IPv4 clients reaching a dual-stack endpoint can show up as ::ffff:a.b.c.d
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+            ip => ip,
+        };
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix)).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix)).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+// The client address as the load balancer saw it: API Gateway's `sourceIp`, otherwise the
+// last X-Forwarded-For entry, the one an ALB appends. Earlier entries come from the client
+// and can be forged.
+pub fn source_ip(request: &SlackRequest) -> Option<IpAddr> {
+    let from_gateway = request.gateway.as_ref().and_then(|gateway| gateway.source_ip.as_deref());
+    let forwarded = || request.header("x-forwarded-for")?.rsplit(',').next();
+    from_gateway.or_else(forwarded)?.trim().parse().ok()
+}
+
+// Rejects HTTP requests from outside the given ranges with a 403, in addition to signature
+// verification. Requests that didn't arrive over HTTP (Socket Mode) carry no address and
+// pass. Panics on a malformed range, as the list is fixed at startup.
+//
+//     app.use_middleware(ip_allowlist(["203.0.113.0/24", "2001:db8::/32"]));
+pub fn ip_allowlist<I>(cidrs: I) -> impl Fn(Context, Next) -> MiddlewareFuture + Send + Sync + 'static
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let allowed: Arc<Vec<Cidr>> = Arc::new(
+        cidrs
+            .into_iter()
+            .map(|cidr| {
+                Cidr::parse(cidr.as_ref()).unwrap_or_else(|| panic!("invalid CIDR range {:?}", cidr.as_ref()))
+            })
+            .collect(),
+    );
+
+    move |context: Context, next: Next| -> MiddlewareFuture {
+        let allowed = allowed.clone();
+        Box::pin(async move {
+            let over_http = context.request.gateway.is_some() || context.request.header("x-forwarded-for").is_some();
+            if !over_http {
+                return next(context).await;
+            }
+
+            match source_ip(&context.request) {
+                Some(ip) if allowed.iter().any(|cidr| cidr.contains(ip)) => next(context).await,
+                ip => {
+                    warn!("Rejecting request from {:?}, outside the IP allowlist", ip);
+                    Err(SlackError::IpNotAllowed(ip.map(|ip| ip.to_string()).unwrap_or_default()))
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::GatewayContext;
+    use crate::testing::CommandRequestBuilder;
+    use http::HeaderValue;
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    fn forwarded_for(value: &str) -> SlackRequest {
+        let mut request = CommandRequestBuilder::new("/deploy").build();
+        request.headers.insert("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+        request
+    }
+
+    #[test]
+    fn zero_prefix_contains_every_address_of_its_family() {
+        let cidr = Cidr::parse("0.0.0.0/0").unwrap();
+
+        assert!(cidr.contains(ip("203.0.113.7")));
+        assert!(cidr.contains(ip("255.255.255.255")));
+        assert!(!cidr.contains(ip("2001:db8::1")));
+        assert!(Cidr::parse("::/0").unwrap().contains(ip("2001:db8::1")));
+    }
+
+    #[test]
+    fn full_prefix_contains_only_that_address() {
+        let v4 = Cidr::parse("203.0.113.7").unwrap();
+        assert_eq!(v4, Cidr::parse("203.0.113.7/32").unwrap());
+        assert!(v4.contains(ip("203.0.113.7")));
+        assert!(!v4.contains(ip("203.0.113.8")));
+
+        let v6 = Cidr::parse("2001:db8::1/128").unwrap();
+        assert!(v6.contains(ip("2001:db8::1")));
+        assert!(!v6.contains(ip("2001:db8::2")));
+    }
+
+    #[test]
+    fn ipv4_mapped_addresses_match_ipv4_ranges() {
+        let cidr = Cidr::parse("203.0.113.0/24").unwrap();
+
+        assert!(cidr.contains(ip("::ffff:203.0.113.7")));
+        assert!(!cidr.contains(ip("::ffff:198.51.100.7")));
+    }
+
+    #[test]
+    fn malformed_ranges_are_rejected() {
+        assert!(Cidr::parse("10.0.0.0/33").is_none());
+        assert!(Cidr::parse("2001:db8::/129").is_none());
+        assert!(Cidr::parse("not an address").is_none());
+    }
+
+    #[test]
+    fn source_ip_is_the_last_forwarded_for_entry() {
+        let request = forwarded_for("198.51.100.1, 10.0.0.1, 203.0.113.7");
+
+        assert_eq!(source_ip(&request), Some(ip("203.0.113.7")));
+    }
+
+    #[test]
+    fn source_ip_prefers_the_gateway() {
+        let mut request = forwarded_for("203.0.113.7");
+        request.gateway = Some(GatewayContext {
+            source_ip: Some("198.51.100.1".to_string()),
+            ..GatewayContext::default()
+        });
+
+        assert_eq!(source_ip(&request), Some(ip("198.51.100.1")));
+    }
+}
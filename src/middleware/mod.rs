@@ -1,23 +1,66 @@
 pub mod auth;
+pub mod debug;
+pub mod ip_allowlist;
 pub mod logging;
+pub mod shared_channels;
+
+pub use ip_allowlist::ip_allowlist;
+pub use shared_channels::exclude_external_channels;
 
 use crate::error::Result;
-use crate::request::SlackRequest;
 use crate::response::SlackResponse;
 use crate::context::Context;
-use std::sync::Arc;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+pub type MiddlewareFuture = Pin<Box<dyn Future<Output = Result<SlackResponse>> + Send>>;
+pub type Next = Box<dyn FnOnce(Context) -> MiddlewareFuture + Send>;
+pub type MiddlewareHandler = Arc<dyn Fn(Context, Next) -> MiddlewareFuture + Send + Sync>;
+
+pub fn middleware<F, Fut>(f: F) -> MiddlewareHandler
+where
+    F: Fn(Context, Next) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+{
+    Arc::new(move |context, next| Box::pin(f(context, next)))
+}
 
-pub type MiddlewareHandler = Arc<dyn Fn(Context, Next) -> Result<SlackResponse> + Send + Sync>;
-pub type Next = Arc<dyn Fn(Context) -> Result<SlackResponse> + Send + Sync>;
+// Runs once the chain has produced its outcome; returns the outcome the adapter should see
+pub type AfterResponseHandler = Arc<dyn Fn(Context, Result<SlackResponse>) -> MiddlewareFuture + Send + Sync>;
 
+pub fn after_response<F, Fut>(f: F) -> AfterResponseHandler
+where
+    F: Fn(Context, Result<SlackResponse>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<SlackResponse>> + Send + 'static,
+{
+    Arc::new(move |context, result| Box::pin(f(context, result)))
+}
+
+#[derive(Clone)]
 pub struct MiddlewareStack {
     middlewares: Vec<MiddlewareHandler>,
+    after: Vec<AfterResponseHandler>,
+}
+
+// The context as the chain received it, and as it reached the endpoint if it got that far
+struct Observed {
+    entry: Context,
+    reached: Arc<Mutex<Option<Context>>>,
+}
+
+impl Default for MiddlewareStack {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MiddlewareStack {
     pub fn new() -> Self {
         Self {
             middlewares: Vec::new(),
+            after: Vec::new(),
         }
     }
 
@@ -25,9 +68,91 @@ impl MiddlewareStack {
         self.middlewares.push(middleware);
     }
 
-    pub async fn execute(&self, context: Context) -> Result<SlackResponse> {
-        // Execute middleware chain
-        // This is a placeholder implementation
-        Ok(SlackResponse::empty())
+    pub fn add_after(&mut self, hook: AfterResponseHandler) {
+        self.after.push(hook);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty() && self.after.is_empty()
+    }
+
+    // Middleware run in registration order; each decides whether to call `next`,
+    // and the innermost `next` is `endpoint` (normally the router)
+    pub async fn execute(&self, context: Context, endpoint: Next) -> Result<SlackResponse> {
+        let (endpoint, observed) = self.observe(&context, endpoint);
+        let chain = self.middlewares.iter().rev().fold(endpoint, |next, middleware| {
+            let middleware = middleware.clone();
+            Box::new(move |context| middleware(context, next))
+        });
+
+        let result = chain(context).await;
+        self.run_after(observed, result).await
+    }
+
+    // Same as `execute`, additionally logging which middleware stopped the chain
+    pub async fn execute_traced(&self, context: Context, endpoint: Next) -> Result<SlackResponse> {
+        let (endpoint, observed) = self.observe(&context, endpoint);
+        let chain = self.middlewares.iter().enumerate().rev().fold(endpoint, |next, (index, middleware)| {
+            let middleware = middleware.clone();
+            Box::new(move |context| {
+                let called = Arc::new(AtomicBool::new(false));
+                let flag = called.clone();
+                let next: Next = Box::new(move |context| {
+                    flag.store(true, Ordering::Relaxed);
+                    next(context)
+                });
+                let future = middleware(context, next);
+
+                Box::pin(async move {
+                    let result = future.await;
+                    debug::log_middleware(index, called.load(Ordering::Relaxed));
+                    result
+                })
+            })
+        });
+
+        let result = chain(context).await;
+        self.run_after(observed, result).await
     }
-}
\ No newline at end of file
+
+    // Wraps the endpoint so after-response hooks see values middleware put on the context
+    fn observe(&self, context: &Context, endpoint: Next) -> (Next, Option<Observed>) {
+        if self.after.is_empty() {
+            return (endpoint, None);
+        }
+
+        let reached = Arc::new(Mutex::new(None));
+        let slot = reached.clone();
+        let endpoint: Next = Box::new(move |context: Context| {
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(context.clone());
+            }
+            endpoint(context)
+        });
+
+        let observed = Observed {
+            entry: context.clone(),
+            reached,
+        };
+        (endpoint, Some(observed))
+    }
+
+    // Hooks run in registration order, each receiving the outcome left by the previous one;
+    // they also run when middleware short-circuited or the listener failed
+    async fn run_after(&self, observed: Option<Observed>, mut result: Result<SlackResponse>) -> Result<SlackResponse> {
+        let Some(observed) = observed else {
+            return result;
+        };
+        let context = observed
+            .reached
+            .lock()
+            .ok()
+            .and_then(|mut slot| slot.take())
+            .unwrap_or(observed.entry);
+
+        for hook in &self.after {
+            result = hook(context.clone(), result).await;
+        }
+        result
+    }
+}
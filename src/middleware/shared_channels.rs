@@ -0,0 +1,26 @@
+use crate::context::Context;
+use crate::middleware::{MiddlewareFuture, Next};
+use crate::response::SlackResponse;
+use tracing::debug;
+
+// Drops requests from Slack Connect channels (connected or pending) before any listener runs.
+// Requests outside a channel pass through; a failed conversations.info lookup fails the request.
+//
+//     app.use_middleware(exclude_external_channels());
+pub fn exclude_external_channels() -> impl Fn(Context, Next) -> MiddlewareFuture + Send + Sync + 'static {
+    |context: Context, next: Next| -> MiddlewareFuture {
+        Box::pin(async move {
+            if context.request.channel_id().is_none() {
+                return next(context).await;
+            }
+
+            let channel = context.channel_info().await?;
+            if channel.is_external() {
+                debug!("Ignoring request from externally shared channel {}", channel.id);
+                return Ok(SlackResponse::empty());
+            }
+
+            next(context).await
+        })
+    }
+}
@@ -1,61 +1,86 @@
 #[cfg(feature = "oauth")]
 use crate::error::{Result, SlackError};
-use crate::oauth::{InstallationStore, StateStore, Installation, OAuthState};
+use crate::dynamodb::{batch_put_items, ensure_table, TableOutcome, TableSpec};
+use crate::infrastructure::Resource;
+use crate::oauth::{InstallationStore, StateStore, Installation, InstallationPage, OAuthState};
+use crate::secret::SecretString;
 use async_trait::async_trait;
 use aws_sdk_dynamodb::{Client as DynamoDbClient, types::AttributeValue};
 use chrono::{DateTime, Utc};
-use serde_json;
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct DynamoDbInstallationStore {
     client: DynamoDbClient,
+    read_client: Option<DynamoDbClient>,
     table_name: String,
+    replica_regions: Vec<String>,
 }
 
 impl DynamoDbInstallationStore {
     pub fn new(client: DynamoDbClient, table_name: String) -> Self {
-        Self { client, table_name }
+        Self {
+            client,
+            read_client: None,
+            table_name,
+            replica_regions: Vec::new(),
+        }
+    }
+
+    // Reads go to this client (typically the nearest replica) and fall back to the primary on a miss
+    pub fn with_read_client(mut self, client: DynamoDbClient) -> Self {
+        self.read_client = Some(client);
+        self
+    }
+
+    pub async fn with_preferred_read_region(self, region: &str) -> Self {
+        let client = crate::shared::regional_dynamodb_client(region).await;
+        self.with_read_client(client)
+    }
+
+    // Regions the table is replicated to as a global table, besides the client's own
+    pub fn with_replica_regions<I, S>(mut self, regions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.replica_regions = regions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn table_spec<S: Into<String>>(table_name: S) -> TableSpec {
+        // Installations outlive their tokens (which get refreshed), so no TTL here
+        TableSpec::new(table_name, "team_id").with_sort_key("enterprise_id")
     }
 
     pub async fn create_table(&self) -> Result<()> {
-        let key_schema = vec![
-            aws_sdk_dynamodb::types::KeySchemaElement::builder()
-                .attribute_name("team_id")
-                .key_type(aws_sdk_dynamodb::types::KeyType::Hash)
-                .build()
-                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
-            aws_sdk_dynamodb::types::KeySchemaElement::builder()
-                .attribute_name("enterprise_id")
-                .key_type(aws_sdk_dynamodb::types::KeyType::Range)
-                .build()
-                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
-        ];
+        ensure_table(&self.client, &self.spec(), DEFAULT_TABLE_TIMEOUT).await?;
+        Ok(())
+    }
 
-        let attribute_definitions = vec![
-            aws_sdk_dynamodb::types::AttributeDefinition::builder()
-                .attribute_name("team_id")
-                .attribute_type(aws_sdk_dynamodb::types::ScalarAttributeType::S)
-                .build()
-                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
-            aws_sdk_dynamodb::types::AttributeDefinition::builder()
-                .attribute_name("enterprise_id")
-                .attribute_type(aws_sdk_dynamodb::types::ScalarAttributeType::S)
-                .build()
-                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
-        ];
+    fn spec(&self) -> TableSpec {
+        Self::table_spec(self.table_name.as_str()).with_replicas(self.replica_regions.iter().cloned())
+    }
 
-        self.client
-            .create_table()
+    async fn get_item(
+        &self,
+        client: &DynamoDbClient,
+        team_id: &str,
+        enterprise_id: Option<&str>,
+    ) -> Result<Option<HashMap<String, AttributeValue>>> {
+        let enterprise_key = enterprise_id.unwrap_or("NONE");
+
+        let response = client
+            .get_item()
             .table_name(&self.table_name)
-            .set_key_schema(Some(key_schema))
-            .set_attribute_definitions(Some(attribute_definitions))
-            .billing_mode(aws_sdk_dynamodb::types::BillingMode::PayPerRequest)
+            .key("team_id", AttributeValue::S(team_id.to_string()))
+            .key("enterprise_id", AttributeValue::S(enterprise_key.to_string()))
             .send()
             .await
             .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
 
-        Ok(())
+        Ok(response.item)
     }
 
     fn installation_to_item(&self, installation: &Installation) -> HashMap<String, AttributeValue> {
@@ -67,7 +92,7 @@ impl DynamoDbInstallationStore {
         ));
         
         if let Some(bot_token) = &installation.bot_token {
-            item.insert("bot_token".to_string(), AttributeValue::S(bot_token.clone()));
+            item.insert("bot_token".to_string(), AttributeValue::S(bot_token.expose().to_string()));
         }
         
         if let Some(bot_user_id) = &installation.bot_user_id {
@@ -75,7 +100,7 @@ impl DynamoDbInstallationStore {
         }
         
         if let Some(user_token) = &installation.user_token {
-            item.insert("user_token".to_string(), AttributeValue::S(user_token.clone()));
+            item.insert("user_token".to_string(), AttributeValue::S(user_token.expose().to_string()));
         }
         
         if let Some(user_id) = &installation.user_id {
@@ -89,6 +114,10 @@ impl DynamoDbInstallationStore {
         if let Some(expires_at) = installation.expires_at {
             item.insert("expires_at".to_string(), AttributeValue::S(expires_at.to_rfc3339()));
         }
+
+        if let Some(webhook) = installation.incoming_webhook.as_ref().and_then(|w| serde_json::to_string(w).ok()) {
+            item.insert("incoming_webhook".to_string(), AttributeValue::S(webhook));
+        }
         
         item
     }
@@ -102,12 +131,12 @@ impl DynamoDbInstallationStore {
         let enterprise_id = item.get("enterprise_id")
             .and_then(|v| v.as_s().ok())
             .filter(|s| *s != "NONE")
-            .map(|s| s.clone());
+            .cloned();
 
-        let bot_token = item.get("bot_token").and_then(|v| v.as_s().ok()).map(|s| s.clone());
-        let bot_user_id = item.get("bot_user_id").and_then(|v| v.as_s().ok()).map(|s| s.clone());
-        let user_token = item.get("user_token").and_then(|v| v.as_s().ok()).map(|s| s.clone());
-        let user_id = item.get("user_id").and_then(|v| v.as_s().ok()).map(|s| s.clone());
+        let bot_token = item.get("bot_token").and_then(|v| v.as_s().ok()).map(SecretString::new);
+        let bot_user_id = item.get("bot_user_id").and_then(|v| v.as_s().ok()).cloned();
+        let user_token = item.get("user_token").and_then(|v| v.as_s().ok()).map(SecretString::new);
+        let user_id = item.get("user_id").and_then(|v| v.as_s().ok()).cloned();
 
         let scopes = item.get("scopes")
             .and_then(|v| v.as_s().ok())
@@ -130,6 +159,10 @@ impl DynamoDbInstallationStore {
             .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
             .map(|dt| dt.with_timezone(&Utc));
 
+        let incoming_webhook = item.get("incoming_webhook")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|s| serde_json::from_str(s).ok());
+
         Ok(Installation {
             team_id,
             enterprise_id,
@@ -141,12 +174,17 @@ impl DynamoDbInstallationStore {
             user_scopes,
             installed_at,
             expires_at,
+            incoming_webhook,
         })
     }
 }
 
 #[async_trait]
 impl InstallationStore for DynamoDbInstallationStore {
+    fn required_resources(&self) -> Vec<Resource> {
+        vec![Resource::Table(self.spec())]
+    }
+
     async fn save(&self, installation: &Installation) -> Result<()> {
         let item = self.installation_to_item(installation);
         
@@ -161,19 +199,25 @@ impl InstallationStore for DynamoDbInstallationStore {
         Ok(())
     }
 
+    async fn save_many(&self, installations: &[Installation]) -> Result<()> {
+        let items = installations
+            .iter()
+            .map(|installation| self.installation_to_item(installation))
+            .collect();
+        batch_put_items(&self.client, &self.table_name, items).await
+    }
+
     async fn find_by_team(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<Option<Installation>> {
-        let enterprise_key = enterprise_id.unwrap_or("NONE");
-        
-        let response = self.client
-            .get_item()
-            .table_name(&self.table_name)
-            .key("team_id", AttributeValue::S(team_id.to_string()))
-            .key("enterprise_id", AttributeValue::S(enterprise_key.to_string()))
-            .send()
-            .await
-            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+        // Replication lags by a second or so, a fresh install may not have reached the read replica yet
+        let mut item = None;
+        if let Some(read_client) = &self.read_client {
+            item = self.get_item(read_client, team_id, enterprise_id).await?;
+        }
+        if item.is_none() {
+            item = self.get_item(&self.client, team_id, enterprise_id).await?;
+        }
 
-        if let Some(item) = response.item {
+        if let Some(item) = item {
             Ok(Some(self.item_to_installation(item)?))
         } else {
             Ok(None)
@@ -194,57 +238,118 @@ impl InstallationStore for DynamoDbInstallationStore {
 
         Ok(())
     }
+
+    async fn list_page(&self, cursor: Option<&str>, limit: usize) -> Result<InstallationPage> {
+        // The cursor is the scan's LastEvaluatedKey, i.e. the team_id/enterprise_id pair as JSON
+        let start_key = cursor
+            .map(serde_json::from_str::<HashMap<String, String>>)
+            .transpose()?
+            .map(|key| key.into_iter().map(|(name, value)| (name, AttributeValue::S(value))).collect());
+
+        let response = self.client
+            .scan()
+            .table_name(&self.table_name)
+            .limit(limit.clamp(1, i32::MAX as usize) as i32)
+            .set_exclusive_start_key(start_key)
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        let installations = response.items.unwrap_or_default()
+            .into_iter()
+            .map(|item| self.item_to_installation(item))
+            .collect::<Result<Vec<_>>>()?;
+
+        let next_cursor = response.last_evaluated_key
+            .map(|key| {
+                let key: HashMap<String, String> = key.into_iter()
+                    .filter_map(|(name, value)| value.as_s().ok().map(|value| (name, value.clone())))
+                    .collect();
+                serde_json::to_string(&key)
+            })
+            .transpose()?;
+
+        Ok(InstallationPage { installations, next_cursor })
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct DynamoDbStateStore {
     client: DynamoDbClient,
+    read_client: Option<DynamoDbClient>,
     table_name: String,
+    replica_regions: Vec<String>,
 }
 
 impl DynamoDbStateStore {
     pub fn new(client: DynamoDbClient, table_name: String) -> Self {
-        Self { client, table_name }
+        Self {
+            client,
+            read_client: None,
+            table_name,
+            replica_regions: Vec::new(),
+        }
+    }
+
+    // Reads go to this client (typically the nearest replica) and fall back to the primary on a miss
+    pub fn with_read_client(mut self, client: DynamoDbClient) -> Self {
+        self.read_client = Some(client);
+        self
+    }
+
+    pub async fn with_preferred_read_region(self, region: &str) -> Self {
+        let client = crate::shared::regional_dynamodb_client(region).await;
+        self.with_read_client(client)
+    }
+
+    // Regions the table is replicated to as a global table, besides the client's own
+    pub fn with_replica_regions<I, S>(mut self, regions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.replica_regions = regions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn table_spec<S: Into<String>>(table_name: S) -> TableSpec {
+        TableSpec::new(table_name, "state").with_ttl("expires_at")
     }
 
     pub async fn create_table(&self) -> Result<()> {
-        let key_schema = vec![
-            aws_sdk_dynamodb::types::KeySchemaElement::builder()
-                .attribute_name("state")
-                .key_type(aws_sdk_dynamodb::types::KeyType::Hash)
-                .build()
-                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
-        ];
+        ensure_table(&self.client, &self.spec(), DEFAULT_TABLE_TIMEOUT).await?;
+        Ok(())
+    }
 
-        let attribute_definitions = vec![
-            aws_sdk_dynamodb::types::AttributeDefinition::builder()
-                .attribute_name("state")
-                .attribute_type(aws_sdk_dynamodb::types::ScalarAttributeType::S)
-                .build()
-                .map_err(|e| SlackError::DynamoDb(e.to_string()))?,
-        ];
+    fn spec(&self) -> TableSpec {
+        Self::table_spec(self.table_name.as_str()).with_replicas(self.replica_regions.iter().cloned())
+    }
 
-        self.client
-            .create_table()
+    async fn get_item(&self, client: &DynamoDbClient, state: &str) -> Result<Option<HashMap<String, AttributeValue>>> {
+        let response = client
+            .get_item()
             .table_name(&self.table_name)
-            .set_key_schema(Some(key_schema))
-            .set_attribute_definitions(Some(attribute_definitions))
-            .billing_mode(aws_sdk_dynamodb::types::BillingMode::PayPerRequest)
+            .key("state", AttributeValue::S(state.to_string()))
             .send()
             .await
             .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
 
-        Ok(())
+        Ok(response.item)
     }
 }
 
 #[async_trait]
 impl StateStore for DynamoDbStateStore {
+    fn required_resources(&self) -> Vec<Resource> {
+        vec![Resource::Table(self.spec())]
+    }
+
     async fn save(&self, state: &OAuthState) -> Result<()> {
         let mut item = HashMap::new();
         item.insert("state".to_string(), AttributeValue::S(state.state.clone()));
         item.insert("created_at".to_string(), AttributeValue::S(state.created_at.to_rfc3339()));
-        item.insert("expires_at".to_string(), AttributeValue::S(state.expires_at.to_rfc3339()));
+        // Epoch seconds so DynamoDB TTL removes abandoned states
+        item.insert("expires_at".to_string(), AttributeValue::N(state.expires_at.timestamp().to_string()));
         
         if let Some(redirect_uri) = &state.redirect_uri {
             item.insert("redirect_uri".to_string(), AttributeValue::S(redirect_uri.clone()));
@@ -262,15 +367,16 @@ impl StateStore for DynamoDbStateStore {
     }
 
     async fn find(&self, state: &str) -> Result<Option<OAuthState>> {
-        let response = self.client
-            .get_item()
-            .table_name(&self.table_name)
-            .key("state", AttributeValue::S(state.to_string()))
-            .send()
-            .await
-            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+        // The callback can land in a different region than the install redirect that saved the state
+        let mut item = None;
+        if let Some(read_client) = &self.read_client {
+            item = self.get_item(read_client, state).await?;
+        }
+        if item.is_none() {
+            item = self.get_item(&self.client, state).await?;
+        }
 
-        if let Some(item) = response.item {
+        if let Some(item) = item {
             let state_value = item.get("state")
                 .and_then(|v| v.as_s().ok())
                 .ok_or_else(|| SlackError::Internal("Missing state".to_string()))?
@@ -282,15 +388,18 @@ impl StateStore for DynamoDbStateStore {
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(Utc::now);
 
+            // Older items stored an RFC 3339 string
             let expires_at = item.get("expires_at")
-                .and_then(|v| v.as_s().ok())
-                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                .map(|dt| dt.with_timezone(&Utc))
+                .and_then(|v| match v {
+                    AttributeValue::N(n) => n.parse::<i64>().ok().and_then(|secs| DateTime::from_timestamp(secs, 0)),
+                    AttributeValue::S(s) => DateTime::parse_from_rfc3339(s).ok().map(|dt| dt.with_timezone(&Utc)),
+                    _ => None,
+                })
                 .unwrap_or_else(|| Utc::now() + chrono::Duration::minutes(10));
 
             let redirect_uri = item.get("redirect_uri")
                 .and_then(|v| v.as_s().ok())
-                .map(|s| s.clone());
+                .cloned();
 
             Ok(Some(OAuthState {
                 state: state_value,
@@ -320,4 +429,112 @@ impl StateStore for DynamoDbStateStore {
         // For now, return 0 as this is a basic implementation
         Ok(0)
     }
-}
\ No newline at end of file
+}
+
+const DEFAULT_TABLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone)]
+pub struct BootstrapConfig {
+    pub installations_table: String,
+    pub states_table: String,
+    // Keyed by Slack's event_id with a TTL, for dropping redelivered events
+    pub dedup_table: Option<String>,
+    // Non-empty makes every table a global table replicated to these regions
+    pub replica_regions: Vec<String>,
+    pub timeout: Duration,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            installations_table: "slack_installations".to_string(),
+            states_table: "slack_oauth_states".to_string(),
+            dedup_table: Some("slack_event_dedup".to_string()),
+            replica_regions: Vec::new(),
+            timeout: DEFAULT_TABLE_TIMEOUT,
+        }
+    }
+}
+
+impl BootstrapConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Table names from INSTALLATIONS_TABLE, OAUTH_STATES_TABLE and DEDUP_TABLE, else the defaults.
+    // DYNAMODB_REPLICA_REGIONS is a comma separated list, e.g. "us-west-2,eu-west-1".
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            installations_table: std::env::var("INSTALLATIONS_TABLE").unwrap_or(defaults.installations_table),
+            states_table: std::env::var("OAUTH_STATES_TABLE").unwrap_or(defaults.states_table),
+            dedup_table: std::env::var("DEDUP_TABLE").ok().or(defaults.dedup_table),
+            replica_regions: std::env::var("DYNAMODB_REPLICA_REGIONS")
+                .map(|regions| {
+                    regions
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|region| !region.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            timeout: defaults.timeout,
+        }
+    }
+
+    pub fn with_installations_table<S: Into<String>>(mut self, table_name: S) -> Self {
+        self.installations_table = table_name.into();
+        self
+    }
+
+    pub fn with_states_table<S: Into<String>>(mut self, table_name: S) -> Self {
+        self.states_table = table_name.into();
+        self
+    }
+
+    pub fn with_dedup_table<S: Into<String>>(mut self, table_name: Option<S>) -> Self {
+        self.dedup_table = table_name.map(Into::into);
+        self
+    }
+
+    pub fn with_replica_regions<I, S>(mut self, regions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.replica_regions = regions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn table_specs(&self) -> Vec<TableSpec> {
+        let mut specs = vec![
+            DynamoDbInstallationStore::table_spec(self.installations_table.as_str()),
+            DynamoDbStateStore::table_spec(self.states_table.as_str()),
+        ];
+        if let Some(dedup_table) = &self.dedup_table {
+            specs.push(TableSpec::new(dedup_table.as_str(), "event_id").with_ttl("expires_at"));
+        }
+        specs
+            .into_iter()
+            .map(|spec| spec.with_replicas(self.replica_regions.iter().cloned()))
+            .collect()
+    }
+}
+
+// Creates every table the OAuth stores need (in parallel), waits for them to be ACTIVE and
+// enables TTL where items expire. Safe to run on every deploy.
+pub async fn bootstrap(client: &DynamoDbClient, config: &BootstrapConfig) -> Result<Vec<(String, TableOutcome)>> {
+    let specs = config.table_specs();
+    let outcomes = futures::future::try_join_all(
+        specs.iter().map(|spec| ensure_table(client, spec, config.timeout)),
+    )
+    .await?;
+
+    Ok(specs.into_iter().map(|spec| spec.table_name).zip(outcomes).collect())
+}
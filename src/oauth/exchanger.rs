@@ -0,0 +1,85 @@
+use crate::error::Result;
+use crate::oauth::flow::{OAuthAccessResponse, OAuthExchangeResponse};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::fmt::Debug;
+
+// The calls to slack.com in an OAuth flow. Implementations return Slack's response as is,
+// `ok: false` included; `OAuthFlow` turns that into an error. `testing::FakeTokenExchanger`
+// stands in for Slack in tests.
+#[async_trait]
+pub trait TokenExchanger: Send + Sync + Debug {
+    // oauth.v2.access with the code from the install redirect
+    async fn exchange_code(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+        code: &str,
+        redirect_uri: &str,
+    ) -> Result<OAuthAccessResponse>;
+
+    // oauth.v2.exchange with a classic app's token
+    async fn exchange_token(&self, client_id: &str, client_secret: &str, token: &str) -> Result<OAuthExchangeResponse>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SlackTokenExchanger {
+    http_client: Client,
+}
+
+impl Default for SlackTokenExchanger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlackTokenExchanger {
+    pub fn new() -> Self {
+        Self {
+            http_client: crate::shared::http_client(),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenExchanger for SlackTokenExchanger {
+    async fn exchange_code(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+        code: &str,
+        redirect_uri: &str,
+    ) -> Result<OAuthAccessResponse> {
+        let params = [
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+        ];
+
+        let response = self.http_client
+            .post("https://slack.com/api/oauth.v2.access")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .form(&params)
+            .send()
+            .await?;
+
+        Ok(response.json().await?)
+    }
+
+    async fn exchange_token(&self, client_id: &str, client_secret: &str, token: &str) -> Result<OAuthExchangeResponse> {
+        let params = [
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("token", token),
+        ];
+
+        let response = self.http_client
+            .post("https://slack.com/api/oauth.v2.exchange")
+            .form(&params)
+            .send()
+            .await?;
+
+        Ok(response.json().await?)
+    }
+}
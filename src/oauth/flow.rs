@@ -1,18 +1,27 @@
+use crate::app::AppConfig;
 use crate::error::{Result, SlackError};
-use crate::oauth::{InstallationStore, StateStore, Installation, OAuthState};
-use reqwest::Client;
+use crate::oauth::state_cookie;
+use crate::secret::SecretString;
+use crate::oauth::{
+    IncomingWebhook, Installation, InstallationStore, OAuthSettings, OAuthState, SlackTokenExchanger, StateStore,
+    StateVerification, TokenExchanger,
+};
+use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use url::Url;
 
 pub struct OAuthFlow {
     client_id: String,
-    client_secret: String,
+    client_secret: SecretString,
     redirect_uri: String,
     scopes: Vec<String>,
     user_scopes: Vec<String>,
-    installation_store: Box<dyn InstallationStore>,
-    state_store: Box<dyn StateStore>,
-    http_client: Client,
+    installation_store: Arc<dyn InstallationStore>,
+    state_store: Arc<dyn StateStore>,
+    state_verification: StateVerification,
+    state_cookie: bool,
+    token_exchanger: Arc<dyn TokenExchanger>,
 }
 
 impl OAuthFlow {
@@ -27,26 +36,106 @@ impl OAuthFlow {
     ) -> Self {
         Self {
             client_id,
-            client_secret,
+            client_secret: client_secret.into(),
             redirect_uri,
             scopes,
             user_scopes,
-            installation_store,
-            state_store,
-            http_client: Client::new(),
+            installation_store: installation_store.into(),
+            state_store: state_store.into(),
+            state_verification: StateVerification::Require,
+            // Same default as OAuthSettings: the state is bound to the browser that started the install
+            state_cookie: true,
+            token_exchanger: Arc::new(SlackTokenExchanger::new()),
         }
     }
 
+    pub fn with_state_verification(mut self, state_verification: StateVerification) -> Self {
+        self.state_verification = state_verification;
+        self
+    }
+
+    // On by default; turn off only where the install is started and finished in different browsers
+    pub fn with_state_cookie(mut self, enabled: bool) -> Self {
+        self.state_cookie = enabled;
+        self
+    }
+
+    pub fn with_token_exchanger<E: TokenExchanger + 'static>(mut self, token_exchanger: E) -> Self {
+        self.token_exchanger = Arc::new(token_exchanger);
+        self
+    }
+
+    // Flow for an app built with `oauth_settings`; values missing from the settings fall back
+    // to the app config, so credentials only need to be given once
+    pub fn from_settings(config: &AppConfig, settings: &OAuthSettings) -> Result<Self> {
+        let missing = |name: &str| SlackError::Config(format!("OAuth {} is not configured", name));
+        let client_id = settings.client_id.clone().or_else(|| config.client_id.clone());
+        let client_secret = settings.client_secret.clone().or_else(|| config.client_secret.clone());
+        let redirect_uri = settings.redirect_uri.clone().or_else(|| config.redirect_uri.clone());
+        let scopes = settings.scopes.clone().unwrap_or_else(|| config.scopes.clone());
+        let user_scopes = settings.user_scopes.clone().unwrap_or_else(|| config.user_scopes.clone());
+
+        Ok(Self {
+            client_id: client_id.ok_or_else(|| missing("client_id"))?,
+            client_secret: client_secret.ok_or_else(|| missing("client_secret"))?,
+            redirect_uri: redirect_uri.ok_or_else(|| missing("redirect_uri"))?,
+            scopes,
+            user_scopes,
+            installation_store: settings.installation_store.clone().ok_or_else(|| missing("installation_store"))?,
+            state_store: settings.state_store.clone().ok_or_else(|| missing("state_store"))?,
+            state_verification: settings.state_verification,
+            state_cookie: settings.state_cookie,
+            token_exchanger: settings
+                .token_exchanger
+                .clone()
+                .unwrap_or_else(|| Arc::new(SlackTokenExchanger::new())),
+        })
+    }
+
     pub async fn start(&self) -> Result<String> {
+        self.start_with_state().await.map(|(url, _)| url)
+    }
+
+    // Authorize URL along with the state it carries, for adapters that set the state cookie
+    pub async fn start_with_state(&self) -> Result<(String, String)> {
         let state = OAuthState::new().with_redirect_uri(&self.redirect_uri);
         self.state_store.save(&state).await?;
+        let url = self.authorize_url(Some(&state.state))?;
+        Ok((url, state.state))
+    }
 
+    // `Set-Cookie` value to send with the install redirect, when cookie binding is on
+    pub fn state_cookie(&self, state: &str) -> Result<Option<String>> {
+        if !self.state_cookie {
+            return Ok(None);
+        }
+        state_cookie::set_cookie(self.client_secret.expose(), state).map(Some)
+    }
+
+    // Run on the redirect before `complete`; a missing state is left to the verification policy
+    pub fn verify_state_cookie(&self, cookie_header: Option<&str>, state: &str) -> Result<()> {
+        if !self.state_cookie || state.is_empty() {
+            return Ok(());
+        }
+        state_cookie::verify(self.client_secret.expose(), cookie_header, state)
+    }
+
+    // Authorize URL without a stored state, for links handed out ahead of time such as the
+    // App Directory's direct install URL. Completing it needs `StateVerification::AllowMissing`.
+    pub fn direct_install_url(&self) -> Result<String> {
+        self.authorize_url(None)
+    }
+
+    fn authorize_url(&self, state: Option<&str>) -> Result<String> {
         let mut url = Url::parse("https://slack.com/oauth/v2/authorize")?;
         url.query_pairs_mut()
             .append_pair("client_id", &self.client_id)
             .append_pair("scope", &self.scopes.join(","))
-            .append_pair("redirect_uri", &self.redirect_uri)
-            .append_pair("state", &state.state);
+            .append_pair("redirect_uri", &self.redirect_uri);
+
+        if let Some(state) = state {
+            url.query_pairs_mut().append_pair("state", state);
+        }
 
         if !self.user_scopes.is_empty() {
             url.query_pairs_mut()
@@ -56,52 +145,86 @@ impl OAuthFlow {
         Ok(url.to_string())
     }
 
-    pub async fn complete(&self, code: &str, state: &str) -> Result<Installation> {
-        // Verify state
-        let oauth_state = self.state_store.verify_and_consume(state).await?
-            .ok_or_else(|| SlackError::OAuth("Invalid or expired state".to_string()))?;
+    // `state` is empty when Slack started the flow itself; a state that is present is always
+    // checked, whatever the policy
+    pub async fn complete(&self, code: &str, state: &str) -> Result<InstallOutcome> {
+        if !state.is_empty() {
+            self.state_store.verify_and_consume(state).await?
+                .ok_or_else(|| SlackError::OAuth("Invalid or expired state".to_string()))?;
+        } else if self.state_verification == StateVerification::Require {
+            return Err(SlackError::OAuth("Missing state".to_string()));
+        }
 
-        // Exchange code for tokens
-        let token_response = self.exchange_code(code).await?;
+        let response = self.exchange_code(code).await?;
+        let installation = self.installation_from(&response)?;
+        self.installation_store.save(&installation).await?;
+
+        Ok(InstallOutcome { installation, response })
+    }
 
-        // Create installation
-        let mut installation = Installation::new(token_response.team.id.clone())
-            .with_scopes(self.scopes.clone());
+    // Scopes come from what Slack granted, which can differ from what was asked for; the
+    // configured scopes are only a fallback for responses without a `scope`
+    fn installation_from(&self, response: &OAuthAccessResponse) -> Result<Installation> {
+        // Org-wide installs have no team; the installation is keyed by the enterprise instead
+        let team_id = response
+            .team
+            .as_ref()
+            .map(|team| team.id.clone())
+            .or_else(|| response.enterprise.as_ref().map(|enterprise| enterprise.id.clone()))
+            .ok_or_else(|| SlackError::OAuth("oauth.v2.access returned neither team nor enterprise".to_string()))?;
 
-        if let Some(bot) = token_response.access_token {
-            installation = installation.with_bot_token(bot, token_response.bot_user_id.unwrap_or_default());
+        let scopes = response.scopes();
+        let mut installation = Installation::new(team_id).with_scopes(if scopes.is_empty() {
+            self.scopes.clone()
+        } else {
+            scopes
+        });
+
+        if let Some(bot) = &response.access_token {
+            installation = installation.with_bot_token(bot.expose().to_string(), response.bot_user_id.clone().unwrap_or_default());
+            installation.expires_at = response.expires_in.map(|secs| Utc::now() + Duration::seconds(secs));
         }
 
-        if let Some(user_token) = token_response.authed_user.access_token {
-            installation = installation.with_user_token(user_token, token_response.authed_user.id);
+        if let Some(user_token) = &response.authed_user.access_token {
+            installation = installation
+                .with_user_token(user_token.expose().to_string(), response.authed_user.id.clone())
+                .with_user_scopes(response.authed_user.scopes());
         }
 
-        if let Some(enterprise) = token_response.enterprise {
-            installation = installation.with_enterprise_id(enterprise.id);
+        if let Some(enterprise) = &response.enterprise {
+            installation = installation.with_enterprise_id(enterprise.id.clone());
         }
 
-        // Save installation
-        self.installation_store.save(&installation).await?;
+        if let Some(webhook) = &response.incoming_webhook {
+            installation = installation.with_incoming_webhook(webhook.clone());
+        }
 
         Ok(installation)
     }
 
-    async fn exchange_code(&self, code: &str) -> Result<OAuthAccessResponse> {
-        let params = [
-            ("client_id", self.client_id.as_str()),
-            ("client_secret", self.client_secret.as_str()),
-            ("code", code),
-            ("redirect_uri", self.redirect_uri.as_str()),
-        ];
-
-        let response = self.http_client
-            .post("https://slack.com/api/oauth.v2.access")
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .form(&params)
-            .send()
+    // Trades a classic app's token (xoxb or xoxp) for a granular-scope token via
+    // oauth.v2.exchange; the classic token stops working once this succeeds
+    pub async fn exchange_token(&self, token: &str) -> Result<OAuthExchangeResponse> {
+        let exchange = self
+            .token_exchanger
+            .exchange_token(&self.client_id, self.client_secret.expose(), token)
             .await?;
 
-        let oauth_response: OAuthAccessResponse = response.json().await?;
+        if !exchange.ok {
+            return Err(SlackError::OAuth(
+                exchange.error.unwrap_or_else(|| "Unknown OAuth error".to_string())
+            ));
+        }
+
+        Ok(exchange)
+    }
+
+    // oauth.v2.access without storing anything; `complete` is the usual entry point
+    pub async fn exchange_code(&self, code: &str) -> Result<OAuthAccessResponse> {
+        let oauth_response = self
+            .token_exchanger
+            .exchange_code(&self.client_id, self.client_secret.expose(), code, &self.redirect_uri)
+            .await?;
 
         if !oauth_response.ok {
             return Err(SlackError::OAuth(
@@ -113,36 +236,95 @@ impl OAuthFlow {
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct OAuthAccessResponse {
-    ok: bool,
-    error: Option<String>,
-    access_token: Option<String>,
-    token_type: Option<String>,
-    scope: Option<String>,
-    bot_user_id: Option<String>,
-    app_id: String,
-    team: Team,
-    enterprise: Option<Enterprise>,
-    authed_user: AuthedUser,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthExchangeResponse {
+    pub ok: bool,
+    pub error: Option<String>,
+    pub access_token: Option<SecretString>,
+    // "bot" or "user", matching the kind of token that was exchanged
+    pub token_type: Option<String>,
+    pub scope: Option<String>,
+    pub bot_user_id: Option<String>,
+    pub app_id: Option<String>,
+    // Only set when token rotation is enabled for the app
+    pub expires_in: Option<i64>,
+}
+
+impl OAuthExchangeResponse {
+    // Granted scopes
+    pub fn scopes(&self) -> Vec<String> {
+        split_scopes(self.scope.as_deref())
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct Team {
-    id: String,
-    name: String,
+// What a completed install produced: the installation as stored and Slack's whole response,
+// for fields the installation doesn't keep (app_id, token_type, refresh_token, ...)
+#[derive(Debug, Clone)]
+pub struct InstallOutcome {
+    pub installation: Installation,
+    pub response: OAuthAccessResponse,
 }
 
-#[derive(Debug, Deserialize)]
-struct Enterprise {
-    id: String,
-    name: String,
+// oauth.v2.access as Slack sends it; the top-level token is the bot's
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthAccessResponse {
+    pub ok: bool,
+    pub error: Option<String>,
+    pub access_token: Option<SecretString>,
+    pub token_type: Option<String>,
+    pub scope: Option<String>,
+    pub bot_user_id: Option<String>,
+    pub app_id: Option<String>,
+    // None for org-wide installs
+    pub team: Option<OAuthTeam>,
+    pub enterprise: Option<OAuthEnterprise>,
+    #[serde(default)]
+    pub is_enterprise_install: bool,
+    #[serde(default)]
+    pub authed_user: OAuthAuthedUser,
+    pub incoming_webhook: Option<IncomingWebhook>,
+    // Only set when token rotation is enabled for the app
+    pub expires_in: Option<i64>,
+    pub refresh_token: Option<SecretString>,
 }
 
-#[derive(Debug, Deserialize)]
-struct AuthedUser {
-    id: String,
-    scope: Option<String>,
-    access_token: Option<String>,
-    token_type: Option<String>,
-}
\ No newline at end of file
+impl OAuthAccessResponse {
+    // Bot scopes as granted
+    pub fn scopes(&self) -> Vec<String> {
+        split_scopes(self.scope.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthTeam {
+    pub id: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthEnterprise {
+    pub id: String,
+    pub name: Option<String>,
+}
+
+// The installing user, with a token of their own when user scopes were requested
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OAuthAuthedUser {
+    pub id: String,
+    pub scope: Option<String>,
+    pub access_token: Option<SecretString>,
+    pub token_type: Option<String>,
+}
+
+impl OAuthAuthedUser {
+    pub fn scopes(&self) -> Vec<String> {
+        split_scopes(self.scope.as_deref())
+    }
+}
+
+// Slack sends scopes comma separated
+fn split_scopes(scope: Option<&str>) -> Vec<String> {
+    scope
+        .map(|scope| scope.split(',').filter(|s| !s.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,52 @@
+use crate::client::{MigrationExchangeRequest, SlackApi};
+use crate::error::Result;
+use crate::oauth::{Installation, InstallationStore};
+use tracing::{info, warn};
+
+// When a workspace migrates into Enterprise Grid, requests start carrying an enterprise_id and
+// the workspace's users get global W-prefixed IDs. This re-keys the stored installation under
+// the enterprise and swaps its user IDs via migration.exchange. Call it from a
+// `grid_migration_finished` listener (or a one-off job) with the installation's bot token.
+// Returns None when there was no installation stored under the old key.
+pub async fn remap_installation(
+    api: &dyn SlackApi,
+    store: &dyn InstallationStore,
+    team_id: &str,
+    previous_enterprise_id: Option<&str>,
+) -> Result<Option<Installation>> {
+    let Some(installation) = store.find_by_team(team_id, previous_enterprise_id).await? else {
+        return Ok(None);
+    };
+
+    let user_ids: Vec<String> = installation
+        .user_id
+        .iter()
+        .chain(installation.bot_user_id.iter())
+        .cloned()
+        .collect();
+    let exchange = api
+        .exchange_user_ids(&MigrationExchangeRequest::new(user_ids).with_team_id(team_id))
+        .await?;
+    if !exchange.invalid_user_ids.is_empty() {
+        warn!("migration.exchange could not map {:?} for team {}", exchange.invalid_user_ids, team_id);
+    }
+
+    let remapped = Installation {
+        enterprise_id: exchange.enterprise_id.clone().or(installation.enterprise_id.clone()),
+        user_id: installation.user_id.as_deref().map(|id| exchange.resolve(id).to_string()),
+        bot_user_id: installation.bot_user_id.as_deref().map(|id| exchange.resolve(id).to_string()),
+        ..installation.clone()
+    };
+
+    // Save before deleting so a failure in between leaves the installation findable
+    store.save(&remapped).await?;
+    if remapped.enterprise_id != installation.enterprise_id {
+        store.delete(team_id, installation.enterprise_id.as_deref()).await?;
+    }
+
+    info!(
+        "Remapped installation for team {} into enterprise {:?}",
+        team_id, remapped.enterprise_id
+    );
+    Ok(Some(remapped))
+}
@@ -1,21 +1,34 @@
-use crate::error::Result;
+use crate::error::{Result, SlackError};
 use async_trait::async_trait;
+use crate::infrastructure::Resource;
+use crate::secret::SecretString;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+// Granted with the `incoming-webhook` scope: a URL posting into the channel the installer picked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncomingWebhook {
+    pub url: String,
+    pub channel: Option<String>,
+    pub channel_id: Option<String>,
+    pub configuration_url: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Installation {
     pub team_id: String,
     pub enterprise_id: Option<String>,
-    pub bot_token: Option<String>,
+    pub bot_token: Option<SecretString>,
     pub bot_user_id: Option<String>,
-    pub user_token: Option<String>,
+    pub user_token: Option<SecretString>,
     pub user_id: Option<String>,
     pub scopes: Vec<String>,
     pub user_scopes: Vec<String>,
     pub installed_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incoming_webhook: Option<IncomingWebhook>,
 }
 
 impl Installation {
@@ -31,17 +44,18 @@ impl Installation {
             user_scopes: Vec::new(),
             installed_at: Utc::now(),
             expires_at: None,
+            incoming_webhook: None,
         }
     }
 
     pub fn with_bot_token<S: Into<String>>(mut self, token: S, user_id: S) -> Self {
-        self.bot_token = Some(token.into());
+        self.bot_token = Some(SecretString::new(token));
         self.bot_user_id = Some(user_id.into());
         self
     }
 
     pub fn with_user_token<S: Into<String>>(mut self, token: S, user_id: S) -> Self {
-        self.user_token = Some(token.into());
+        self.user_token = Some(SecretString::new(token));
         self.user_id = Some(user_id.into());
         self
     }
@@ -69,6 +83,11 @@ impl Installation {
         self
     }
 
+    pub fn with_incoming_webhook(mut self, webhook: IncomingWebhook) -> Self {
+        self.incoming_webhook = Some(webhook);
+        self
+    }
+
     pub fn is_expired(&self) -> bool {
         if let Some(expires_at) = self.expires_at {
             expires_at < Utc::now()
@@ -78,24 +97,51 @@ impl Installation {
     }
 }
 
+// One page of `InstallationStore::list_page`; `next_cursor` is None on the last page
+#[derive(Debug, Clone, Default)]
+pub struct InstallationPage {
+    pub installations: Vec<Installation>,
+    pub next_cursor: Option<String>,
+}
+
 #[async_trait]
 pub trait InstallationStore: Send + Sync + Debug {
     async fn save(&self, installation: &Installation) -> Result<()>;
+
+    // Saves several installations, for migrations; stores with a bulk write override it. An
+    // error may leave some of them saved, so callers re-run rather than roll back.
+    async fn save_many(&self, installations: &[Installation]) -> Result<()> {
+        for installation in installations {
+            self.save(installation).await?;
+        }
+        Ok(())
+    }
     
     async fn find_by_team(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<Option<Installation>>;
     
     async fn delete(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<()>;
+
+    // Tables, buckets etc. the store expects to exist, for `App::describe_infrastructure`
+    fn required_resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+
+    // Enumerates every installation, for migrations between backends. Cursors are opaque to callers.
+    async fn list_page(&self, cursor: Option<&str>, limit: usize) -> Result<InstallationPage> {
+        let _ = (cursor, limit);
+        Err(SlackError::Config(format!("{:?} does not support listing installations", self)))
+    }
     
     async fn find_bot_token(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<Option<String>> {
         let installation = self.find_by_team(team_id, enterprise_id).await?;
-        Ok(installation.and_then(|i| i.bot_token))
+        Ok(installation.and_then(|i| i.bot_token).map(SecretString::into_exposed))
     }
     
     async fn find_user_token(&self, team_id: &str, user_id: &str, enterprise_id: Option<&str>) -> Result<Option<String>> {
         let installation = self.find_by_team(team_id, enterprise_id).await?;
         Ok(installation.and_then(|i| {
             if i.user_id.as_deref() == Some(user_id) {
-                i.user_token
+                i.user_token.map(SecretString::into_exposed)
             } else {
                 None
             }
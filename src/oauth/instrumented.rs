@@ -0,0 +1,153 @@
+use crate::error::Result;
+use crate::infrastructure::Resource;
+use crate::metrics::Metrics;
+use crate::oauth::{Installation, InstallationPage, InstallationStore, OAuthState, StateStore};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Instant;
+
+// Wraps an InstallationStore or StateStore and records per-operation metrics, tagged with
+// `Store` and `Operation`: StoreCalls, StoreLatency (ms), StoreErrors, and StoreHits/StoreMisses
+// for lookups. Token lookups sit on the hot path of every request, so this is the first place
+// to look when requests are slow.
+#[derive(Debug)]
+pub struct InstrumentedStore<S> {
+    inner: S,
+    metrics: Arc<dyn Metrics>,
+}
+
+impl<S> InstrumentedStore<S> {
+    pub fn new(inner: S, metrics: Arc<dyn Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn record<T>(&self, store: &str, operation: &str, started: Instant, result: &Result<T>) {
+        let tags = [("Store", store), ("Operation", operation)];
+        self.metrics.counter("StoreCalls", 1, &tags);
+        self.metrics.histogram("StoreLatency", started.elapsed().as_secs_f64() * 1000.0, &tags);
+        if result.is_err() {
+            self.metrics.counter("StoreErrors", 1, &tags);
+        }
+    }
+
+    fn record_lookup<T>(&self, store: &str, operation: &str, started: Instant, result: &Result<Option<T>>) {
+        self.record(store, operation, started, result);
+
+        let tags = [("Store", store), ("Operation", operation)];
+        match result {
+            Ok(Some(_)) => self.metrics.counter("StoreHits", 1, &tags),
+            Ok(None) => self.metrics.counter("StoreMisses", 1, &tags),
+            Err(_) => {}
+        }
+    }
+}
+
+const INSTALLATION_STORE: &str = "Installation";
+const STATE_STORE: &str = "State";
+
+#[async_trait]
+impl<S: InstallationStore> InstallationStore for InstrumentedStore<S> {
+    async fn save(&self, installation: &Installation) -> Result<()> {
+        let started = Instant::now();
+        let result = self.inner.save(installation).await;
+        self.record(INSTALLATION_STORE, "save", started, &result);
+        result
+    }
+
+    async fn save_many(&self, installations: &[Installation]) -> Result<()> {
+        let started = Instant::now();
+        let result = self.inner.save_many(installations).await;
+        self.record(INSTALLATION_STORE, "save_many", started, &result);
+        result
+    }
+
+    async fn find_by_team(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<Option<Installation>> {
+        let started = Instant::now();
+        let result = self.inner.find_by_team(team_id, enterprise_id).await;
+        self.record_lookup(INSTALLATION_STORE, "find_by_team", started, &result);
+        result
+    }
+
+    async fn delete(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<()> {
+        let started = Instant::now();
+        let result = self.inner.delete(team_id, enterprise_id).await;
+        self.record(INSTALLATION_STORE, "delete", started, &result);
+        result
+    }
+
+    fn required_resources(&self) -> Vec<Resource> {
+        self.inner.required_resources()
+    }
+
+    async fn list_page(&self, cursor: Option<&str>, limit: usize) -> Result<InstallationPage> {
+        let started = Instant::now();
+        let result = self.inner.list_page(cursor, limit).await;
+        self.record(INSTALLATION_STORE, "list_page", started, &result);
+        result
+    }
+
+    // Forwarded rather than left to the defaults, so stores with cheaper token lookups keep them
+    async fn find_bot_token(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<Option<String>> {
+        let started = Instant::now();
+        let result = self.inner.find_bot_token(team_id, enterprise_id).await;
+        self.record_lookup(INSTALLATION_STORE, "find_bot_token", started, &result);
+        result
+    }
+
+    async fn find_user_token(&self, team_id: &str, user_id: &str, enterprise_id: Option<&str>) -> Result<Option<String>> {
+        let started = Instant::now();
+        let result = self.inner.find_user_token(team_id, user_id, enterprise_id).await;
+        self.record_lookup(INSTALLATION_STORE, "find_user_token", started, &result);
+        result
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> StateStore for InstrumentedStore<S> {
+    async fn save(&self, state: &OAuthState) -> Result<()> {
+        let started = Instant::now();
+        let result = self.inner.save(state).await;
+        self.record(STATE_STORE, "save", started, &result);
+        result
+    }
+
+    async fn find(&self, state: &str) -> Result<Option<OAuthState>> {
+        let started = Instant::now();
+        let result = self.inner.find(state).await;
+        self.record_lookup(STATE_STORE, "find", started, &result);
+        result
+    }
+
+    async fn delete(&self, state: &str) -> Result<()> {
+        let started = Instant::now();
+        let result = self.inner.delete(state).await;
+        self.record(STATE_STORE, "delete", started, &result);
+        result
+    }
+
+    fn required_resources(&self) -> Vec<Resource> {
+        self.inner.required_resources()
+    }
+
+    async fn cleanup_expired(&self) -> Result<u64> {
+        let started = Instant::now();
+        let result = self.inner.cleanup_expired().await;
+        self.record(STATE_STORE, "cleanup_expired", started, &result);
+        result
+    }
+
+    async fn verify_and_consume(&self, state: &str) -> Result<Option<OAuthState>> {
+        let started = Instant::now();
+        let result = self.inner.verify_and_consume(state).await;
+        self.record_lookup(STATE_STORE, "verify_and_consume", started, &result);
+        result
+    }
+}
@@ -0,0 +1,185 @@
+use crate::error::{Result, SlackError};
+use crate::oauth::{Installation, InstallationStore};
+use std::fmt;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+pub type ProgressCallback = Arc<dyn Fn(&MigrationProgress) + Send + Sync>;
+
+#[derive(Clone)]
+pub struct MigrationOptions {
+    pub dry_run: bool,
+    // Installations already in the target are skipped unless this is set
+    pub overwrite: bool,
+    pub page_size: usize,
+    pub on_progress: Option<ProgressCallback>,
+}
+
+impl Default for MigrationOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            overwrite: false,
+            page_size: 100,
+            on_progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for MigrationOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MigrationOptions")
+            .field("dry_run", &self.dry_run)
+            .field("overwrite", &self.overwrite)
+            .field("page_size", &self.page_size)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl MigrationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    pub fn with_progress<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&MigrationProgress) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(f));
+        self
+    }
+}
+
+// Reported after every page; counts are cumulative
+#[derive(Debug, Clone, Default)]
+pub struct MigrationProgress {
+    pub pages: usize,
+    pub scanned: usize,
+    pub migrated: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    pub dry_run: bool,
+    pub scanned: usize,
+    // In a dry run, the installations that would have been written
+    pub migrated: usize,
+    pub skipped: usize,
+    // (team_id, enterprise_id, error) for installations that could not be written
+    pub failures: Vec<(String, Option<String>, String)>,
+}
+
+impl MigrationReport {
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+// Copies every installation from one backend to another, a page at a time, writing each page
+// with `save_many`. Failing to read the source aborts the migration; failing to write is
+// recorded in the report and the migration carries on, so it can simply be re-run.
+pub async fn migrate_installations(
+    from: &dyn InstallationStore,
+    to: &dyn InstallationStore,
+    options: &MigrationOptions,
+) -> Result<MigrationReport> {
+    let mut report = MigrationReport {
+        dry_run: options.dry_run,
+        ..MigrationReport::default()
+    };
+    let mut progress = MigrationProgress::default();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let page = from.list_page(cursor.as_deref(), options.page_size).await?;
+        progress.pages += 1;
+
+        let mut to_write = Vec::new();
+        for installation in page.installations {
+            report.scanned += 1;
+
+            let enterprise_id = installation.enterprise_id.as_deref();
+            let exists = if options.overwrite {
+                Ok(false)
+            } else {
+                to.find_by_team(&installation.team_id, enterprise_id)
+                    .await
+                    .map(|existing| existing.is_some())
+            };
+
+            match exists {
+                Ok(true) => report.skipped += 1,
+                Ok(false) if options.dry_run => report.migrated += 1,
+                Ok(false) => to_write.push(installation),
+                Err(e) => record_failure(&mut report, &installation, &e),
+            }
+        }
+
+        // A page is written in one go; if that fails, every installation in it is reported,
+        // since some may or may not have landed
+        if !to_write.is_empty() {
+            match to.save_many(&to_write).await {
+                Ok(()) => report.migrated += to_write.len(),
+                Err(e) => {
+                    for installation in &to_write {
+                        record_failure(&mut report, installation, &e);
+                    }
+                }
+            }
+        }
+
+        progress.scanned = report.scanned;
+        progress.migrated = report.migrated;
+        progress.skipped = report.skipped;
+        progress.failed = report.failures.len();
+        if let Some(on_progress) = &options.on_progress {
+            on_progress(&progress);
+        }
+
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    info!(
+        "Installation migration finished{}: {} scanned, {} migrated, {} skipped, {} failed",
+        if options.dry_run { " (dry run)" } else { "" },
+        report.scanned,
+        report.migrated,
+        report.skipped,
+        report.failures.len()
+    );
+
+    Ok(report)
+}
+
+fn record_failure(report: &mut MigrationReport, installation: &Installation, error: &SlackError) {
+    warn!("Failed to migrate installation for team {}: {}", installation.team_id, error);
+    report
+        .failures
+        .push((installation.team_id.clone(), installation.enterprise_id.clone(), error.to_string()));
+}
+
+// Classic apps asked for the umbrella `bot` scope, which granular-scope apps cannot request
+pub fn is_classic_installation(installation: &Installation) -> bool {
+    installation.scopes.iter().any(|scope| scope == "bot")
+}
@@ -1,24 +1,65 @@
+#[cfg(feature = "oauth")]
+pub mod exchanger;
+#[cfg(feature = "oauth")]
 pub mod flow;
+pub mod grid_migration;
+pub mod instrumented;
+pub mod migrate;
+pub mod state_cookie;
 pub mod installation_store;
 pub mod state_store;
-
 #[cfg(feature = "oauth")]
+pub mod upgrade;
+
+#[cfg(all(feature = "oauth", feature = "dynamodb"))]
 pub mod dynamodb_store;
 
-pub use installation_store::{InstallationStore, Installation};
+pub use grid_migration::remap_installation;
+#[cfg(feature = "oauth")]
+pub use exchanger::{SlackTokenExchanger, TokenExchanger};
+#[cfg(feature = "oauth")]
+pub use flow::{InstallOutcome, OAuthAccessResponse, OAuthAuthedUser};
+pub use installation_store::{IncomingWebhook, InstallationStore, Installation, InstallationPage};
+pub use instrumented::InstrumentedStore;
+pub use migrate::{is_classic_installation, migrate_installations, MigrationOptions, MigrationProgress, MigrationReport};
 pub use state_store::{StateStore, OAuthState};
+#[cfg(feature = "oauth")]
+pub use upgrade::upgrade_classic_installations;
 
-use crate::error::Result;
+use crate::secret::SecretString;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct OAuthSettings {
     pub client_id: Option<String>,
-    pub client_secret: Option<String>,
+    pub client_secret: Option<SecretString>,
     pub redirect_uri: Option<String>,
-    pub scopes: Vec<String>,
-    pub user_scopes: Vec<String>,
-    pub installation_store: Option<Box<dyn InstallationStore>>,
-    pub state_store: Option<Box<dyn StateStore>>,
+    // Unset scopes fall back to the app config's, like the credentials above
+    pub scopes: Option<Vec<String>>,
+    pub user_scopes: Option<Vec<String>>,
+    pub installation_store: Option<Arc<dyn InstallationStore>>,
+    pub state_store: Option<Arc<dyn StateStore>>,
+    pub state_verification: StateVerification,
+    // Also bind the state to the installing browser with a signed cookie
+    pub state_cookie: bool,
+    // Defaults to calling slack.com; tests swap in `testing::FakeTokenExchanger`
+    #[cfg(feature = "oauth")]
+    pub token_exchanger: Option<Arc<dyn TokenExchanger>>,
+}
+
+// Whether an OAuth redirect must carry a state this app issued. Installs started from Slack's
+// side (App Directory direct installs, org-wide installs by an admin) arrive without one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StateVerification {
+    #[default]
+    Require,
+    AllowMissing,
+}
+
+impl Default for OAuthSettings {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl OAuthSettings {
@@ -27,10 +68,14 @@ impl OAuthSettings {
             client_id: None,
             client_secret: None,
             redirect_uri: None,
-            scopes: vec!["chat:write".to_string()],
-            user_scopes: vec![],
+            scopes: None,
+            user_scopes: None,
             installation_store: None,
             state_store: None,
+            state_verification: StateVerification::Require,
+            state_cookie: true,
+            #[cfg(feature = "oauth")]
+            token_exchanger: None,
         }
     }
 
@@ -39,7 +84,7 @@ impl OAuthSettings {
         self
     }
 
-    pub fn client_secret<S: Into<String>>(mut self, client_secret: S) -> Self {
+    pub fn client_secret<S: Into<SecretString>>(mut self, client_secret: S) -> Self {
         self.client_secret = Some(client_secret.into());
         self
     }
@@ -54,7 +99,7 @@ impl OAuthSettings {
         I: IntoIterator,
         I::Item: Into<String>,
     {
-        self.scopes = scopes.into_iter().map(|s| s.into()).collect();
+        self.scopes = Some(scopes.into_iter().map(|s| s.into()).collect());
         self
     }
 
@@ -63,17 +108,35 @@ impl OAuthSettings {
         I: IntoIterator,
         I::Item: Into<String>,
     {
-        self.user_scopes = scopes.into_iter().map(|s| s.into()).collect();
+        self.user_scopes = Some(scopes.into_iter().map(|s| s.into()).collect());
         self
     }
 
     pub fn installation_store<S: InstallationStore + 'static>(mut self, store: S) -> Self {
-        self.installation_store = Some(Box::new(store));
+        self.installation_store = Some(Arc::new(store));
         self
     }
 
     pub fn state_store<S: StateStore + 'static>(mut self, store: S) -> Self {
-        self.state_store = Some(Box::new(store));
+        self.state_store = Some(Arc::new(store));
+        self
+    }
+
+    pub fn state_verification(mut self, state_verification: StateVerification) -> Self {
+        self.state_verification = state_verification;
         self
     }
-}
\ No newline at end of file
+
+    // Off only for installs that cannot keep cookies between the install link and the
+    // redirect, e.g. when the two are served from different domains
+    pub fn state_cookie(mut self, enabled: bool) -> Self {
+        self.state_cookie = enabled;
+        self
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn token_exchanger<E: TokenExchanger + 'static>(mut self, token_exchanger: E) -> Self {
+        self.token_exchanger = Some(Arc::new(token_exchanger));
+        self
+    }
+}
@@ -0,0 +1,64 @@
+// Binds an OAuth state to the browser that started the install. The state store proves the
+// state was issued by this app; the cookie proves the redirect lands in the same browser, so
+// a victim cannot be tricked into completing an install started by someone else.
+use crate::error::{Result, SlackError};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const STATE_COOKIE_NAME: &str = "slack-app-oauth-state";
+
+// Matches the lifetime of a stored `OAuthState`
+const STATE_COOKIE_MAX_AGE_SECS: u64 = 600;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// `Set-Cookie` value for the install redirect. SameSite=Lax still sends it on the top-level
+// GET back from slack.com.
+pub fn set_cookie(secret: &str, state: &str) -> Result<String> {
+    Ok(format!(
+        "{}={}.{}; Max-Age={}; Path=/; HttpOnly; Secure; SameSite=Lax",
+        STATE_COOKIE_NAME,
+        state,
+        sign(secret, state)?,
+        STATE_COOKIE_MAX_AGE_SECS
+    ))
+}
+
+// `Set-Cookie` value that removes the cookie once the callback has been handled
+pub fn clear_cookie() -> String {
+    format!(
+        "{}=; Max-Age=0; Path=/; HttpOnly; Secure; SameSite=Lax",
+        STATE_COOKIE_NAME
+    )
+}
+
+// Checks the request's `Cookie` header holds a cookie signed for exactly this state
+pub fn verify(secret: &str, cookie_header: Option<&str>, state: &str) -> Result<()> {
+    let invalid = || SlackError::OAuth("OAuth state cookie is missing or does not match".to_string());
+
+    let value = cookie_header
+        .into_iter()
+        .flat_map(|header| header.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == STATE_COOKIE_NAME)
+        .map(|(_, value)| value)
+        .ok_or_else(invalid)?;
+    let (cookie_state, signature) = value.rsplit_once('.').ok_or_else(invalid)?;
+    if cookie_state != state {
+        return Err(invalid());
+    }
+
+    let signature = hex::decode(signature).map_err(|_| invalid())?;
+    mac(secret, state)?.verify_slice(&signature).map_err(|_| invalid())
+}
+
+fn sign(secret: &str, state: &str) -> Result<String> {
+    Ok(hex::encode(mac(secret, state)?.finalize().into_bytes()))
+}
+
+fn mac(secret: &str, state: &str) -> Result<HmacSha256> {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| SlackError::Config("Invalid OAuth state cookie secret".to_string()))?;
+    mac.update(state.as_bytes());
+    Ok(mac)
+}
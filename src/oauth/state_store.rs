@@ -1,8 +1,10 @@
 use crate::error::Result;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc, Duration};
+use crate::infrastructure::Resource;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
+#[cfg(feature = "uuid")]
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,11 +15,20 @@ pub struct OAuthState {
     pub expires_at: DateTime<Utc>,
 }
 
+// New states need the `uuid` feature: they must be unguessable
+#[cfg(feature = "uuid")]
+impl Default for OAuthState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OAuthState {
+    #[cfg(feature = "uuid")]
     pub fn new() -> Self {
         let state = Uuid::new_v4().to_string();
         let now = Utc::now();
-        let expires_at = now + Duration::minutes(10); // 10 minute expiration
+        let expires_at = now + chrono::Duration::minutes(10); // 10 minute expiration
         
         Self {
             state,
@@ -48,6 +59,10 @@ pub trait StateStore: Send + Sync + Debug {
     async fn find(&self, state: &str) -> Result<Option<OAuthState>>;
     
     async fn delete(&self, state: &str) -> Result<()>;
+
+    fn required_resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
     
     async fn cleanup_expired(&self) -> Result<u64> {
         // Default implementation - stores can override for efficiency
@@ -0,0 +1,111 @@
+// Classic-to-granular token upgrades; these talk to Slack, so they need the `oauth` feature
+use crate::error::{Result, SlackError};
+use crate::oauth::flow::{OAuthExchangeResponse, OAuthFlow};
+use crate::oauth::migrate::{is_classic_installation, MigrationOptions, MigrationProgress, MigrationReport};
+use crate::oauth::{Installation, InstallationStore};
+use crate::secret::SecretString;
+use chrono::{Duration, Utc};
+use tracing::{info, warn};
+
+// Upgrades every classic-app installation in a store to granular-scope tokens with
+// oauth.v2.exchange, in place. Installations that are not classic count as skipped. The
+// classic token is revoked as soon as Slack exchanges it, so each upgraded installation is
+// saved straight away; a failure is recorded in the report and the upgrade carries on.
+pub async fn upgrade_classic_installations(
+    flow: &OAuthFlow,
+    store: &dyn InstallationStore,
+    options: &MigrationOptions,
+) -> Result<MigrationReport> {
+    let mut report = MigrationReport {
+        dry_run: options.dry_run,
+        ..MigrationReport::default()
+    };
+    let mut progress = MigrationProgress::default();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let page = store.list_page(cursor.as_deref(), options.page_size).await?;
+        progress.pages += 1;
+
+        for installation in page.installations {
+            report.scanned += 1;
+
+            if !is_classic_installation(&installation) {
+                report.skipped += 1;
+                continue;
+            }
+            if options.dry_run {
+                report.migrated += 1;
+                continue;
+            }
+
+            match upgrade_installation(flow, store, &installation).await {
+                Ok(()) => report.migrated += 1,
+                Err(e) => {
+                    warn!("Failed to upgrade installation for team {}: {}", installation.team_id, e);
+                    report
+                        .failures
+                        .push((installation.team_id.clone(), installation.enterprise_id.clone(), e.to_string()));
+                }
+            }
+        }
+
+        progress.scanned = report.scanned;
+        progress.migrated = report.migrated;
+        progress.skipped = report.skipped;
+        progress.failed = report.failures.len();
+        if let Some(on_progress) = &options.on_progress {
+            on_progress(&progress);
+        }
+
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    info!(
+        "Classic token upgrade finished{}: {} scanned, {} upgraded, {} skipped, {} failed",
+        if options.dry_run { " (dry run)" } else { "" },
+        report.scanned,
+        report.migrated,
+        report.skipped,
+        report.failures.len()
+    );
+
+    Ok(report)
+}
+
+async fn upgrade_installation(
+    flow: &OAuthFlow,
+    store: &dyn InstallationStore,
+    installation: &Installation,
+) -> Result<()> {
+    let mut upgraded = installation.clone();
+
+    if let Some(bot_token) = &installation.bot_token {
+        let exchange = flow.exchange_token(bot_token.expose()).await?;
+        upgraded.bot_token = Some(exchanged_token(&exchange)?);
+        upgraded.bot_user_id = exchange.bot_user_id.clone().or(upgraded.bot_user_id);
+        upgraded.scopes = exchange.scopes();
+        upgraded.expires_at = exchange.expires_in.map(|secs| Utc::now() + Duration::seconds(secs));
+        // Keep the new bot token even if the user token below fails to exchange
+        store.save(&upgraded).await?;
+    }
+
+    if let Some(user_token) = &installation.user_token {
+        let exchange = flow.exchange_token(user_token.expose()).await?;
+        upgraded.user_token = Some(exchanged_token(&exchange)?);
+        upgraded.user_scopes = exchange.scopes();
+        store.save(&upgraded).await?;
+    }
+
+    Ok(())
+}
+
+fn exchanged_token(exchange: &OAuthExchangeResponse) -> Result<SecretString> {
+    exchange
+        .access_token
+        .clone()
+        .ok_or_else(|| SlackError::OAuth("oauth.v2.exchange returned no access_token".to_string()))
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// The parts of API Gateway's `requestContext` worth exposing to listeners. Slack never sees
+// any of it; it describes how the request reached the function.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GatewayContext {
+    pub request_id: Option<String>,
+    pub stage: Option<String>,
+    pub source_ip: Option<String>,
+    pub domain_name: Option<String>,
+    // The path API Gateway delivered, before any configured base path was stripped
+    pub raw_path: Option<String>,
+    #[serde(default)]
+    pub authorizer: Value,
+}
+
+impl GatewayContext {
+    // Reads the REST API (`requestContext`) and HTTP API (`requestContext.http`) shapes
+    pub fn from_request_context(request_context: &Value) -> Self {
+        let string = |value: Option<&Value>| value.and_then(|v| v.as_str()).map(|v| v.to_string());
+
+        Self {
+            request_id: string(request_context.get("requestId")),
+            stage: string(request_context.get("stage")),
+            source_ip: string(
+                request_context
+                    .pointer("/identity/sourceIp")
+                    .or_else(|| request_context.pointer("/http/sourceIp")),
+            ),
+            domain_name: string(request_context.get("domainName")),
+            raw_path: None,
+            authorizer: request_context.get("authorizer").cloned().unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn with_raw_path<S: Into<String>>(mut self, raw_path: S) -> Self {
+        self.raw_path = Some(raw_path.into());
+        self
+    }
+
+    // A claim set by a JWT/Cognito authorizer (`authorizer.claims` on REST APIs,
+    // `authorizer.jwt.claims` on HTTP APIs) or a key in a Lambda authorizer's context
+    pub fn authorizer_claim(&self, name: &str) -> Option<&Value> {
+        self.authorizer
+            .get("claims")
+            .and_then(|claims| claims.get(name))
+            .or_else(|| {
+                self.authorizer
+                    .pointer("/jwt/claims")
+                    .and_then(|claims| claims.get(name))
+            })
+            .or_else(|| self.authorizer.pointer("/lambda").and_then(|context| context.get(name)))
+            .or_else(|| self.authorizer.get(name))
+    }
+}
+
+// Drops a configured base path such as "/prod" so "/prod/slack/events" is seen as "/slack/events"
+pub fn strip_base_path(path: &str, base_path: &str) -> String {
+    let base_path = base_path.trim_end_matches('/');
+    if base_path.is_empty() {
+        return path.to_string();
+    }
+
+    match path.strip_prefix(base_path) {
+        Some("") => "/".to_string(),
+        Some(rest) if rest.starts_with('/') => rest.to_string(),
+        _ => path.to_string(),
+    }
+}
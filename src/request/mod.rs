@@ -1,13 +1,49 @@
+pub mod gateway;
+pub mod parse;
+pub mod payloads;
+pub mod signature;
+
+pub use gateway::GatewayContext;
+
+use crate::event_types::EventType;
+use bytes::Bytes;
+use http::HeaderMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+// Set on Slack's redeliveries of an event; Socket Mode envelopes carry the same as
+// `retry_attempt` and `retry_reason`, copied into these headers by the adapter
+pub const RETRY_NUM_HEADER: &str = "x-slack-retry-num";
+pub const RETRY_REASON_HEADER: &str = "x-slack-retry-reason";
+
+// Retry reasons for which the first delivery reached the app and may still be running or
+// already be done; other retries redeliver a request that failed or never arrived
+const DUPLICATE_RETRY_REASONS: &[&str] = &["http_timeout", "timeout"];
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SlackRequest {
     pub method: String,
     pub path: String,
-    pub headers: HashMap<String, String>,
+    #[serde(with = "http_serde::header_map")]
+    pub headers: HeaderMap,
     pub query_params: HashMap<String, String>,
+    // Every value of each query parameter; `query_params` only keeps the last one
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub multi_query_params: HashMap<String, Vec<String>>,
     pub body: SlackRequestBody,
+    // Exactly the bytes Slack sent; signatures are computed over this, never over `body`
+    #[serde(skip)]
+    pub raw_body: Bytes,
+    // Set by the API Gateway adapter; None for Socket Mode, SQS and synthetic requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<GatewayContext>,
+    // Serialized with the request so deferred (SQS) processing keeps the same ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    // Set on requests taken off the deferred-request queue, so a listener can tell the
+    // follow-up run from the delivery it deferred
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deferred: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -18,17 +54,66 @@ pub enum SlackRequestBody {
     Interactive(InteractiveRequest),
     OAuth(OAuthRequest),
     Raw(String),
+    Unknown(UnknownRequest),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EventRequest {
+    #[serde(default)]
     pub token: String,
+    #[serde(default)]
     pub team_id: String,
+    #[serde(default)]
     pub api_app_id: String,
+    pub enterprise_id: Option<String>,
+    #[serde(default)]
     pub event: serde_json::Value,
+    #[serde(rename = "type")]
     pub event_type: String,
+    #[serde(default)]
     pub event_time: u64,
     pub challenge: Option<String>,
+    // Only set on app_rate_limited payloads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minute_rate_limited: Option<i64>,
+    // Installations the event is visible to; Slack sends one, for the receiving app
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authorizations: Vec<EventAuthorization>,
+    // Unique per event and kept across Slack's retries; absent on url_verification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    // Opaque identifier shared by every app receiving the event, for apps.event.authorizations.list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_context: Option<String>,
+    // The event happened in a Slack Connect channel shared with another organization
+    #[serde(default)]
+    pub is_ext_shared_channel: bool,
+}
+
+impl EventRequest {
+    // The inner `event.type`, as opposed to the envelope's `type`
+    pub fn inner_type(&self) -> EventType {
+        EventType::from(self.event.get("type").and_then(|v| v.as_str()).unwrap_or_default())
+    }
+
+    // The receiving app's bot user, from the envelope's authorizations
+    pub fn bot_user_id(&self) -> Option<&str> {
+        self.authorizations
+            .iter()
+            .find(|authorization| authorization.is_bot)
+            .map(|authorization| authorization.user_id.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventAuthorization {
+    pub enterprise_id: Option<String>,
+    pub team_id: Option<String>,
+    pub user_id: String,
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub is_enterprise_install: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -44,19 +129,50 @@ pub struct CommandRequest {
     pub text: String,
     pub response_url: String,
     pub trigger_id: String,
+    pub enterprise_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InteractiveRequest {
+    #[serde(rename = "type")]
+    pub payload_type: String,
+    #[serde(default)]
     pub token: String,
+    #[serde(default)]
     pub team: serde_json::Value,
+    #[serde(default)]
     pub user: serde_json::Value,
     pub channel: Option<serde_json::Value>,
     pub message: Option<serde_json::Value>,
+    #[serde(default)]
     pub actions: Vec<serde_json::Value>,
     pub callback_id: Option<String>,
+    #[serde(default)]
     pub trigger_id: String,
+    #[serde(default)]
     pub response_url: String,
+    pub view: Option<serde_json::Value>,
+    pub workflow_step: Option<serde_json::Value>,
+    pub enterprise: Option<serde_json::Value>,
+    // Shortcuts only; message_ts identifies the message a message shortcut was used on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_ts: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_ts: Option<String>,
+}
+
+// A payload that parsed as JSON (or form fields) but not as any known request shape;
+// only produced by lenient parsing
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct UnknownRequest {
+    pub payload: serde_json::Value,
+}
+
+impl UnknownRequest {
+    pub fn payload_type(&self) -> Option<&str> {
+        self.payload.get("type").and_then(|v| v.as_str())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -64,4 +180,170 @@ pub struct OAuthRequest {
     pub code: Option<String>,
     pub state: Option<String>,
     pub error: Option<String>,
-}
\ No newline at end of file
+}
+
+impl SlackRequestBody {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SlackRequestBody::Event(_) => "event",
+            SlackRequestBody::Command(_) => "command",
+            SlackRequestBody::Interactive(_) => "interactive",
+            SlackRequestBody::OAuth(_) => "oauth",
+            SlackRequestBody::Raw(_) => "raw",
+            SlackRequestBody::Unknown(_) => "unknown",
+        }
+    }
+}
+
+impl SlackRequest {
+    // Case-insensitive; None when the header is missing or not valid UTF-8
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    // 1 for the first retry; None for an original delivery
+    pub fn retry_num(&self) -> Option<u32> {
+        self.header(RETRY_NUM_HEADER).and_then(|v| v.parse().ok())
+    }
+
+    pub fn retry_reason(&self) -> Option<&str> {
+        self.header(RETRY_REASON_HEADER)
+    }
+
+    // Listeners skip these unless registered with `receive_retries(true)`
+    pub fn is_duplicate_retry(&self) -> bool {
+        self.retry_num().is_some()
+            && self.retry_reason().is_none_or(|reason| DUPLICATE_RETRY_REASONS.contains(&reason))
+    }
+
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query_params.get(name).map(|v| v.as_str())
+    }
+
+    // All values of a repeated parameter, falling back to the single-value map
+    pub fn query_param_all(&self, name: &str) -> Vec<&str> {
+        match self.multi_query_params.get(name) {
+            Some(values) => values.iter().map(|v| v.as_str()).collect(),
+            None => self.query_param(name).into_iter().collect(),
+        }
+    }
+
+    pub fn team_id(&self) -> Option<&str> {
+        match &self.body {
+            SlackRequestBody::Event(event) => Some(event.team_id.as_str()),
+            SlackRequestBody::Command(command) => Some(command.team_id.as_str()),
+            SlackRequestBody::Interactive(interactive) => interactive.team.get("id").and_then(|v| v.as_str()),
+            SlackRequestBody::Unknown(unknown) => unknown
+                .payload
+                .get("team_id")
+                .or_else(|| unknown.payload.get("team").and_then(|team| team.get("id")))
+                .and_then(|v| v.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn enterprise_id(&self) -> Option<&str> {
+        match &self.body {
+            SlackRequestBody::Event(event) => event.enterprise_id.as_deref(),
+            SlackRequestBody::Command(command) => command.enterprise_id.as_deref().filter(|id| !id.is_empty()),
+            SlackRequestBody::Interactive(interactive) => interactive
+                .enterprise
+                .as_ref()
+                .and_then(|enterprise| enterprise.get("id"))
+                .or_else(|| interactive.team.get("enterprise_id"))
+                .and_then(|v| v.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn channel_id(&self) -> Option<&str> {
+        match &self.body {
+            SlackRequestBody::Event(event) => event
+                .event
+                .get("channel")
+                .and_then(|channel| channel.as_str().or_else(|| channel.get("id").and_then(|v| v.as_str()))),
+            SlackRequestBody::Command(command) => Some(command.channel_id.as_str()),
+            SlackRequestBody::Interactive(interactive) => interactive
+                .channel
+                .as_ref()
+                .and_then(|channel| channel.get("id"))
+                .and_then(|v| v.as_str()),
+            _ => None,
+        }
+    }
+
+    // The acting user's locale when the payload carries it, or the browser's Accept-Language for
+    // OAuth pages. Slash commands never include it; see `Context::resolve_locale`.
+    pub fn locale(&self) -> Option<&str> {
+        match &self.body {
+            SlackRequestBody::Event(event) => event
+                .event
+                .get("locale")
+                .and_then(|v| v.as_str())
+                .or_else(|| event.event.get("user").and_then(|user| user.get("locale")).and_then(|v| v.as_str())),
+            SlackRequestBody::Interactive(interactive) => interactive.user.get("locale").and_then(|v| v.as_str()),
+            SlackRequestBody::OAuth(_) => self
+                .header("accept-language")
+                .and_then(crate::i18n::preferred_locale),
+            _ => None,
+        }
+    }
+
+    // Largest block list carried by the payload (message, view or event)
+    pub fn block_count(&self) -> usize {
+        let count = |value: Option<&serde_json::Value>| {
+            value
+                .and_then(|v| v.get("blocks"))
+                .and_then(|blocks| blocks.as_array())
+                .map(|blocks| blocks.len())
+                .unwrap_or(0)
+        };
+
+        match &self.body {
+            SlackRequestBody::Event(event) => count(Some(&event.event)),
+            SlackRequestBody::Interactive(interactive) => {
+                count(interactive.message.as_ref()).max(count(interactive.view.as_ref()))
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn thread_ts(&self) -> Option<&str> {
+        match &self.body {
+            SlackRequestBody::Event(event) => event.event.get("thread_ts").and_then(|v| v.as_str()),
+            SlackRequestBody::Interactive(interactive) => interactive
+                .message
+                .as_ref()
+                .and_then(|message| message.get("thread_ts"))
+                .and_then(|v| v.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn user_id(&self) -> Option<&str> {
+        match &self.body {
+            SlackRequestBody::Event(event) => event.event.get("user").and_then(|user| {
+                user.as_str().or_else(|| user.get("id").and_then(|v| v.as_str()))
+            }),
+            SlackRequestBody::Command(command) => Some(command.user_id.as_str()),
+            SlackRequestBody::Interactive(interactive) => interactive.user.get("id").and_then(|v| v.as_str()),
+            _ => None,
+        }
+    }
+
+    // The identifier listeners are matched on: event type, command, action_id or callback_id
+    pub fn routing_key(&self) -> Option<&str> {
+        match &self.body {
+            SlackRequestBody::Event(event) => event.event.get("type").and_then(|v| v.as_str()),
+            SlackRequestBody::Command(command) => Some(command.command.as_str()),
+            SlackRequestBody::Interactive(interactive) => interactive
+                .actions
+                .first()
+                .and_then(|action| action.get("action_id"))
+                .or_else(|| interactive.view.as_ref().and_then(|view| view.get("callback_id")))
+                .and_then(|v| v.as_str())
+                .or(interactive.callback_id.as_deref()),
+            _ => None,
+        }
+    }
+}
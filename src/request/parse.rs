@@ -0,0 +1,130 @@
+use crate::error::{Result, SlackError};
+use crate::request::{CommandRequest, EventRequest, InteractiveRequest, OAuthRequest, SlackRequestBody, UnknownRequest};
+use std::borrow::Cow;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use url::form_urlencoded;
+
+impl SlackRequestBody {
+    // Like `parse`, but JSON and form bodies that don't match a known shape come back as
+    // `Unknown` instead of an error or `Raw`, so newer Slack payloads still reach listeners
+    pub fn parse_lenient(raw_body: &[u8], content_type: &str) -> Result<Self> {
+        let error = match Self::parse(raw_body, content_type) {
+            Ok(SlackRequestBody::Raw(raw)) => match unknown(raw_body, content_type) {
+                Some(payload) => return Ok(SlackRequestBody::Unknown(UnknownRequest { payload })),
+                None => return Ok(SlackRequestBody::Raw(raw)),
+            },
+            Ok(body) => return Ok(body),
+            Err(e) => e,
+        };
+
+        unknown(raw_body, content_type)
+            .map(|payload| SlackRequestBody::Unknown(UnknownRequest { payload }))
+            .ok_or(error)
+    }
+
+    // Decodes a body as delivered over HTTP: JSON for the Events API, form-encoded for
+    // commands, interactivity (`payload=`) and OAuth callbacks, anything else kept raw
+    pub fn parse(raw_body: &[u8], content_type: &str) -> Result<Self> {
+        let content_type = content_type.to_lowercase();
+
+        if content_type.contains("application/json") {
+            // Event API request
+            let event_req: EventRequest = serde_json::from_slice(raw_body)?;
+            Ok(SlackRequestBody::Event(event_req))
+        } else if content_type.contains("application/x-www-form-urlencoded") {
+            let body = std::str::from_utf8(raw_body)
+                .map_err(|_| SlackError::InvalidForm("not valid UTF-8".to_string()))?;
+            let mut form_data = parse_form_data(body)?;
+            
+            if let Some(payload) = form_data.get("payload") {
+                // Interactive component request
+                let interactive_req: InteractiveRequest = serde_json::from_str(payload)?;
+                Ok(SlackRequestBody::Interactive(interactive_req))
+            } else if form_data.contains_key("command") {
+                // Slash command request; fields are moved out of the form, only copied
+                // when decoding actually had to allocate
+                let mut field = |name: &str| form_data.remove(name).map(Cow::into_owned);
+                let command_req = CommandRequest {
+                    token: field("token").unwrap_or_default(),
+                    team_id: field("team_id").unwrap_or_default(),
+                    team_domain: field("team_domain").unwrap_or_default(),
+                    channel_id: field("channel_id").unwrap_or_default(),
+                    channel_name: field("channel_name").unwrap_or_default(),
+                    user_id: field("user_id").unwrap_or_default(),
+                    user_name: field("user_name").unwrap_or_default(),
+                    command: field("command").unwrap_or_default(),
+                    text: field("text").unwrap_or_default(),
+                    response_url: field("response_url").unwrap_or_default(),
+                    trigger_id: field("trigger_id").unwrap_or_default(),
+                    enterprise_id: field("enterprise_id"),
+                };
+                Ok(SlackRequestBody::Command(command_req))
+            } else if form_data.contains_key("code") || form_data.contains_key("error") {
+                // OAuth callback
+                let mut field = |name: &str| form_data.remove(name).map(Cow::into_owned);
+                let oauth_req = OAuthRequest {
+                    code: field("code"),
+                    state: field("state"),
+                    error: field("error"),
+                };
+                Ok(SlackRequestBody::OAuth(oauth_req))
+            } else {
+                Ok(SlackRequestBody::Raw(body.to_string()))
+            }
+        } else {
+            Ok(SlackRequestBody::Raw(String::from_utf8_lossy(raw_body).into_owned()))
+        }
+    }
+}
+
+// Slack sends a dozen or so fields; anything far beyond that is not a Slack request
+const MAX_FORM_FIELDS: usize = 100;
+const MAX_FORM_KEY_BYTES: usize = 256;
+// Decoded. The largest value Slack sends is an interactive `payload` with a full modal, a few
+// hundred KB at most
+pub const MAX_FORM_VALUE_BYTES: usize = 512 * 1024;
+
+// Standard application/x-www-form-urlencoded decoding: `+` is a space, `key` and `key=` are
+// empty values, and the first of repeated keys wins. Borrows from `body` wherever a key or
+// value needs no decoding.
+fn parse_form_data(body: &str) -> Result<HashMap<Cow<'_, str>, Cow<'_, str>>> {
+    let mut form_data = HashMap::new();
+
+    for (index, (key, value)) in form_urlencoded::parse(body.as_bytes()).enumerate() {
+        if index >= MAX_FORM_FIELDS {
+            return Err(SlackError::InvalidForm(format!("more than {} fields", MAX_FORM_FIELDS)));
+        }
+        if key.len() > MAX_FORM_KEY_BYTES {
+            return Err(SlackError::InvalidForm(format!("field name longer than {} bytes", MAX_FORM_KEY_BYTES)));
+        }
+        if value.len() > MAX_FORM_VALUE_BYTES {
+            return Err(SlackError::InvalidForm(format!("{} longer than {} bytes", key, MAX_FORM_VALUE_BYTES)));
+        }
+        form_data.entry(key).or_insert(value);
+    }
+
+    Ok(form_data)
+}
+
+// Best-effort structure for bodies the strict parser rejected
+fn unknown(raw_body: &[u8], content_type: &str) -> Option<Value> {
+    let content_type = content_type.to_lowercase();
+
+    if content_type.contains("application/json") {
+        serde_json::from_slice(raw_body).ok()
+    } else if content_type.contains("application/x-www-form-urlencoded") {
+        let form_data = parse_form_data(std::str::from_utf8(raw_body).ok()?).ok()?;
+        match form_data.get("payload") {
+            Some(payload) => serde_json::from_str(payload).ok(),
+            None => Some(Value::Object(
+                form_data
+                    .into_iter()
+                    .map(|(key, value)| (key.into_owned(), Value::String(value.into_owned())))
+                    .collect::<Map<String, Value>>(),
+            )),
+        }
+    } else {
+        None
+    }
+}
@@ -0,0 +1,374 @@
+// Typed views of the parts of a request handlers usually need, for use with the
+// extractors on `Context` (`context.event::<AppMentionEvent>()`, `context.action()`, ...)
+use crate::event_types;
+use crate::listener::FunctionExecutedEvent;
+use crate::request::EventRequest;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+// An inner Events API event with a fixed `event.type`
+pub trait TypedEvent: DeserializeOwned {
+    const EVENT_TYPE: &'static str;
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppMentionEvent {
+    pub user: Option<String>,
+    pub text: String,
+    pub ts: String,
+    pub channel: String,
+    pub thread_ts: Option<String>,
+    pub event_ts: Option<String>,
+    #[serde(default)]
+    pub blocks: Vec<Value>,
+}
+
+impl AppMentionEvent {
+    // The text with the bot's own `<@U…>` mention removed and whitespace trimmed. Without a
+    // bot user ID only a leading mention is removed, which is where Slack clients put it.
+    pub fn text_without_mention(&self, bot_user_id: Option<&str>) -> String {
+        match bot_user_id {
+            Some(bot_user_id) => {
+                let mut text = self.text.clone();
+                for mention in [format!("<@{}>", bot_user_id), format!("<@{}|", bot_user_id)] {
+                    while let Some(start) = text.find(&mention) {
+                        let mut end = text[start..].find('>').map(|end| start + end + 1).unwrap_or(text.len());
+                        // Take one following space along so "hey <@U1> there" keeps single spacing
+                        if text[end..].starts_with(' ') {
+                            end += 1;
+                        }
+                        text.replace_range(start..end, "");
+                    }
+                }
+                text.trim().to_string()
+            }
+            None => {
+                let text = self.text.trim_start();
+                match text.strip_prefix("<@").and_then(|rest| rest.find('>').map(|end| &rest[end + 1..])) {
+                    Some(rest) => rest.trim().to_string(),
+                    None => text.trim().to_string(),
+                }
+            }
+        }
+    }
+}
+
+impl TypedEvent for AppMentionEvent {
+    const EVENT_TYPE: &'static str = event_types::APP_MENTION;
+}
+
+// What `App::mention` listeners receive: the event plus its text with the bot mention stripped
+#[derive(Debug, Clone)]
+pub struct Mention {
+    pub event: AppMentionEvent,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MessageEvent {
+    pub subtype: Option<String>,
+    pub user: Option<String>,
+    pub bot_id: Option<String>,
+    #[serde(default)]
+    pub text: String,
+    pub ts: String,
+    pub channel: String,
+    pub channel_type: Option<String>,
+    pub thread_ts: Option<String>,
+    pub event_ts: Option<String>,
+    #[serde(default)]
+    pub blocks: Vec<Value>,
+}
+
+impl TypedEvent for MessageEvent {
+    const EVENT_TYPE: &'static str = event_types::MESSAGE;
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReactionItem {
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub channel: Option<String>,
+    pub ts: Option<String>,
+    pub file: Option<String>,
+}
+
+impl ReactionItem {
+    pub fn is_message(&self) -> bool {
+        self.item_type == "message"
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReactionAddedEvent {
+    pub user: String,
+    pub reaction: String,
+    pub item_user: Option<String>,
+    pub item: ReactionItem,
+    pub event_ts: String,
+}
+
+impl ReactionAddedEvent {
+    // Channel and timestamp of the reacted-to message, for replying or updating it
+    pub fn channel(&self) -> Option<&str> {
+        self.item.channel.as_deref()
+    }
+
+    pub fn ts(&self) -> Option<&str> {
+        self.item.ts.as_deref()
+    }
+}
+
+impl TypedEvent for ReactionAddedEvent {
+    const EVENT_TYPE: &'static str = event_types::REACTION_ADDED;
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReactionRemovedEvent {
+    pub user: String,
+    pub reaction: String,
+    pub item_user: Option<String>,
+    pub item: ReactionItem,
+    pub event_ts: String,
+}
+
+impl ReactionRemovedEvent {
+    pub fn channel(&self) -> Option<&str> {
+        self.item.channel.as_deref()
+    }
+
+    pub fn ts(&self) -> Option<&str> {
+        self.item.ts.as_deref()
+    }
+}
+
+impl TypedEvent for ReactionRemovedEvent {
+    const EVENT_TYPE: &'static str = event_types::REACTION_REMOVED;
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppHomeOpenedEvent {
+    pub user: String,
+    pub channel: String,
+    pub tab: String,
+    pub event_ts: Option<String>,
+    pub view: Option<ViewPayload>,
+}
+
+impl TypedEvent for AppHomeOpenedEvent {
+    const EVENT_TYPE: &'static str = event_types::APP_HOME_OPENED;
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemberJoinedChannelEvent {
+    pub user: String,
+    pub channel: String,
+    pub channel_type: Option<String>,
+    pub team: Option<String>,
+    pub inviter: Option<String>,
+}
+
+impl TypedEvent for MemberJoinedChannelEvent {
+    const EVENT_TYPE: &'static str = event_types::MEMBER_JOINED_CHANNEL;
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FileRef {
+    pub id: String,
+}
+
+// Only carries the file ID; fetch details with `client.file_info` or the bytes with `client.download_file`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FileSharedEvent {
+    pub file_id: String,
+    pub user_id: String,
+    pub file: FileRef,
+    pub channel_id: Option<String>,
+    pub event_ts: Option<String>,
+}
+
+impl TypedEvent for FileSharedEvent {
+    const EVENT_TYPE: &'static str = event_types::FILE_SHARED;
+}
+
+impl TypedEvent for FunctionExecutedEvent {
+    const EVENT_TYPE: &'static str = event_types::FUNCTION_EXECUTED;
+}
+
+// One entry of a block_actions payload's `actions`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BlockAction {
+    pub action_id: String,
+    pub block_id: Option<String>,
+    #[serde(rename = "type")]
+    pub action_type: String,
+    pub value: Option<String>,
+    pub action_ts: Option<String>,
+    pub selected_option: Option<Value>,
+    #[serde(default)]
+    pub selected_options: Vec<Value>,
+    pub selected_user: Option<String>,
+    pub selected_channel: Option<String>,
+    pub selected_conversation: Option<String>,
+    pub selected_date: Option<String>,
+    pub selected_time: Option<String>,
+}
+
+// A view as Slack sends it back in view_submission/view_closed and app_home_opened
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ViewPayload {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub view_type: String,
+    pub callback_id: Option<String>,
+    pub external_id: Option<String>,
+    #[serde(default)]
+    pub private_metadata: String,
+    pub hash: Option<String>,
+    pub title: Option<Value>,
+    #[serde(default)]
+    pub blocks: Vec<Value>,
+    #[serde(default)]
+    pub state: ViewState,
+    pub root_view_id: Option<String>,
+    pub previous_view_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ViewState {
+    // block_id -> action_id -> element state
+    #[serde(default)]
+    pub values: HashMap<String, HashMap<String, Value>>,
+}
+
+impl ViewState {
+    pub fn get(&self, block_id: &str, action_id: &str) -> Option<&Value> {
+        self.values.get(block_id).and_then(|block| block.get(action_id))
+    }
+
+    // The submitted `value` of a text-like input
+    pub fn value(&self, block_id: &str, action_id: &str) -> Option<&str> {
+        self.get(block_id, action_id)
+            .and_then(|state| state.get("value"))
+            .and_then(|v| v.as_str())
+    }
+}
+
+// A global shortcut, started from the shortcuts menu or search; there is no channel or message
+#[derive(Debug, Clone, Serialize)]
+pub struct GlobalShortcut {
+    pub callback_id: String,
+    pub trigger_id: String,
+    pub action_ts: Option<String>,
+    pub user_id: String,
+    pub team_id: Option<String>,
+}
+
+// A message shortcut, started from a message's context menu
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageShortcut {
+    pub callback_id: String,
+    pub trigger_id: String,
+    pub action_ts: Option<String>,
+    pub user_id: String,
+    pub team_id: Option<String>,
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    pub message_ts: String,
+    // The message the shortcut was used on, as in a message event
+    pub message: Value,
+    // For replying in the channel without chat:write access to it
+    pub response_url: String,
+}
+
+impl MessageShortcut {
+    pub fn message_text(&self) -> Option<&str> {
+        self.message.get("text").and_then(|v| v.as_str())
+    }
+
+    // Replies belong in the thread the message is part of, or start one under it
+    pub fn thread_ts(&self) -> &str {
+        self.message.get("thread_ts").and_then(|v| v.as_str()).unwrap_or(&self.message_ts)
+    }
+}
+
+// Outer Events API payload Slack sends when it starts dropping the app's events for a team
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppRateLimited {
+    pub team_id: String,
+    pub api_app_id: String,
+    // Start of the rate-limited minute, in epoch seconds
+    pub minute_rate_limited: i64,
+}
+
+impl AppRateLimited {
+    pub fn minute(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.minute_rate_limited, 0)
+    }
+}
+
+impl From<&EventRequest> for AppRateLimited {
+    fn from(event: &EventRequest) -> Self {
+        Self {
+            team_id: event.team_id.clone(),
+            api_app_id: event.api_app_id.clone(),
+            minute_rate_limited: event.minute_rate_limited.unwrap_or_default(),
+        }
+    }
+}
+
+// What an app_uninstalled or tokens_revoked event took away from a workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum UninstallKind {
+    AppUninstalled,
+    // User IDs whose user tokens and bot IDs whose bot tokens were revoked
+    TokensRevoked { users: Vec<String>, bots: Vec<String> },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Uninstall {
+    pub team_id: String,
+    pub enterprise_id: Option<String>,
+    pub kind: UninstallKind,
+}
+
+impl Uninstall {
+    // None for any other event
+    pub fn from_event(event: &EventRequest) -> Option<Self> {
+        let kind = match event.event.get("type").and_then(|v| v.as_str()) {
+            Some(event_types::APP_UNINSTALLED) => UninstallKind::AppUninstalled,
+            Some(event_types::TOKENS_REVOKED) => {
+                let ids = |kind: &str| -> Vec<String> {
+                    event
+                        .event
+                        .pointer(&format!("/tokens/{}", kind))
+                        .and_then(|v| v.as_array())
+                        .map(|ids| ids.iter().filter_map(|id| id.as_str().map(str::to_string)).collect())
+                        .unwrap_or_default()
+                };
+                UninstallKind::TokensRevoked {
+                    users: ids("oauth"),
+                    bots: ids("bot"),
+                }
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            team_id: event.team_id.clone(),
+            enterprise_id: event.enterprise_id.clone(),
+            kind,
+        })
+    }
+
+    // Whether the bot lost access, so the stored installation is no longer usable
+    pub fn removes_bot(&self) -> bool {
+        match &self.kind {
+            UninstallKind::AppUninstalled => true,
+            UninstallKind::TokensRevoked { bots, .. } => !bots.is_empty(),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::error::{Result, SlackError};
+use crate::request::SlackRequest;
+use hmac::{Hmac, Mac};
+use chrono::Utc;
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "x-slack-signature";
+pub const TIMESTAMP_HEADER: &str = "x-slack-request-timestamp";
+
+const MAX_REQUEST_AGE_SECONDS: i64 = 5 * 60;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// `v0=` + hex HMAC-SHA256 of `v0:{timestamp}:{body}`, as Slack computes it
+pub fn compute(signing_secret: &str, timestamp: &str, body: &[u8]) -> Result<String> {
+    Ok(format!("v0={}", hex::encode(mac(signing_secret, timestamp, body)?.finalize().into_bytes())))
+}
+
+pub fn verify(signing_secret: &str, request: &SlackRequest) -> Result<()> {
+    let timestamp = request.header(TIMESTAMP_HEADER).ok_or(SlackError::InvalidSignature)?;
+    let signature = request.header(SIGNATURE_HEADER).ok_or(SlackError::InvalidSignature)?;
+
+    // Slack's replay window: a captured request stops verifying after five minutes
+    let sent_at: i64 = timestamp.parse().map_err(|_| SlackError::InvalidSignature)?;
+    if (Utc::now().timestamp() - sent_at).abs() > MAX_REQUEST_AGE_SECONDS {
+        return Err(SlackError::InvalidSignature);
+    }
+
+    let signature = signature
+        .strip_prefix("v0=")
+        .and_then(|hex_signature| hex::decode(hex_signature).ok())
+        .ok_or(SlackError::InvalidSignature)?;
+
+    // Constant time, so the comparison doesn't leak how much of a forged signature matched
+    mac(signing_secret, timestamp, &request.raw_body)?
+        .verify_slice(&signature)
+        .map_err(|_| SlackError::InvalidSignature)
+}
+
+fn mac(signing_secret: &str, timestamp: &str, body: &[u8]) -> Result<HmacSha256> {
+    let mut mac = HmacSha256::new_from_slice(signing_secret.as_bytes())
+        .map_err(|_| SlackError::InvalidSignature)?;
+
+    // Fed in pieces so the body is never copied
+    mac.update(b"v0:");
+    mac.update(timestamp.as_bytes());
+    mac.update(b":");
+    mac.update(body);
+    Ok(mac)
+}
@@ -0,0 +1,83 @@
+use crate::response::{SlackResponse, SlackResponseBody};
+use http::header::{
+    HeaderName, HeaderValue, CACHE_CONTROL, CONTENT_SECURITY_POLICY, CONTENT_TYPE, EXPIRES, PRAGMA, REFERRER_POLICY,
+    X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+};
+use tracing::debug;
+
+impl SlackResponse {
+    pub fn with_status(mut self, status_code: u16) -> Self {
+        self.status_code = status_code;
+        self
+    }
+
+    // Replaces any existing value; invalid names or values are dropped with a debug log
+    pub fn with_header<N: AsRef<str>, V: AsRef<str>>(mut self, name: N, value: V) -> Self {
+        match (HeaderName::try_from(name.as_ref()), HeaderValue::try_from(value.as_ref())) {
+            (Ok(name), Ok(value)) => {
+                self.headers.insert(name, value);
+            }
+            _ => debug!("Dropping invalid response header {}", name.as_ref()),
+        }
+        self
+    }
+
+    // Adds another value for a header that may repeat, e.g. Set-Cookie
+    pub fn append_header<N: AsRef<str>, V: AsRef<str>>(mut self, name: N, value: V) -> Self {
+        match (HeaderName::try_from(name.as_ref()), HeaderValue::try_from(value.as_ref())) {
+            (Ok(name), Ok(value)) => {
+                self.headers.append(name, value);
+            }
+            _ => debug!("Dropping invalid response header {}", name.as_ref()),
+        }
+        self
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.header(CONTENT_TYPE.as_str())
+    }
+
+    // For pages that must never be served from a cache, like OAuth redirects and results
+    pub fn no_cache(mut self) -> Self {
+        self.headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store, no-cache, must-revalidate"));
+        self.headers.insert(PRAGMA, HeaderValue::from_static("no-cache"));
+        self.headers.insert(EXPIRES, HeaderValue::from_static("0"));
+        self
+    }
+
+    // Locks an HTML page down to its own inline styles; nothing else may load or frame it
+    pub fn with_security_headers(mut self) -> Self {
+        self.headers.insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+        self.headers.insert(X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
+        self.headers.insert(REFERRER_POLICY, HeaderValue::from_static("no-referrer"));
+        self.headers.entry(CONTENT_SECURITY_POLICY).or_insert(HeaderValue::from_static(
+            "default-src 'none'; style-src 'unsafe-inline'; frame-ancestors 'none'",
+        ));
+        self
+    }
+
+    pub fn is_html(&self) -> bool {
+        self.content_type().is_some_and(|v| v.starts_with("text/html"))
+    }
+
+    // Fills in what the body implies without overriding anything a handler set explicitly
+    pub fn with_default_headers(mut self) -> Self {
+        let default_type = match &self.body {
+            SlackResponseBody::Empty => None,
+            SlackResponseBody::Content(_) => Some("text/plain; charset=utf-8"),
+            SlackResponseBody::Binary(_) => Some("application/octet-stream"),
+            #[cfg(feature = "streaming")]
+            SlackResponseBody::Stream(_) => Some("application/octet-stream"),
+            _ => Some("application/json"),
+        };
+
+        if let Some(default_type) = default_type {
+            self.headers.entry(CONTENT_TYPE).or_insert(HeaderValue::from_static(default_type));
+        }
+        self
+    }
+}
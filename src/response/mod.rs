@@ -1,10 +1,18 @@
+pub mod headers;
+#[cfg(feature = "streaming")]
+pub mod stream;
+
+#[cfg(feature = "streaming")]
+pub use stream::ResponseStream;
+
+use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE, LOCATION};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlackResponse {
     pub status_code: u16,
-    pub headers: HashMap<String, String>,
+    #[serde(with = "http_serde::header_map")]
+    pub headers: HeaderMap,
     pub body: SlackResponseBody,
 }
 
@@ -15,7 +23,17 @@ pub enum SlackResponseBody {
     Blocks(BlocksResponse),
     Challenge(ChallengeResponse),
     OAuth(OAuthResponse),
+    // Sent as-is rather than as JSON; the Content-Type header says what it is
+    Content(String),
+    // Base64-encoded on the way out (API Gateway `isBase64Encoded`)
+    Binary(Vec<u8>),
     Empty,
+    // Only sent incrementally by `LambdaHandler::run_streaming`; other adapters buffer it
+    #[cfg(feature = "streaming")]
+    #[serde(skip)]
+    Stream(ResponseStream),
+    // Any other JSON payload, e.g. block_suggestion options; last so untagged deserialization prefers the typed bodies
+    Json(serde_json::Value),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,7 +74,7 @@ impl SlackResponse {
     pub fn empty() -> Self {
         Self {
             status_code: 200,
-            headers: HashMap::new(),
+            headers: HeaderMap::new(),
             body: SlackResponseBody::Empty,
         }
     }
@@ -64,7 +82,7 @@ impl SlackResponse {
     pub fn text<S: Into<String>>(text: S) -> Self {
         Self {
             status_code: 200,
-            headers: HashMap::new(),
+            headers: HeaderMap::new(),
             body: SlackResponseBody::Text(TextResponse {
                 text: text.into(),
                 response_type: None,
@@ -77,16 +95,65 @@ impl SlackResponse {
     pub fn challenge<S: Into<String>>(challenge: S) -> Self {
         Self {
             status_code: 200,
-            headers: HashMap::new(),
+            headers: HeaderMap::new(),
             body: SlackResponseBody::Challenge(ChallengeResponse {
                 challenge: challenge.into(),
             }),
         }
     }
 
+    pub fn html<S: Into<String>>(html: S) -> Self {
+        Self::with_body("text/html; charset=utf-8", html)
+    }
+
+    pub fn json(body: serde_json::Value) -> Self {
+        Self {
+            status_code: 200,
+            headers: HeaderMap::new(),
+            body: SlackResponseBody::Json(body),
+        }
+    }
+
+    // Any non-JSON body, e.g. plain text for health checks or CSV from a custom route
+    pub fn with_body<C: AsRef<str>, S: Into<String>>(content_type: C, body: S) -> Self {
+        let mut headers = HeaderMap::new();
+        if let Ok(content_type) = HeaderValue::from_str(content_type.as_ref()) {
+            headers.insert(CONTENT_TYPE, content_type);
+        }
+
+        Self {
+            status_code: 200,
+            headers,
+            body: SlackResponseBody::Content(body.into()),
+        }
+    }
+
+    // Images, archives and other non-UTF-8 bodies from custom routes
+    pub fn binary<C: AsRef<str>, B: Into<Vec<u8>>>(content_type: C, body: B) -> Self {
+        Self {
+            body: SlackResponseBody::Binary(body.into()),
+            ..Self::with_body(content_type, String::new())
+        }
+    }
+
+    #[cfg(feature = "streaming")]
+    pub fn stream<C, S, B>(content_type: C, stream: S) -> Self
+    where
+        C: AsRef<str>,
+        S: futures::Stream<Item = crate::error::Result<B>> + Send + 'static,
+        B: Into<bytes::Bytes>,
+    {
+        Self {
+            body: SlackResponseBody::Stream(ResponseStream::new(stream)),
+            ..Self::with_body(content_type, String::new())
+        }
+    }
+
     pub fn redirect<S: Into<String>>(url: S) -> Self {
-        let mut headers = HashMap::new();
-        headers.insert("Location".to_string(), url.into());
+        let mut headers = HeaderMap::new();
+        if let Ok(location) = HeaderValue::try_from(url.into()) {
+            headers.insert(LOCATION, location);
+        }
         
         Self {
             status_code: 302,
@@ -94,4 +161,20 @@ impl SlackResponse {
             body: SlackResponseBody::Empty,
         }
     }
-}
\ No newline at end of file
+}
+
+// For interpolating user-controlled text (team names, error codes) into HTML pages
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,50 @@
+use crate::error::Result;
+use bytes::Bytes;
+use futures::stream::{Stream, StreamExt};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+pub type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
+
+// A body produced incrementally, for custom routes serving exports or progress pages. It can
+// be consumed once; responses are cloned freely, so the stream sits behind a shared slot.
+#[derive(Clone)]
+pub struct ResponseStream {
+    stream: Arc<Mutex<Option<BodyStream>>>,
+}
+
+impl ResponseStream {
+    pub fn new<S, B>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<B>> + Send + 'static,
+        B: Into<Bytes>,
+    {
+        let stream: BodyStream = Box::pin(stream.map(|chunk| chunk.map(Into::into)));
+        Self {
+            stream: Arc::new(Mutex::new(Some(stream))),
+        }
+    }
+
+    // None once another clone has taken it
+    pub fn take(&self) -> Option<BodyStream> {
+        self.stream.lock().ok().and_then(|mut stream| stream.take())
+    }
+
+    // Buffers the whole body, for adapters that cannot stream
+    pub async fn collect(&self) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+        if let Some(mut stream) = self.take() {
+            while let Some(chunk) = stream.next().await {
+                body.extend_from_slice(&chunk?);
+            }
+        }
+        Ok(body)
+    }
+}
+
+impl fmt::Debug for ResponseStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseStream").finish_non_exhaustive()
+    }
+}
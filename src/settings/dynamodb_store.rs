@@ -0,0 +1,89 @@
+use crate::dynamodb::TableSpec;
+use crate::error::{Result, SlackError};
+use crate::infrastructure::Resource;
+use crate::settings::{TeamSettings, TeamSettingsStore};
+use async_trait::async_trait;
+use aws_sdk_dynamodb::{types::AttributeValue, Client as DynamoDbClient};
+use chrono::Utc;
+
+// Keyed like the installations table (team_id, enterprise_id with "NONE" for
+// non-Grid workspaces) so the two can share tooling and backups
+#[derive(Debug, Clone)]
+pub struct DynamoDbTeamSettingsStore {
+    client: DynamoDbClient,
+    table_name: String,
+}
+
+impl DynamoDbTeamSettingsStore {
+    pub fn new(client: DynamoDbClient, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+
+    fn enterprise_key(enterprise_id: Option<&str>) -> String {
+        enterprise_id.unwrap_or("NONE").to_string()
+    }
+}
+
+#[async_trait]
+impl TeamSettingsStore for DynamoDbTeamSettingsStore {
+    fn required_resources(&self) -> Vec<Resource> {
+        let spec = TableSpec::new(self.table_name.as_str(), "team_id").with_sort_key("enterprise_id");
+        vec![Resource::Table(spec)]
+    }
+
+    async fn find(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<Option<TeamSettings>> {
+        let response = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .key("team_id", AttributeValue::S(team_id.to_string()))
+            .key("enterprise_id", AttributeValue::S(Self::enterprise_key(enterprise_id)))
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        let settings = response
+            .item
+            .as_ref()
+            .and_then(|item| item.get("settings"))
+            .and_then(|v| v.as_s().ok())
+            .map(|json| serde_json::from_str(json))
+            .transpose()?;
+        Ok(settings)
+    }
+
+    async fn save(&self, settings: &TeamSettings) -> Result<()> {
+        let settings = TeamSettings {
+            updated_at: Some(Utc::now()),
+            ..settings.clone()
+        };
+
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .item("team_id", AttributeValue::S(settings.team_id.clone()))
+            .item(
+                "enterprise_id",
+                AttributeValue::S(Self::enterprise_key(settings.enterprise_id.as_deref())),
+            )
+            .item("settings", AttributeValue::S(serde_json::to_string(&settings)?))
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<()> {
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key("team_id", AttributeValue::S(team_id.to_string()))
+            .key("enterprise_id", AttributeValue::S(Self::enterprise_key(enterprise_id)))
+            .send()
+            .await
+            .map_err(|e| SlackError::DynamoDb(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb_store;
+
+#[cfg(feature = "dynamodb")]
+pub use dynamodb_store::DynamoDbTeamSettingsStore;
+
+use crate::error::Result;
+use crate::infrastructure::Resource;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+// Per-workspace configuration kept next to the installation record. The common fields are
+// typed; anything app-specific goes in `values`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TeamSettings {
+    pub team_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enterprise_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_channel: Option<String>,
+    #[serde(default)]
+    pub features: HashMap<String, bool>,
+    #[serde(default)]
+    pub admin_users: Vec<String>,
+    #[serde(default)]
+    pub values: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl TeamSettings {
+    pub fn new<S: Into<String>>(team_id: S) -> Self {
+        Self {
+            team_id: team_id.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_enterprise_id<S: Into<String>>(mut self, enterprise_id: S) -> Self {
+        self.enterprise_id = Some(enterprise_id.into());
+        self
+    }
+
+    pub fn with_default_channel<S: Into<String>>(mut self, channel: S) -> Self {
+        self.default_channel = Some(channel.into());
+        self
+    }
+
+    pub fn with_feature<S: Into<String>>(mut self, feature: S, enabled: bool) -> Self {
+        self.features.insert(feature.into(), enabled);
+        self
+    }
+
+    pub fn with_admin<S: Into<String>>(mut self, user_id: S) -> Self {
+        let user_id = user_id.into();
+        if !self.admin_users.contains(&user_id) {
+            self.admin_users.push(user_id);
+        }
+        self
+    }
+
+    pub fn with_value<K: Into<String>, T: Serialize>(mut self, key: K, value: T) -> Result<Self> {
+        self.values.insert(key.into(), serde_json::to_value(value)?);
+        Ok(self)
+    }
+
+    // Features default to off until a workspace turns them on
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.features.get(feature).copied().unwrap_or(false)
+    }
+
+    pub fn is_admin(&self, user_id: &str) -> bool {
+        self.admin_users.iter().any(|admin| admin == user_id)
+    }
+
+    // None when the key is missing; an error when it holds something other than `T`
+    pub fn value<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.values
+            .get(key)
+            .map(|value| serde_json::from_value(value.clone()))
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+#[async_trait]
+pub trait TeamSettingsStore: Send + Sync + Debug {
+    async fn find(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<Option<TeamSettings>>;
+
+    async fn save(&self, settings: &TeamSettings) -> Result<()>;
+
+    async fn delete(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<()>;
+
+    fn required_resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+
+    // Stored settings, or empty ones for a workspace that never saved any
+    async fn find_or_default(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<TeamSettings> {
+        Ok(self.find(team_id, enterprise_id).await?.unwrap_or_else(|| {
+            let settings = TeamSettings::new(team_id);
+            match enterprise_id {
+                Some(enterprise_id) => settings.with_enterprise_id(enterprise_id),
+                None => settings,
+            }
+        }))
+    }
+}
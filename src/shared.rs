@@ -0,0 +1,70 @@
+// Resources that are expensive to build and safe to share, created once per execution
+// environment so warm invocations reuse connection pools instead of rebuilding them.
+use crate::error::{Result, SlackError};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+#[cfg(feature = "dynamodb")]
+use tokio::sync::OnceCell;
+
+#[cfg(feature = "http-client")]
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+#[cfg(feature = "dynamodb")]
+static DYNAMODB_CLIENT: OnceCell<aws_sdk_dynamodb::Client> = OnceCell::const_new();
+#[cfg(feature = "dynamodb")]
+static REGIONAL_DYNAMODB_CLIENTS: OnceLock<Mutex<HashMap<String, aws_sdk_dynamodb::Client>>> = OnceLock::new();
+static REGEX_CACHE: OnceLock<Mutex<HashMap<String, Arc<Regex>>>> = OnceLock::new();
+
+#[cfg(feature = "http-client")]
+pub fn http_client() -> reqwest::Client {
+    // reqwest::Client is reference counted, clones share the same pool
+    HTTP_CLIENT.get_or_init(reqwest::Client::new).clone()
+}
+
+#[cfg(feature = "dynamodb")]
+pub async fn dynamodb_client() -> aws_sdk_dynamodb::Client {
+    DYNAMODB_CLIENT
+        .get_or_init(|| async {
+            let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+            aws_sdk_dynamodb::Client::new(&config)
+        })
+        .await
+        .clone()
+}
+
+// Client pinned to a specific region, e.g. the preferred read replica of a global table
+#[cfg(feature = "dynamodb")]
+pub async fn regional_dynamodb_client(region: &str) -> aws_sdk_dynamodb::Client {
+    let clients = REGIONAL_DYNAMODB_CLIENTS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(client) = clients.lock().ok().and_then(|clients| clients.get(region).cloned()) {
+        return client;
+    }
+
+    let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+    let client = aws_sdk_dynamodb::Client::new(&config);
+
+    if let Ok(mut clients) = clients.lock() {
+        clients.entry(region.to_string()).or_insert_with(|| client.clone());
+    }
+    client
+}
+
+pub fn regex(pattern: &str) -> Result<Arc<Regex>> {
+    let cache = REGEX_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache
+        .lock()
+        .map_err(|_| SlackError::Internal("Regex cache poisoned".to_string()))?;
+
+    if let Some(regex) = cache.get(pattern) {
+        return Ok(regex.clone());
+    }
+
+    let regex = Arc::new(
+        Regex::new(pattern).map_err(|e| SlackError::Config(format!("Invalid regex {}: {}", pattern, e)))?,
+    );
+    cache.insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
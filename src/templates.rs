@@ -0,0 +1,169 @@
+// Message and block templates defined once as JSON and filled in per request, so handlers
+// pass data instead of building blocks. Placeholders:
+//
+//   "{{service}}"       the value at `service`, mrkdwn-escaped, inside any string
+//   "{{deploy.sha}}"    dotted paths; numeric segments index arrays ("{{items.0.name}}")
+//   "{{{link}}}"        inserted as is, for values that already are mrkdwn (links, mentions)
+//   "{{{fields}}}"      alone in a string, replaced by the value itself: arrays of blocks,
+//                       numbers, booleans
+//
+// A placeholder without a value is an error rather than an empty string, so a renamed field
+// fails loudly. Object keys are never interpolated.
+//
+//   templates::register_json("deploy_done", r#"[{"type": "section", "text":
+//       {"type": "mrkdwn", "text": "*{{service}}* deployed by <@{{user_id}}>"}}]"#)?;
+//   let blocks = templates::render("deploy_done", &json!({ "service": "api", "user_id": "U1" }))?;
+use crate::error::{Result, SlackError};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+static REGISTRY: OnceLock<RwLock<Templates>> = OnceLock::new();
+
+#[derive(Debug, Clone, Default)]
+pub struct Templates {
+    templates: HashMap<String, Value>,
+}
+
+impl Templates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_template<S: Into<String>>(mut self, name: S, template: Value) -> Self {
+        self.insert(name, template);
+        self
+    }
+
+    pub fn with_json<S: Into<String>>(mut self, name: S, template: &str) -> Result<Self> {
+        self.insert(name, serde_json::from_str(template)?);
+        Ok(self)
+    }
+
+    // Registering a second template under the same name replaces the first
+    pub fn insert<S: Into<String>>(&mut self, name: S, template: Value) {
+        self.templates.insert(name.into(), template);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    pub fn render<T: Serialize + ?Sized>(&self, name: &str, data: &T) -> Result<Value> {
+        let template = self
+            .templates
+            .get(name)
+            .ok_or_else(|| SlackError::Config(format!("No template named {}", name)))?;
+        let data = serde_json::to_value(data)?;
+        render_value(name, template, &data)
+    }
+}
+
+// Process-wide templates behind `register` and `render`, typically filled in an `on_init` hook
+fn registry() -> &'static RwLock<Templates> {
+    REGISTRY.get_or_init(|| RwLock::new(Templates::new()))
+}
+
+pub fn register<S: Into<String>>(name: S, template: Value) {
+    registry().write().unwrap().insert(name, template);
+}
+
+pub fn register_json<S: Into<String>>(name: S, template: &str) -> Result<()> {
+    register(name, serde_json::from_str(template)?);
+    Ok(())
+}
+
+pub fn render<T: Serialize + ?Sized>(name: &str, data: &T) -> Result<Value> {
+    registry().read().unwrap().render(name, data)
+}
+
+// Slack's control characters in mrkdwn; everything else renders literally
+pub fn escape_mrkdwn(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn render_value(name: &str, template: &Value, data: &Value) -> Result<Value> {
+    match template {
+        Value::String(text) => render_string(name, text, data),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| render_value(name, item, data))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), render_value(name, value, data)?)))
+            .collect::<Result<serde_json::Map<_, _>>>()
+            .map(Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
+fn render_string(name: &str, text: &str, data: &Value) -> Result<Value> {
+    // A lone raw placeholder takes on the value's own type
+    if let Some(path) = text.strip_prefix("{{{").and_then(|rest| rest.strip_suffix("}}}")) {
+        if !path.contains("}}") {
+            let value = lookup(name, data, path.trim())?;
+            if !value.is_string() {
+                return Ok(value.clone());
+            }
+        }
+    }
+
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let (raw, open, close) = if rest[start..].starts_with("{{{") {
+            (true, 3, "}}}")
+        } else {
+            (false, 2, "}}")
+        };
+        let after_open = &rest[start + open..];
+        let Some(end) = after_open.find(close) else {
+            return Err(SlackError::Config(format!("Template {} has an unclosed placeholder", name)));
+        };
+
+        let path = after_open[..end].trim();
+        let value = scalar_text(name, path, lookup(name, data, path)?)?;
+        if raw {
+            rendered.push_str(&value);
+        } else {
+            rendered.push_str(&escape_mrkdwn(&value));
+        }
+        rest = &after_open[end + close.len()..];
+    }
+    rendered.push_str(rest);
+    Ok(Value::String(rendered))
+}
+
+fn lookup<'a>(name: &str, data: &'a Value, path: &str) -> Result<&'a Value> {
+    path.split('.')
+        .try_fold(data, |value, segment| match value {
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => value.get(segment),
+        })
+        .ok_or_else(|| SlackError::Config(format!("Template {} has no value for {}", name, path)))
+}
+
+fn scalar_text(name: &str, path: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Null => Ok(String::new()),
+        Value::Number(_) | Value::Bool(_) => Ok(value.to_string()),
+        Value::Array(_) | Value::Object(_) => Err(SlackError::Config(format!(
+            "Template {} interpolates {} into text, but it is not a scalar",
+            name, path
+        ))),
+    }
+}
@@ -0,0 +1,361 @@
+// Builders for complete, correctly signed SlackRequests in the same wire format Slack
+// uses, so they can be fed to `LambdaHandler::process_request` and pass verification.
+use crate::request::signature::{self, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use crate::request::{CommandRequest, EventRequest, InteractiveRequest, SlackRequest, SlackRequestBody};
+use crate::testing::{TEST_CHANNEL_ID, TEST_TEAM_ID, TEST_USER_ID};
+use bytes::Bytes;
+use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+pub const TEST_SIGNING_SECRET: &str = "test_signing_secret";
+
+#[derive(Debug, Clone)]
+pub(crate) struct Signing {
+    secret: String,
+    timestamp: i64,
+}
+
+impl Default for Signing {
+    fn default() -> Self {
+        Self {
+            secret: TEST_SIGNING_SECRET.to_string(),
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+impl Signing {
+    pub(crate) fn request(&self, path: &str, content_type: &str, raw_body: String, body: SlackRequestBody) -> SlackRequest {
+        let timestamp = self.timestamp.to_string();
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type).expect("valid content type"));
+        headers.insert(TIMESTAMP_HEADER, HeaderValue::from_str(&timestamp).expect("valid timestamp"));
+
+        // Only fails for secrets HMAC can't key, which SHA-256 doesn't have
+        if let Ok(signature) = signature::compute(&self.secret, &timestamp, raw_body.as_bytes()) {
+            headers.insert(SIGNATURE_HEADER, HeaderValue::from_str(&signature).expect("valid signature"));
+        }
+
+        SlackRequest {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            headers,
+            query_params: HashMap::new(),
+            multi_query_params: HashMap::new(),
+            body,
+            raw_body: Bytes::from(raw_body),
+            gateway: None,
+            correlation_id: None,
+            deferred: false,
+        }
+    }
+}
+
+fn form_encode(fields: &[(&str, &str)]) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| format!("{}={}", urlencoding::encode(key), urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[derive(Debug, Clone)]
+pub struct EventRequestBuilder {
+    team_id: String,
+    enterprise_id: Option<String>,
+    event_id: Option<String>,
+    is_ext_shared_channel: bool,
+    event: Map<String, Value>,
+    signing: Signing,
+}
+
+impl EventRequestBuilder {
+    pub fn new<S: Into<String>>(event_type: S) -> Self {
+        let mut event = Map::new();
+        event.insert("type".to_string(), json!(event_type.into()));
+        event.insert("user".to_string(), json!(TEST_USER_ID));
+        event.insert("channel".to_string(), json!(TEST_CHANNEL_ID));
+        event.insert("ts".to_string(), json!("1700000000.000100"));
+
+        Self {
+            team_id: TEST_TEAM_ID.to_string(),
+            enterprise_id: None,
+            event_id: None,
+            is_ext_shared_channel: false,
+            event,
+            signing: Signing::default(),
+        }
+    }
+
+    pub fn team_id<S: Into<String>>(mut self, team_id: S) -> Self {
+        self.team_id = team_id.into();
+        self
+    }
+
+    pub fn enterprise_id<S: Into<String>>(mut self, enterprise_id: S) -> Self {
+        self.enterprise_id = Some(enterprise_id.into());
+        self
+    }
+
+    pub fn event_id<S: Into<String>>(mut self, event_id: S) -> Self {
+        self.event_id = Some(event_id.into());
+        self
+    }
+
+    pub fn ext_shared_channel(mut self) -> Self {
+        self.is_ext_shared_channel = true;
+        self
+    }
+
+    pub fn user<S: Into<String>>(self, user_id: S) -> Self {
+        self.field("user", json!(user_id.into()))
+    }
+
+    pub fn channel<S: Into<String>>(self, channel_id: S) -> Self {
+        self.field("channel", json!(channel_id.into()))
+    }
+
+    pub fn text<S: Into<String>>(self, text: S) -> Self {
+        self.field("text", json!(text.into()))
+    }
+
+    pub fn thread_ts<S: Into<String>>(self, thread_ts: S) -> Self {
+        self.field("thread_ts", json!(thread_ts.into()))
+    }
+
+    // Sets any field on the inner event object
+    pub fn field<S: Into<String>>(mut self, key: S, value: Value) -> Self {
+        self.event.insert(key.into(), value);
+        self
+    }
+
+    pub fn signing_secret<S: Into<String>>(mut self, secret: S) -> Self {
+        self.signing.secret = secret.into();
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.signing.timestamp = timestamp;
+        self
+    }
+
+    pub fn build(self) -> SlackRequest {
+        let event = EventRequest {
+            token: String::new(),
+            team_id: self.team_id,
+            api_app_id: "A0000TEST".to_string(),
+            enterprise_id: self.enterprise_id,
+            event: Value::Object(self.event),
+            event_type: "event_callback".to_string(),
+            event_time: self.signing.timestamp as u64,
+            challenge: None,
+            minute_rate_limited: None,
+            authorizations: Vec::new(),
+            event_id: self.event_id,
+            event_context: None,
+            is_ext_shared_channel: self.is_ext_shared_channel,
+        };
+        let raw_body = serde_json::to_string(&event).unwrap_or_default();
+
+        self.signing
+            .request("/slack/events", "application/json", raw_body, SlackRequestBody::Event(event))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandRequestBuilder {
+    command: CommandRequest,
+    signing: Signing,
+}
+
+impl CommandRequestBuilder {
+    pub fn new<S: Into<String>>(command: S) -> Self {
+        Self {
+            command: CommandRequest {
+                token: String::new(),
+                team_id: TEST_TEAM_ID.to_string(),
+                team_domain: "test".to_string(),
+                channel_id: TEST_CHANNEL_ID.to_string(),
+                channel_name: "general".to_string(),
+                user_id: TEST_USER_ID.to_string(),
+                user_name: "tester".to_string(),
+                command: command.into(),
+                text: String::new(),
+                response_url: "https://hooks.slack.com/commands/T0000TEST/1/test".to_string(),
+                trigger_id: "1.1.test".to_string(),
+                enterprise_id: None,
+            },
+            signing: Signing::default(),
+        }
+    }
+
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.command.text = text.into();
+        self
+    }
+
+    pub fn team_id<S: Into<String>>(mut self, team_id: S) -> Self {
+        self.command.team_id = team_id.into();
+        self
+    }
+
+    pub fn enterprise_id<S: Into<String>>(mut self, enterprise_id: S) -> Self {
+        self.command.enterprise_id = Some(enterprise_id.into());
+        self
+    }
+
+    pub fn channel_id<S: Into<String>>(mut self, channel_id: S) -> Self {
+        self.command.channel_id = channel_id.into();
+        self
+    }
+
+    pub fn user_id<S: Into<String>>(mut self, user_id: S) -> Self {
+        self.command.user_id = user_id.into();
+        self
+    }
+
+    pub fn signing_secret<S: Into<String>>(mut self, secret: S) -> Self {
+        self.signing.secret = secret.into();
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.signing.timestamp = timestamp;
+        self
+    }
+
+    pub fn build(self) -> SlackRequest {
+        let command = &self.command;
+        let mut fields = vec![
+            ("token", command.token.as_str()),
+            ("team_id", command.team_id.as_str()),
+            ("team_domain", command.team_domain.as_str()),
+            ("channel_id", command.channel_id.as_str()),
+            ("channel_name", command.channel_name.as_str()),
+            ("user_id", command.user_id.as_str()),
+            ("user_name", command.user_name.as_str()),
+            ("command", command.command.as_str()),
+            ("text", command.text.as_str()),
+            ("response_url", command.response_url.as_str()),
+            ("trigger_id", command.trigger_id.as_str()),
+        ];
+        if let Some(enterprise_id) = &command.enterprise_id {
+            fields.push(("enterprise_id", enterprise_id.as_str()));
+        }
+        let raw_body = form_encode(&fields);
+
+        self.signing.request(
+            "/slack/commands",
+            "application/x-www-form-urlencoded",
+            raw_body,
+            SlackRequestBody::Command(self.command),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockActionBuilder {
+    actions: Vec<Value>,
+    team_id: String,
+    user_id: String,
+    channel_id: String,
+    message: Option<Value>,
+    view: Option<Value>,
+    signing: Signing,
+}
+
+impl BlockActionBuilder {
+    pub fn new<S: Into<String>>(action_id: S) -> Self {
+        Self {
+            actions: vec![json!({
+                "type": "button",
+                "action_id": action_id.into(),
+                "block_id": "test_block",
+                "action_ts": "1700000000.000200",
+            })],
+            team_id: TEST_TEAM_ID.to_string(),
+            user_id: TEST_USER_ID.to_string(),
+            channel_id: TEST_CHANNEL_ID.to_string(),
+            message: None,
+            view: None,
+            signing: Signing::default(),
+        }
+    }
+
+    // Sets `value` on the most recently added action
+    pub fn value<S: Into<String>>(mut self, value: S) -> Self {
+        if let Some(action) = self.actions.last_mut() {
+            action["value"] = json!(value.into());
+        }
+        self
+    }
+
+    pub fn action(mut self, action: Value) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    pub fn team_id<S: Into<String>>(mut self, team_id: S) -> Self {
+        self.team_id = team_id.into();
+        self
+    }
+
+    pub fn user_id<S: Into<String>>(mut self, user_id: S) -> Self {
+        self.user_id = user_id.into();
+        self
+    }
+
+    pub fn channel_id<S: Into<String>>(mut self, channel_id: S) -> Self {
+        self.channel_id = channel_id.into();
+        self
+    }
+
+    pub fn message(mut self, message: Value) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    pub fn view(mut self, view: Value) -> Self {
+        self.view = Some(view);
+        self
+    }
+
+    pub fn signing_secret<S: Into<String>>(mut self, secret: S) -> Self {
+        self.signing.secret = secret.into();
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.signing.timestamp = timestamp;
+        self
+    }
+
+    pub fn build(self) -> SlackRequest {
+        let payload = InteractiveRequest {
+            payload_type: "block_actions".to_string(),
+            token: String::new(),
+            team: json!({ "id": self.team_id, "domain": "test" }),
+            user: json!({ "id": self.user_id, "name": "tester", "team_id": self.team_id }),
+            channel: Some(json!({ "id": self.channel_id, "name": "general" })),
+            message: self.message,
+            actions: self.actions,
+            callback_id: None,
+            trigger_id: "1.1.test".to_string(),
+            response_url: "https://hooks.slack.com/actions/T0000TEST/1/test".to_string(),
+            view: self.view,
+            workflow_step: None,
+            enterprise: None,
+            action_ts: None,
+            message_ts: None,
+        };
+        let raw_body = form_encode(&[("payload", &serde_json::to_string(&payload).unwrap_or_default())]);
+
+        self.signing.request(
+            "/slack/actions",
+            "application/x-www-form-urlencoded",
+            raw_body,
+            SlackRequestBody::Interactive(payload),
+        )
+    }
+}
@@ -0,0 +1,58 @@
+use crate::conversation::{ConversationKey, ConversationState, ConversationStore};
+use crate::error::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// Conversation state kept in memory, conditional writes included. Clones share the map, so a
+// test can hand one to the app (or a `Digest`) and inspect it afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryConversationStore {
+    states: Arc<Mutex<HashMap<String, ConversationState>>>,
+}
+
+impl MemoryConversationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ConversationStore for MemoryConversationStore {
+    async fn find(&self, key: &ConversationKey) -> Result<Option<ConversationState>> {
+        Ok(self.states.lock().ok().and_then(|states| states.get(&key.as_storage_key()).cloned()))
+    }
+
+    async fn update(&self, key: &ConversationKey, state: &ConversationState) -> Result<()> {
+        if let Ok(mut states) = self.states.lock() {
+            states.insert(key.as_storage_key(), state.clone());
+        }
+        Ok(())
+    }
+
+    async fn expire(&self, key: &ConversationKey) -> Result<()> {
+        if let Ok(mut states) = self.states.lock() {
+            states.remove(&key.as_storage_key());
+        }
+        Ok(())
+    }
+
+    async fn update_versioned(
+        &self,
+        key: &ConversationKey,
+        state: &ConversationState,
+        expected: Option<u64>,
+    ) -> Result<bool> {
+        let mut states = match self.states.lock() {
+            Ok(states) => states,
+            Err(_) => return Ok(false),
+        };
+        let storage_key = key.as_storage_key();
+        if states.get(&storage_key).map(|stored| stored.version) != expected {
+            return Ok(false);
+        }
+
+        states.insert(storage_key, state.clone());
+        Ok(true)
+    }
+}
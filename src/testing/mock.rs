@@ -0,0 +1,160 @@
+use crate::client::SlackApi;
+use crate::error::{Result, SlackError};
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// Downloads are recorded as calls to this pseudo-method, with the URL as payload
+pub const DOWNLOAD_METHOD: &str = "files.download";
+// response_url posts are recorded under this pseudo-method, with the URL in `response_url`;
+// stub it with `"ok": false` to simulate an expired URL
+pub const RESPONSE_URL_METHOD: &str = "response_url";
+
+#[derive(Debug, Clone)]
+pub struct RecordedCall {
+    pub method: String,
+    pub payload: Value,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    // Queued one-shot responses are used before the method's persistent stub
+    queued: HashMap<String, VecDeque<Value>>,
+    stubs: HashMap<String, Value>,
+    files: HashMap<String, Bytes>,
+    calls: Vec<RecordedCall>,
+}
+
+// A SlackApi that never touches the network. Unstubbed methods answer `{"ok": true}`;
+// stubs with `"ok": false` fail the call the same way the real client does.
+// Clones (including `with_token` clients) share stubs and recorded calls.
+#[derive(Debug, Clone, Default)]
+pub struct MockSlackClient {
+    state: Arc<Mutex<MockState>>,
+    token: Option<String>,
+}
+
+impl MockSlackClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        // A panicking test thread shouldn't hide the calls made before it
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn stub<S: Into<String>>(&self, method: S, response: Value) -> &Self {
+        self.state().stubs.insert(method.into(), response);
+        self
+    }
+
+    pub fn stub_once<S: Into<String>>(&self, method: S, response: Value) -> &Self {
+        self.state().queued.entry(method.into()).or_default().push_back(response);
+        self
+    }
+
+    pub fn stub_error<S: Into<String>, E: Into<String>>(&self, method: S, error: E) -> &Self {
+        self.stub(method, json!({ "ok": false, "error": error.into() }))
+    }
+
+    // Content returned when `url` is downloaded; unstubbed URLs fail with `file_not_found`
+    pub fn stub_file<S: Into<String>, B: Into<Bytes>>(&self, url: S, content: B) -> &Self {
+        self.state().files.insert(url.into(), content.into());
+        self
+    }
+
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.state().calls.clone()
+    }
+
+    pub fn calls_to(&self, method: &str) -> Vec<Value> {
+        self.state()
+            .calls
+            .iter()
+            .filter(|call| call.method == method)
+            .map(|call| call.payload.clone())
+            .collect()
+    }
+
+    pub fn reset(&self) {
+        *self.state() = MockState::default();
+    }
+
+    pub fn assert_called(&self, method: &str) {
+        assert!(!self.calls_to(method).is_empty(), "expected a call to {}, got {:?}", method, self.methods());
+    }
+
+    pub fn assert_called_times(&self, method: &str, times: usize) {
+        let count = self.calls_to(method).len();
+        assert_eq!(count, times, "expected {} calls to {}, got {}", times, method, count);
+    }
+
+    pub fn assert_not_called(&self, method: &str) {
+        assert!(self.calls_to(method).is_empty(), "expected no call to {}", method);
+    }
+
+    fn methods(&self) -> Vec<String> {
+        self.state().calls.iter().map(|call| call.method.clone()).collect()
+    }
+}
+
+#[async_trait]
+impl SlackApi for MockSlackClient {
+    async fn call(&self, method: &str, payload: Value) -> Result<Value> {
+        let mut state = self.state();
+        state.calls.push(RecordedCall {
+            method: method.to_string(),
+            payload,
+            token: self.token.clone(),
+        });
+
+        let response = state
+            .queued
+            .get_mut(method)
+            .and_then(|queue| queue.pop_front())
+            .or_else(|| state.stubs.get(method).cloned())
+            .unwrap_or_else(|| json!({ "ok": true }));
+
+        if response.get("ok").and_then(|v| v.as_bool()) == Some(false) {
+            return Err(SlackError::SlackApi {
+                code: response.get("error").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                message: format!("{} failed", method),
+            });
+        }
+
+        Ok(response)
+    }
+
+    async fn respond(&self, response_url: &str, payload: Value) -> Result<()> {
+        let mut payload = payload;
+        if let Value::Object(fields) = &mut payload {
+            fields.insert("response_url".to_string(), json!(response_url));
+        }
+        self.call(RESPONSE_URL_METHOD, payload).await.map(|_| ())
+    }
+
+    async fn download(&self, url: &str) -> Result<Bytes> {
+        let mut state = self.state();
+        state.calls.push(RecordedCall {
+            method: DOWNLOAD_METHOD.to_string(),
+            payload: json!({ "url": url }),
+            token: self.token.clone(),
+        });
+
+        state.files.get(url).cloned().ok_or_else(|| SlackError::SlackApi {
+            code: "file_not_found".to_string(),
+            message: format!("No file stubbed for {}", url),
+        })
+    }
+
+    fn with_token(&self, token: String) -> Arc<dyn SlackApi> {
+        Arc::new(Self {
+            state: self.state.clone(),
+            token: Some(token),
+        })
+    }
+}
@@ -0,0 +1,140 @@
+// Helpers for unit testing handlers: requests go through the app's real middleware,
+// conversation store and router, without Lambda, HTTP or signature verification.
+pub mod builders;
+pub mod conversation;
+pub mod fixtures;
+pub mod mock;
+#[cfg(feature = "oauth")]
+pub mod oauth;
+#[cfg(feature = "lambda")]
+pub mod queue;
+pub mod spy;
+#[cfg(feature = "wiremock")]
+pub mod wiremock;
+
+pub use builders::{BlockActionBuilder, CommandRequestBuilder, EventRequestBuilder, TEST_SIGNING_SECRET};
+pub use conversation::MemoryConversationStore;
+pub use mock::{MockSlackClient, RecordedCall, DOWNLOAD_METHOD, RESPONSE_URL_METHOD};
+#[cfg(feature = "oauth")]
+pub use oauth::{FakeTokenExchanger, MemoryInstallationStore, MemoryStateStore};
+#[cfg(feature = "lambda")]
+pub use queue::MemoryRequestQueue;
+pub use spy::{any_text, contains, equals, SpyClient, TextMatcher};
+
+use crate::app::App;
+use crate::error::Result;
+use crate::request::{InteractiveRequest, SlackRequest, SlackRequestBody};
+use crate::response::SlackResponse;
+use bytes::Bytes;
+use http::HeaderMap;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+pub const TEST_TEAM_ID: &str = "T0000TEST";
+pub const TEST_USER_ID: &str = "U0000TEST";
+pub const TEST_CHANNEL_ID: &str = "C0000TEST";
+
+pub struct TestApp {
+    app: App,
+}
+
+impl From<App> for TestApp {
+    fn from(app: App) -> Self {
+        Self { app }
+    }
+}
+
+impl TestApp {
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    pub async fn send(&self, request: SlackRequest) -> Result<SlackResponse> {
+        self.app.dispatch(request).await
+    }
+
+    // `event` is the inner event object, e.g. `{"type": "app_mention", "text": "hi"}`
+    pub async fn send_event(&self, event: Value) -> Result<SlackResponse> {
+        let event_type = event.get("type").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let mut builder = EventRequestBuilder::new(event_type);
+        if let Value::Object(fields) = event {
+            for (key, value) in fields {
+                builder = builder.field(key, value);
+            }
+        }
+        self.send(builder.build()).await
+    }
+
+    pub async fn send_command<C: Into<String>, T: Into<String>>(&self, command: C, text: T) -> Result<SlackResponse> {
+        self.send(CommandRequestBuilder::new(command).text(text).build()).await
+    }
+
+    pub async fn send_action<S: Into<String>, V: Into<String>>(&self, action_id: S, value: V) -> Result<SlackResponse> {
+        self.send(BlockActionBuilder::new(action_id).value(value).build()).await
+    }
+
+    pub async fn send_shortcut<S: Into<String>>(&self, callback_id: S) -> Result<SlackResponse> {
+        self.send_interactive(interactive("shortcut", Some(callback_id.into()))).await
+    }
+
+    // A message shortcut used on `message`, which needs at least a `ts`
+    pub async fn send_message_shortcut<S: Into<String>>(&self, callback_id: S, message: Value) -> Result<SlackResponse> {
+        let mut payload = interactive("message_action", Some(callback_id.into()));
+        payload.message_ts = message.get("ts").and_then(|v| v.as_str()).map(String::from);
+        payload.message = Some(message);
+        self.send_interactive(payload).await
+    }
+
+    // A modal submission; `values` is the view's `state.values` object
+    pub async fn send_view_submission<S: Into<String>>(&self, callback_id: S, values: Value) -> Result<SlackResponse> {
+        let mut payload = interactive("view_submission", None);
+        payload.view = Some(json!({
+            "id": "V0000TEST",
+            "type": "modal",
+            "callback_id": callback_id.into(),
+            "state": { "values": values },
+        }));
+        self.send_interactive(payload).await
+    }
+
+    pub async fn send_interactive(&self, payload: InteractiveRequest) -> Result<SlackResponse> {
+        self.send(synthetic_request(SlackRequestBody::Interactive(payload))).await
+    }
+}
+
+fn interactive(payload_type: &str, callback_id: Option<String>) -> InteractiveRequest {
+    InteractiveRequest {
+        payload_type: payload_type.to_string(),
+        token: String::new(),
+        team: json!({ "id": TEST_TEAM_ID, "domain": "test" }),
+        user: json!({ "id": TEST_USER_ID, "name": "tester" }),
+        channel: Some(json!({ "id": TEST_CHANNEL_ID, "name": "general" })),
+        message: None,
+        actions: Vec::new(),
+        callback_id,
+        trigger_id: String::new(),
+        response_url: String::new(),
+        view: None,
+        workflow_step: None,
+        enterprise: None,
+        action_ts: None,
+        message_ts: None,
+    }
+}
+
+fn synthetic_request(body: SlackRequestBody) -> SlackRequest {
+    let raw_body = serde_json::to_vec(&body).map(Bytes::from).unwrap_or_default();
+
+    SlackRequest {
+        method: "POST".to_string(),
+        path: "/slack/events".to_string(),
+        headers: HeaderMap::new(),
+        query_params: HashMap::new(),
+        multi_query_params: HashMap::new(),
+        body,
+        raw_body,
+        gateway: None,
+        correlation_id: None,
+        deferred: false,
+    }
+}
@@ -0,0 +1,251 @@
+// Stand-ins for slack.com and the storage an OAuth flow needs, so install links and redirects
+// can be driven end to end in tests:
+//
+//     let installations = MemoryInstallationStore::new();
+//     let app = App::builder()
+//         .signing_secret(TEST_SIGNING_SECRET)
+//         .client_id("123.456")
+//         .client_secret("secret")
+//         .redirect_uri("https://example.com/slack/oauth_redirect")
+//         .oauth_settings(|settings| settings
+//             .installation_store(installations.clone())
+//             .state_store(MemoryStateStore::new())
+//             .token_exchanger(FakeTokenExchanger::success()))
+//         .build()?;
+use crate::error::Result;
+use crate::oauth::flow::{OAuthAccessResponse, OAuthExchangeResponse};
+use crate::oauth::{Installation, InstallationPage, InstallationStore, OAuthState, StateStore, TokenExchanger};
+use crate::testing::{TEST_TEAM_ID, TEST_USER_ID};
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub const FAKE_BOT_TOKEN: &str = "xoxb-0000-fake";
+pub const FAKE_BOT_USER_ID: &str = "U0000BOT";
+pub const FAKE_USER_TOKEN: &str = "xoxp-0000-fake";
+pub const FAKE_ROTATING_BOT_TOKEN: &str = "xoxe.xoxb-1-fake";
+pub const FAKE_REFRESH_TOKEN: &str = "xoxe-1-fake";
+pub const FAKE_APP_ID: &str = "A0000TEST";
+
+// Answers oauth.v2.access and oauth.v2.exchange with canned responses and records what it
+// was asked. Clones share the recording.
+#[derive(Debug, Clone)]
+pub struct FakeTokenExchanger {
+    access: OAuthAccessResponse,
+    exchange: OAuthExchangeResponse,
+    codes: Arc<Mutex<Vec<String>>>,
+    tokens: Arc<Mutex<Vec<String>>>,
+}
+
+impl FakeTokenExchanger {
+    pub fn new(access: OAuthAccessResponse, exchange: OAuthExchangeResponse) -> Self {
+        Self {
+            access,
+            exchange,
+            codes: Arc::new(Mutex::new(Vec::new())),
+            tokens: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    // A bot install into TEST_TEAM_ID by TEST_USER_ID, with a user token and long-lived tokens
+    pub fn success() -> Self {
+        Self::new(
+            access_response(json!({
+                "ok": true,
+                "access_token": FAKE_BOT_TOKEN,
+                "token_type": "bot",
+                "scope": "chat:write,commands",
+                "bot_user_id": FAKE_BOT_USER_ID,
+                "app_id": FAKE_APP_ID,
+                "team": { "id": TEST_TEAM_ID, "name": "Test" },
+                "enterprise": null,
+                "is_enterprise_install": false,
+                "authed_user": {
+                    "id": TEST_USER_ID,
+                    "scope": "search:read",
+                    "access_token": FAKE_USER_TOKEN,
+                    "token_type": "user",
+                },
+            })),
+            exchange_response(json!({
+                "ok": true,
+                "access_token": FAKE_BOT_TOKEN,
+                "token_type": "bot",
+                "scope": "chat:write,commands",
+                "bot_user_id": FAKE_BOT_USER_ID,
+                "app_id": FAKE_APP_ID,
+            })),
+        )
+    }
+
+    // What Slack says to a code that is wrong, expired or already used
+    pub fn bad_code() -> Self {
+        Self::error("invalid_code")
+    }
+
+    // Every call fails with `error`, e.g. "bad_redirect_uri" or "invalid_client_id"
+    pub fn error(error: &str) -> Self {
+        let response = json!({ "ok": false, "error": error });
+        Self::new(access_response(response.clone()), exchange_response(response))
+    }
+
+    // Like `success`, for an app with token rotation: the bot token expires in 12 hours and
+    // comes with a refresh token
+    pub fn rotating() -> Self {
+        let mut fake = Self::success();
+        fake.access.access_token = Some(FAKE_ROTATING_BOT_TOKEN.into());
+        fake.access.expires_in = Some(43200);
+        fake.access.refresh_token = Some(FAKE_REFRESH_TOKEN.into());
+        fake.exchange.access_token = Some(FAKE_ROTATING_BOT_TOKEN.into());
+        fake.exchange.expires_in = Some(43200);
+        fake
+    }
+
+    // Codes passed to oauth.v2.access, oldest first
+    pub fn codes(&self) -> Vec<String> {
+        self.codes.lock().map(|codes| codes.clone()).unwrap_or_default()
+    }
+
+    // Classic tokens passed to oauth.v2.exchange, oldest first
+    pub fn tokens(&self) -> Vec<String> {
+        self.tokens.lock().map(|tokens| tokens.clone()).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl TokenExchanger for FakeTokenExchanger {
+    async fn exchange_code(
+        &self,
+        _client_id: &str,
+        _client_secret: &str,
+        code: &str,
+        _redirect_uri: &str,
+    ) -> Result<OAuthAccessResponse> {
+        if let Ok(mut codes) = self.codes.lock() {
+            codes.push(code.to_string());
+        }
+        Ok(self.access.clone())
+    }
+
+    async fn exchange_token(&self, _client_id: &str, _client_secret: &str, token: &str) -> Result<OAuthExchangeResponse> {
+        if let Ok(mut tokens) = self.tokens.lock() {
+            tokens.push(token.to_string());
+        }
+        Ok(self.exchange.clone())
+    }
+}
+
+// The canned responses are fixed, so failing to parse them is a bug in this file
+fn access_response(response: serde_json::Value) -> OAuthAccessResponse {
+    serde_json::from_value(response).expect("canned oauth.v2.access response")
+}
+
+fn exchange_response(response: serde_json::Value) -> OAuthExchangeResponse {
+    serde_json::from_value(response).expect("canned oauth.v2.exchange response")
+}
+
+// Installations kept in memory. Clones share the same map, so a test can hand one to the app
+// and inspect it afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryInstallationStore {
+    installations: Arc<Mutex<HashMap<String, Installation>>>,
+}
+
+impl MemoryInstallationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn installations(&self) -> Vec<Installation> {
+        self.installations
+            .lock()
+            .map(|installations| installations.values().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl InstallationStore for MemoryInstallationStore {
+    async fn save(&self, installation: &Installation) -> Result<()> {
+        if let Ok(mut installations) = self.installations.lock() {
+            let key = installation_key(&installation.team_id, installation.enterprise_id.as_deref());
+            installations.insert(key, installation.clone());
+        }
+        Ok(())
+    }
+
+    async fn find_by_team(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<Option<Installation>> {
+        let key = installation_key(team_id, enterprise_id);
+        Ok(self.installations.lock().ok().and_then(|installations| installations.get(&key).cloned()))
+    }
+
+    async fn delete(&self, team_id: &str, enterprise_id: Option<&str>) -> Result<()> {
+        if let Ok(mut installations) = self.installations.lock() {
+            installations.remove(&installation_key(team_id, enterprise_id));
+        }
+        Ok(())
+    }
+
+    // In key order; the cursor is the last key of the previous page
+    async fn list_page(&self, cursor: Option<&str>, limit: usize) -> Result<InstallationPage> {
+        let installations = match self.installations.lock() {
+            Ok(installations) => installations,
+            Err(_) => return Ok(InstallationPage::default()),
+        };
+        let mut keys: Vec<&String> = installations
+            .keys()
+            .filter(|key| cursor.is_none_or(|cursor| key.as_str() > cursor))
+            .collect();
+        keys.sort();
+        let more = keys.len() > limit.max(1);
+        keys.truncate(limit.max(1));
+
+        Ok(InstallationPage {
+            next_cursor: keys.last().filter(|_| more).map(|key| key.to_string()),
+            installations: keys.into_iter().filter_map(|key| installations.get(key).cloned()).collect(),
+        })
+    }
+}
+
+fn installation_key(team_id: &str, enterprise_id: Option<&str>) -> String {
+    format!("{}:{}", enterprise_id.unwrap_or_default(), team_id)
+}
+
+// OAuth states kept in memory; clones share them
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStateStore {
+    states: Arc<Mutex<HashMap<String, OAuthState>>>,
+}
+
+impl MemoryStateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // States issued and not yet consumed
+    pub fn states(&self) -> Vec<String> {
+        self.states.lock().map(|states| states.keys().cloned().collect()).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStateStore {
+    async fn save(&self, state: &OAuthState) -> Result<()> {
+        if let Ok(mut states) = self.states.lock() {
+            states.insert(state.state.clone(), state.clone());
+        }
+        Ok(())
+    }
+
+    async fn find(&self, state: &str) -> Result<Option<OAuthState>> {
+        Ok(self.states.lock().ok().and_then(|states| states.get(state).cloned()))
+    }
+
+    async fn delete(&self, state: &str) -> Result<()> {
+        if let Ok(mut states) = self.states.lock() {
+            states.remove(state);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::adapter::sqs::{SqsEvent, SqsMessage};
+use crate::adapter::RequestQueue;
+use crate::error::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// Deferred requests kept in memory instead of on SQS. Clones share the queue, so a test can
+// hand one to the app and later feed what it collected to an `SqsHandler`:
+//
+//     let queue = MemoryRequestQueue::new();
+//     let app = App::builder().request_queue(queue.clone())...build()?;
+//     app.clone().sqs_handler().handle_batch(queue.take_event()).await;
+#[derive(Debug, Clone, Default)]
+pub struct MemoryRequestQueue {
+    bodies: Arc<Mutex<Vec<String>>>,
+}
+
+impl MemoryRequestQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Message bodies sent so far, oldest first
+    pub fn bodies(&self) -> Vec<String> {
+        self.bodies.lock().map(|bodies| bodies.clone()).unwrap_or_default()
+    }
+
+    // Empties the queue into the event SQS would deliver to the consuming function
+    pub fn take_event(&self) -> SqsEvent {
+        let bodies = self.bodies.lock().map(|mut bodies| std::mem::take(&mut *bodies)).unwrap_or_default();
+        SqsEvent {
+            records: bodies
+                .into_iter()
+                .enumerate()
+                .map(|(index, body)| SqsMessage {
+                    message_id: format!("message-{}", index + 1),
+                    body,
+                    attributes: HashMap::new(),
+                    event_source_arn: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl RequestQueue for MemoryRequestQueue {
+    async fn send(&self, body: String) -> Result<()> {
+        if let Ok(mut bodies) = self.bodies.lock() {
+            bodies.push(body);
+        }
+        Ok(())
+    }
+}
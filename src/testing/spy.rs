@@ -0,0 +1,188 @@
+use crate::client::SlackApi;
+use crate::error::Result;
+use crate::testing::mock::{RecordedCall, DOWNLOAD_METHOD, RESPONSE_URL_METHOD};
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// Predicate over message text used by the spy's assertion helpers
+pub struct TextMatcher {
+    description: String,
+    predicate: Box<dyn Fn(&str) -> bool + Send + Sync>,
+}
+
+impl TextMatcher {
+    pub fn new<S, F>(description: S, predicate: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            description: description.into(),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        (self.predicate)(text)
+    }
+}
+
+impl fmt::Debug for TextMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+pub fn contains<S: Into<String>>(needle: S) -> TextMatcher {
+    let needle = needle.into();
+    TextMatcher::new(format!("text containing {:?}", needle), move |text| text.contains(&needle))
+}
+
+pub fn equals<S: Into<String>>(expected: S) -> TextMatcher {
+    let expected = expected.into();
+    TextMatcher::new(format!("text equal to {:?}", expected), move |text| text == expected)
+}
+
+pub fn any_text() -> TextMatcher {
+    TextMatcher::new("any text", |_| true)
+}
+
+// Wraps any SlackApi (usually the real client or a MockSlackClient) and records every
+// call before passing it through. Clones share the recording.
+#[derive(Debug, Clone)]
+pub struct SpyClient {
+    inner: Arc<dyn SlackApi>,
+    calls: Arc<Mutex<Vec<RecordedCall>>>,
+    token: Option<String>,
+}
+
+impl SpyClient {
+    pub fn new<A: SlackApi + 'static>(inner: A) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            calls: Arc::new(Mutex::new(Vec::new())),
+            token: None,
+        }
+    }
+
+    fn recorded(&self) -> MutexGuard<'_, Vec<RecordedCall>> {
+        self.calls.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.recorded().clone()
+    }
+
+    pub fn calls_to(&self, method: &str) -> Vec<Value> {
+        self.recorded()
+            .iter()
+            .filter(|call| call.method == method)
+            .map(|call| call.payload.clone())
+            .collect()
+    }
+
+    // Text of every chat.postMessage sent to `channel`, including text inside blocks
+    pub fn messages_to(&self, channel: &str) -> Vec<String> {
+        self.calls_to("chat.postMessage")
+            .iter()
+            .filter(|payload| payload.get("channel").and_then(|v| v.as_str()) == Some(channel))
+            .map(message_text)
+            .collect()
+    }
+
+    pub fn assert_called(&self, method: &str) {
+        let methods: Vec<String> = self.recorded().iter().map(|call| call.method.clone()).collect();
+        assert!(methods.iter().any(|m| m == method), "expected a call to {}, got {:?}", method, methods);
+    }
+
+    pub fn assert_not_called(&self, method: &str) {
+        assert!(self.calls_to(method).is_empty(), "expected no call to {}", method);
+    }
+
+    pub fn assert_posted_to(&self, channel: &str, matcher: TextMatcher) {
+        let messages = self.messages_to(channel);
+        assert!(
+            messages.iter().any(|text| matcher.matches(text)),
+            "expected a message to {} with {:?}, got {:?}",
+            channel,
+            matcher,
+            messages
+        );
+    }
+
+    pub fn assert_not_posted_to(&self, channel: &str) {
+        let messages = self.messages_to(channel);
+        assert!(messages.is_empty(), "expected no message to {}, got {:?}", channel, messages);
+    }
+}
+
+#[async_trait]
+impl SlackApi for SpyClient {
+    async fn call(&self, method: &str, payload: Value) -> Result<Value> {
+        self.recorded().push(RecordedCall {
+            method: method.to_string(),
+            payload: payload.clone(),
+            token: self.token.clone(),
+        });
+        self.inner.call(method, payload).await
+    }
+
+    async fn respond(&self, response_url: &str, payload: Value) -> Result<()> {
+        let mut recorded = payload.clone();
+        if let Value::Object(fields) = &mut recorded {
+            fields.insert("response_url".to_string(), json!(response_url));
+        }
+        self.recorded().push(RecordedCall {
+            method: RESPONSE_URL_METHOD.to_string(),
+            payload: recorded,
+            token: self.token.clone(),
+        });
+        self.inner.respond(response_url, payload).await
+    }
+
+    async fn download(&self, url: &str) -> Result<Bytes> {
+        self.recorded().push(RecordedCall {
+            method: DOWNLOAD_METHOD.to_string(),
+            payload: json!({ "url": url }),
+            token: self.token.clone(),
+        });
+        self.inner.download(url).await
+    }
+
+    fn with_token(&self, token: String) -> Arc<dyn SlackApi> {
+        Arc::new(Self {
+            inner: self.inner.with_token(token.clone()),
+            calls: self.calls.clone(),
+            token: Some(token),
+        })
+    }
+}
+
+fn message_text(payload: &Value) -> String {
+    let mut parts = Vec::new();
+    if let Some(text) = payload.get("text").and_then(|v| v.as_str()) {
+        parts.push(text.to_string());
+    }
+    if let Some(blocks) = payload.get("blocks") {
+        collect_text(blocks, &mut parts);
+    }
+    parts.join("\n")
+}
+
+fn collect_text(value: &Value, parts: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::String(text) if key == "text" => parts.push(text.clone()),
+                    _ => collect_text(value, parts),
+                }
+            }
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_text(value, parts)),
+        _ => {}
+    }
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use slack_serverless::audit::{AuditRecord, AuditSink, BufferedAuditSink};
+use slack_serverless::testing::{CommandRequestBuilder, MockSlackClient};
+use slack_serverless::{App, Result};
+use std::sync::{Arc, Mutex};
+
+// Remembers the size of every write it is asked for; clones share them
+#[derive(Debug, Clone, Default)]
+struct RecordingSink {
+    writes: Arc<Mutex<Vec<usize>>>,
+}
+
+#[async_trait]
+impl AuditSink for RecordingSink {
+    async fn record(&self, _record: &AuditRecord) -> Result<()> {
+        self.writes.lock().unwrap().push(1);
+        Ok(())
+    }
+
+    async fn record_batch(&self, records: &[AuditRecord]) -> Result<()> {
+        self.writes.lock().unwrap().push(records.len());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn buffered_records_are_written_in_one_batch_on_flush() {
+    let sink = RecordingSink::default();
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(MockSlackClient::new())
+        .audit_sink(BufferedAuditSink::new(sink.clone()))
+        .build()
+        .unwrap();
+    app.command("/deploy", |context| async move { context.ack.empty().await });
+
+    for _ in 0..3 {
+        app.dispatch(CommandRequestBuilder::new("/deploy").build()).await.unwrap();
+    }
+    assert!(sink.writes.lock().unwrap().is_empty());
+
+    app.flush().await;
+    assert_eq!(*sink.writes.lock().unwrap(), vec![3]);
+
+    // Nothing left to write
+    app.flush().await;
+    assert_eq!(*sink.writes.lock().unwrap(), vec![3]);
+}
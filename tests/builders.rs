@@ -0,0 +1,42 @@
+use serde_json::json;
+use slack_serverless::request::signature::{self, SIGNATURE_HEADER};
+use slack_serverless::testing::{BlockActionBuilder, CommandRequestBuilder, EventRequestBuilder, TEST_SIGNING_SECRET};
+
+#[test]
+fn built_requests_pass_signature_verification() {
+    let requests = [
+        CommandRequestBuilder::new("/deploy").text("prod").build(),
+        EventRequestBuilder::new("app_mention").text("hi").field("ts", json!("1700000000.000100")).build(),
+        BlockActionBuilder::new("approve").value("yes").build(),
+    ];
+
+    for request in &requests {
+        signature::verify(TEST_SIGNING_SECRET, request).unwrap();
+    }
+}
+
+#[test]
+fn requests_signed_with_another_secret_are_rejected() {
+    let request = CommandRequestBuilder::new("/deploy").signing_secret("other").build();
+
+    assert!(signature::verify(TEST_SIGNING_SECRET, &request).is_err());
+}
+
+#[test]
+fn stale_requests_are_rejected() {
+    let six_minutes_ago = chrono::Utc::now().timestamp() - 6 * 60;
+    let request = EventRequestBuilder::new("app_mention").timestamp(six_minutes_ago).build();
+
+    assert!(signature::verify(TEST_SIGNING_SECRET, &request).is_err());
+}
+
+#[test]
+fn tampered_signatures_are_rejected() {
+    let mut request = EventRequestBuilder::new("app_mention").build();
+    let signature = request.header(SIGNATURE_HEADER).unwrap().to_string();
+    let flipped = if signature.ends_with('0') { "1" } else { "0" };
+    let tampered = format!("{}{}", &signature[..signature.len() - 1], flipped);
+    request.headers.insert(SIGNATURE_HEADER, tampered.parse().unwrap());
+
+    assert!(signature::verify(TEST_SIGNING_SECRET, &request).is_err());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "lambda")]
+
+use serde_json::json;
+use slack_serverless::testing::{CommandRequestBuilder, MemoryRequestQueue, MockSlackClient};
+use slack_serverless::response::SlackResponseBody;
+use slack_serverless::App;
+use std::time::Duration;
+
+#[tokio::test]
+async fn deferred_request_runs_to_completion_in_the_sqs_handler() {
+    let client = MockSlackClient::new();
+    let queue = MemoryRequestQueue::new();
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(client.clone())
+        .request_queue(queue.clone())
+        .process_before_response(false)
+        .build()
+        .unwrap();
+    app.command("/report", |context| async move {
+        if !context.is_deferred() {
+            context.defer().await?;
+            return context.ack.text("Working on it...").await;
+        }
+        context.ack.empty().await?;
+        // Past the ack, so only a run forced to completion gets this far before returning
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        context.client.call("chat.postMessage", json!({ "channel": "C1", "text": "Done" })).await?;
+        context.ack.empty().await
+    });
+
+    let request = CommandRequestBuilder::new("/report").build();
+    let response = app.dispatch(request).await.unwrap();
+    assert!(matches!(response.body, SlackResponseBody::Text(ref text) if text.text == "Working on it..."));
+    assert_eq!(queue.bodies().len(), 1);
+    client.assert_not_called("chat.postMessage");
+
+    let batch = app.clone().sqs_handler().handle_batch(queue.take_event()).await;
+
+    assert!(batch.batch_item_failures.is_empty());
+    client.assert_called_times("chat.postMessage", 1);
+    assert!(queue.bodies().is_empty());
+}
+
+#[tokio::test]
+async fn defer_without_a_queue_is_a_config_error() {
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(MockSlackClient::new())
+        .build()
+        .unwrap();
+    app.command("/report", |context| async move {
+        context.defer().await?;
+        context.ack.empty().await
+    });
+
+    let error = app.dispatch(CommandRequestBuilder::new("/report").build()).await.unwrap_err();
+
+    assert!(matches!(error.root(), slack_serverless::SlackError::Config(_)));
+}
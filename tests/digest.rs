@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use slack_serverless::app::{Digest, DigestItem};
+use slack_serverless::testing::{MemoryConversationStore, MockSlackClient, TEST_CHANNEL_ID, TEST_TEAM_ID};
+use slack_serverless::{Result, SlackApi};
+use std::sync::Arc;
+
+fn digest(store: &MemoryConversationStore) -> Digest {
+    Digest::new("standup", TEST_TEAM_ID, TEST_CHANNEL_ID, Arc::new(store.clone()))
+}
+
+fn texts(items: &[DigestItem]) -> Vec<&str> {
+    items.iter().map(|item| item.text.as_str()).collect()
+}
+
+#[tokio::test]
+async fn concurrent_adds_keep_every_item() {
+    let digest = digest(&MemoryConversationStore::new());
+
+    let adds = (0..20).map(|n| {
+        let digest = digest.clone();
+        tokio::spawn(async move { digest.add(DigestItem::new(format!("item {}", n))).await })
+    });
+    for add in futures::future::join_all(adds).await {
+        add.unwrap().unwrap();
+    }
+
+    assert_eq!(digest.items().await.unwrap().len(), 20);
+}
+
+// Posts like Slack would, after someone adds to the digest mid-flight
+#[derive(Debug)]
+struct AddsDuringPost {
+    digest: Digest,
+    client: MockSlackClient,
+}
+
+#[async_trait]
+impl SlackApi for AddsDuringPost {
+    async fn call(&self, method: &str, payload: Value) -> Result<Value> {
+        self.digest.add(DigestItem::new("late")).await?;
+        self.client.call(method, payload).await
+    }
+
+    fn with_token(&self, _token: String) -> Arc<dyn SlackApi> {
+        unimplemented!()
+    }
+}
+
+#[tokio::test]
+async fn publish_keeps_items_added_while_posting() {
+    let digest = digest(&MemoryConversationStore::new());
+    digest.add(DigestItem::new("early")).await.unwrap();
+    let client = AddsDuringPost { digest: digest.clone(), client: MockSlackClient::new() };
+    client.client.stub("chat.postMessage", json!({ "ok": true, "ts": "1.2" }));
+
+    let ts = digest.publish(&client).await.unwrap();
+
+    assert_eq!(ts.as_deref(), Some("1.2"));
+    assert_eq!(client.client.calls_to("chat.postMessage")[0]["text"], "standup (1 items)");
+    assert_eq!(texts(&digest.items().await.unwrap()), ["late"]);
+}
+
+#[tokio::test]
+async fn failed_post_keeps_the_items() {
+    let digest = digest(&MemoryConversationStore::new());
+    digest.add(DigestItem::new("early")).await.unwrap();
+    let client = MockSlackClient::new();
+    client.stub_error("chat.postMessage", "channel_not_found");
+
+    assert!(digest.publish(&client).await.is_err());
+    assert_eq!(texts(&digest.items().await.unwrap()), ["early"]);
+}
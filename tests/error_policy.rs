@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use slack_serverless::adapter::ErrorPolicy;
+use slack_serverless::oauth::{Installation, InstallationStore};
+use slack_serverless::testing::{CommandRequestBuilder, MockSlackClient};
+use slack_serverless::{App, Result, SlackError};
+
+#[tokio::test]
+async fn transient_listener_error_is_acknowledged() {
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(MockSlackClient::new())
+        .build()
+        .unwrap();
+    app.command("/deploy", |_context| async { Err(SlackError::DynamoDb("throttled".to_string())) });
+
+    let error = app.dispatch(CommandRequestBuilder::new("/deploy").build()).await.unwrap_err();
+
+    assert!(matches!(error.root(), SlackError::DynamoDb(_)));
+    assert!(error.is_retriable());
+    assert_eq!(ErrorPolicy::new().status_code(&error), 200);
+    assert_eq!(ErrorPolicy::new().retry_listener_errors(true).status_code(&error), 503);
+}
+
+#[derive(Debug)]
+struct UnavailableStore;
+
+#[async_trait]
+impl InstallationStore for UnavailableStore {
+    async fn save(&self, _installation: &Installation) -> Result<()> {
+        Err(SlackError::DynamoDb("unavailable".to_string()))
+    }
+
+    async fn find_by_team(&self, _team_id: &str, _enterprise_id: Option<&str>) -> Result<Option<Installation>> {
+        Err(SlackError::DynamoDb("unavailable".to_string()))
+    }
+
+    async fn delete(&self, _team_id: &str, _enterprise_id: Option<&str>) -> Result<()> {
+        Err(SlackError::DynamoDb("unavailable".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn transient_error_before_routing_is_redelivered() {
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(MockSlackClient::new())
+        .oauth_settings(|settings| settings.installation_store(UnavailableStore))
+        .build()
+        .unwrap();
+    app.command("/deploy", |context| async move { context.ack.empty().await });
+
+    let error = app.dispatch(CommandRequestBuilder::new("/deploy").build()).await.unwrap_err();
+
+    assert!(matches!(error, SlackError::DynamoDb(_)));
+    assert_eq!(ErrorPolicy::new().status_code(&error), 503);
+}
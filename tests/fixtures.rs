@@ -0,0 +1,111 @@
+use serde_json::Value;
+use slack_serverless::request::{CommandRequest, EventRequest, InteractiveRequest, OAuthRequest, SlackRequestBody};
+use slack_serverless::listener::function::FunctionExecutedEvent;
+use slack_serverless::request::parse::MAX_FORM_VALUE_BYTES;
+use slack_serverless::testing::fixtures;
+use slack_serverless::SlackError;
+
+// Serializing a parsed body and parsing it again must not lose or change anything
+fn round_trip<T>(value: &T) -> Value
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let first = serde_json::to_value(value).unwrap();
+    let reparsed: T = serde_json::from_value(first.clone()).unwrap();
+    let second = serde_json::to_value(&reparsed).unwrap();
+    assert_eq!(first, second);
+    second
+}
+
+#[test]
+fn every_fixture_parses_as_its_kind() {
+    for fixture in fixtures::all() {
+        let body = fixture.parse().unwrap_or_else(|e| panic!("{} failed to parse: {}", fixture.name, e));
+        assert_eq!(body.kind(), fixture.kind, "{} parsed as the wrong kind", fixture.name);
+    }
+}
+
+#[test]
+fn every_fixture_round_trips() {
+    for fixture in fixtures::all() {
+        match fixture.parse().unwrap() {
+            SlackRequestBody::Event(event) => {
+                round_trip::<EventRequest>(&event);
+            }
+            SlackRequestBody::Command(command) => {
+                round_trip::<CommandRequest>(&command);
+            }
+            SlackRequestBody::Interactive(interactive) => {
+                round_trip::<InteractiveRequest>(&interactive);
+            }
+            SlackRequestBody::OAuth(oauth) => {
+                round_trip::<OAuthRequest>(&oauth);
+            }
+            SlackRequestBody::Raw(_) | SlackRequestBody::Unknown(_) => panic!("{} was not recognized", fixture.name),
+        }
+    }
+}
+
+#[test]
+fn fixture_fields_survive_parsing() {
+    let SlackRequestBody::Command(command) = fixtures::SLASH_COMMAND.parse().unwrap() else {
+        panic!("slash_command is not a command");
+    };
+    assert_eq!(command.command, "/weather");
+    assert_eq!(command.enterprise_id.as_deref(), Some("E0001"));
+    assert_eq!(command.response_url, "https://hooks.slack.com/commands/1234/5678");
+
+    let request = fixtures::BLOCK_ACTIONS.request().unwrap();
+    assert_eq!(request.team_id(), Some("T9TK3CUKW"));
+    assert_eq!(request.channel_id(), Some("CBR2V3XEX"));
+    assert_eq!(request.block_count(), 1);
+
+    let request = fixtures::MESSAGE.request().unwrap();
+    assert_eq!(request.enterprise_id(), Some("E0KH54SBH"));
+    assert_eq!(request.thread_ts(), Some("1355517500.000001"));
+
+    let SlackRequestBody::Interactive(shortcut) = fixtures::MESSAGE_SHORTCUT.parse().unwrap() else {
+        panic!("message_shortcut is not interactive");
+    };
+    assert_eq!(shortcut.message_ts.as_deref(), Some("1581106239.000400"));
+    assert_eq!(shortcut.action_ts.as_deref(), Some("1581106241.371594"));
+}
+
+#[test]
+fn form_bodies_decode_like_browsers_encode() {
+    let body = b"command=%2Fcalc&text=1+%2B+1&text=ignored&team_id=T1&channel_id&user_id=";
+    let SlackRequestBody::Command(command) =
+        SlackRequestBody::parse(body, "application/x-www-form-urlencoded").unwrap()
+    else {
+        panic!("form body is not a command");
+    };
+    assert_eq!(command.text, "1 + 1");
+    assert_eq!(command.channel_id, "");
+    assert_eq!(command.user_id, "");
+}
+
+#[test]
+fn form_values_past_the_limit_are_rejected() {
+    let at_limit = format!("command=%2Fcalc&text={}", "a".repeat(MAX_FORM_VALUE_BYTES));
+    assert!(SlackRequestBody::parse(at_limit.as_bytes(), "application/x-www-form-urlencoded").is_ok());
+
+    // Measured decoded: three encoded bytes per character here, one once decoded
+    let encoded_at_limit = format!("command=%2Fcalc&text={}", "%41".repeat(MAX_FORM_VALUE_BYTES));
+    assert!(SlackRequestBody::parse(encoded_at_limit.as_bytes(), "application/x-www-form-urlencoded").is_ok());
+
+    let over_limit = format!("command=%2Fcalc&text={}", "a".repeat(MAX_FORM_VALUE_BYTES + 1));
+    let error = SlackRequestBody::parse(over_limit.as_bytes(), "application/x-www-form-urlencoded").unwrap_err();
+    assert!(matches!(error, SlackError::InvalidForm(_)));
+}
+
+#[test]
+fn function_token_is_redacted_in_debug() {
+    let SlackRequestBody::Event(event) = fixtures::FUNCTION_EXECUTED.parse().unwrap() else {
+        panic!("function_executed is not an event");
+    };
+    let function: FunctionExecutedEvent = serde_json::from_value(event.event).unwrap();
+    let token = function.bot_access_token.as_ref().unwrap();
+
+    assert_eq!(token.expose(), "xwfp-0000000000-example");
+    assert!(!format!("{:?}", function).contains("xwfp-"));
+}
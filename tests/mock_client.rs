@@ -0,0 +1,39 @@
+use serde_json::json;
+use slack_serverless::response::SlackResponseBody;
+use slack_serverless::testing::{CommandRequestBuilder, MockSlackClient};
+use slack_serverless::App;
+
+#[tokio::test]
+async fn listener_sees_the_stubbed_response() {
+    let client = MockSlackClient::new();
+    client.stub("users.info", json!({ "ok": true, "user": { "id": "U0000TEST", "real_name": "Ada" } }));
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(client.clone())
+        .build()
+        .unwrap();
+    app.command("/whoami", |context| async move {
+        let user = context.client.call("users.info", json!({ "user": "U0000TEST" })).await?;
+        let name = user["user"]["real_name"].as_str().unwrap_or_default().to_string();
+        context.ack.text(name).await
+    });
+
+    let response = app.dispatch(CommandRequestBuilder::new("/whoami").build()).await.unwrap();
+
+    assert!(matches!(response.body, SlackResponseBody::Text(ref text) if text.text == "Ada"));
+    client.assert_called_times("users.info", 1);
+    assert_eq!(client.calls_to("users.info")[0]["user"], "U0000TEST");
+}
+
+#[tokio::test]
+async fn stubbed_error_fails_the_call() {
+    let client = MockSlackClient::new();
+    client.stub_error("chat.postMessage", "channel_not_found");
+
+    let error = slack_serverless::SlackApi::call(&client, "chat.postMessage", json!({ "channel": "C1" }))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, slack_serverless::SlackError::SlackApi { ref code, .. } if code == "channel_not_found"));
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "oauth")]
+
+use slack_serverless::oauth::flow::OAuthFlow;
+use slack_serverless::testing::oauth::{FAKE_BOT_TOKEN, FAKE_REFRESH_TOKEN, FAKE_ROTATING_BOT_TOKEN};
+use slack_serverless::testing::{
+    FakeTokenExchanger, MemoryInstallationStore, MemoryStateStore, MockSlackClient, TEST_SIGNING_SECRET, TEST_TEAM_ID,
+};
+use slack_serverless::{App, SlackError};
+
+fn flow() -> OAuthFlow {
+    OAuthFlow::new(
+        "123.456".to_string(),
+        "secret".to_string(),
+        "https://example.com/slack/oauth_redirect".to_string(),
+        vec!["chat:write".to_string()],
+        Vec::new(),
+        Box::new(MemoryInstallationStore::new()),
+        Box::new(MemoryStateStore::new()),
+    )
+}
+
+#[test]
+fn state_cookie_is_on_by_default() {
+    let flow = flow();
+
+    assert!(flow.state_cookie("state-1").unwrap().is_some());
+    assert!(flow.verify_state_cookie(None, "state-1").is_err());
+}
+
+#[test]
+fn state_cookie_can_be_turned_off() {
+    let flow = flow().with_state_cookie(false);
+
+    assert!(flow.state_cookie("state-1").unwrap().is_none());
+    assert!(flow.verify_state_cookie(None, "state-1").is_ok());
+}
+
+fn app(exchanger: FakeTokenExchanger, installations: MemoryInstallationStore) -> App {
+    App::builder()
+        .signing_secret(TEST_SIGNING_SECRET)
+        .client_id("123.456")
+        .client_secret("secret")
+        .redirect_uri("https://example.com/slack/oauth_redirect")
+        .client(MockSlackClient::new())
+        .oauth_settings(|settings| {
+            settings
+                .installation_store(installations)
+                .state_store(MemoryStateStore::new())
+                .token_exchanger(exchanger)
+        })
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn settings_scopes_override_the_builder_even_when_they_are_the_default() {
+    let app = App::builder()
+        .signing_secret(TEST_SIGNING_SECRET)
+        .client_id("123.456")
+        .client_secret("secret")
+        .redirect_uri("https://example.com/slack/oauth_redirect")
+        .scopes(["chat:write", "commands"])
+        .oauth_settings(|settings| {
+            settings
+                .scopes(["chat:write"])
+                .installation_store(MemoryInstallationStore::new())
+                .state_store(MemoryStateStore::new())
+        })
+        .build()
+        .unwrap();
+
+    let url = app.oauth_flow().unwrap().start().await.unwrap();
+
+    assert!(url.contains("scope=chat%3Awrite&"), "{}", url);
+}
+
+#[tokio::test]
+async fn complete_install_stores_the_installation() {
+    let exchanger = FakeTokenExchanger::success();
+    let installations = MemoryInstallationStore::new();
+    let app = app(exchanger.clone(), installations.clone());
+    let (_, state) = app.oauth_flow().unwrap().start_with_state().await.unwrap();
+
+    let outcome = app.complete_install("code-1", &state).await.unwrap();
+
+    assert_eq!(exchanger.codes(), vec!["code-1".to_string()]);
+    assert_eq!(outcome.installation.team_id, TEST_TEAM_ID);
+    assert_eq!(outcome.installation.bot_token.as_ref().map(|token| token.expose()), Some(FAKE_BOT_TOKEN));
+    assert!(outcome.installation.expires_at.is_none());
+    assert_eq!(installations.installations().len(), 1);
+}
+
+#[tokio::test]
+async fn complete_install_with_a_bad_code_stores_nothing() {
+    let installations = MemoryInstallationStore::new();
+    let app = app(FakeTokenExchanger::bad_code(), installations.clone());
+    let (_, state) = app.oauth_flow().unwrap().start_with_state().await.unwrap();
+
+    let error = app.complete_install("expired", &state).await.unwrap_err();
+
+    assert!(matches!(error, SlackError::OAuth(ref message) if message == "invalid_code"));
+    assert!(installations.installations().is_empty());
+}
+
+#[tokio::test]
+async fn complete_install_with_rotation_keeps_the_expiry() {
+    let app = app(FakeTokenExchanger::rotating(), MemoryInstallationStore::new());
+    let (_, state) = app.oauth_flow().unwrap().start_with_state().await.unwrap();
+
+    let outcome = app.complete_install("code-1", &state).await.unwrap();
+
+    let installation = &outcome.installation;
+    assert_eq!(installation.bot_token.as_ref().map(|token| token.expose()), Some(FAKE_ROTATING_BOT_TOKEN));
+    assert!(installation.expires_at.is_some_and(|expires_at| expires_at > chrono::Utc::now()));
+    assert_eq!(outcome.response.refresh_token.as_ref().map(|token| token.expose()), Some(FAKE_REFRESH_TOKEN));
+}
@@ -0,0 +1,22 @@
+use slack_serverless::testing::{contains, CommandRequestBuilder, MockSlackClient, SpyClient, TEST_CHANNEL_ID};
+use slack_serverless::App;
+
+#[tokio::test]
+async fn spy_records_what_the_listener_posted() {
+    let spy = SpyClient::new(MockSlackClient::new());
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(spy.clone())
+        .build()
+        .unwrap();
+    app.command("/deploy", |context| async move {
+        context.say.text("Deploying prod").await?;
+        context.ack.empty().await
+    });
+
+    app.dispatch(CommandRequestBuilder::new("/deploy").build()).await.unwrap();
+
+    spy.assert_posted_to(TEST_CHANNEL_ID, contains("prod"));
+    spy.assert_not_posted_to("C0000OTHER");
+}
@@ -0,0 +1,27 @@
+use serde_json::json;
+use slack_serverless::response::SlackResponseBody;
+use slack_serverless::testing::{MockSlackClient, TestApp};
+use slack_serverless::App;
+
+#[tokio::test]
+async fn send_command_runs_the_apps_middleware() {
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(MockSlackClient::new())
+        .build()
+        .unwrap();
+    app.use_middleware(|mut context, next| async move {
+        context.set_custom("plan", json!("pro"));
+        next(context).await
+    });
+    app.command("/plan", |context| async move {
+        let plan = context.get_custom("plan").and_then(|v| v.as_str()).unwrap_or("none").to_string();
+        context.ack.text(plan).await
+    });
+    let app = TestApp::from(app);
+
+    let response = app.send_command("/plan", "show").await.unwrap();
+
+    assert!(matches!(response.body, SlackResponseBody::Text(ref text) if text.text == "pro"));
+}
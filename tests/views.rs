@@ -0,0 +1,30 @@
+use serde_json::json;
+use slack_serverless::blocks::View;
+use slack_serverless::client::ViewHandle;
+use slack_serverless::testing::MockSlackClient;
+use slack_serverless::SlackError;
+
+#[tokio::test]
+async fn update_sends_the_hash_and_adopts_the_new_one() {
+    let client = MockSlackClient::new();
+    client.stub("views.update", json!({ "ok": true, "view": { "id": "V1", "type": "modal", "hash": "h2" } }));
+    let mut handle = ViewHandle::new("V1", Some("h1".to_string()));
+
+    handle.update(&client, &View::modal("Results")).await.unwrap();
+
+    assert_eq!(client.calls_to("views.update")[0]["hash"], "h1");
+    assert_eq!(handle.hash.as_deref(), Some("h2"));
+}
+
+#[tokio::test]
+async fn hash_conflict_is_returned_without_dropping_the_hash() {
+    let client = MockSlackClient::new();
+    client.stub_error("views.update", "hash_conflict");
+    let mut handle = ViewHandle::new("V1", Some("h1".to_string()));
+
+    let error = handle.update(&client, &View::modal("Results")).await.unwrap_err();
+
+    assert!(matches!(error, SlackError::SlackApi { ref code, .. } if code == "hash_conflict"));
+    client.assert_called_times("views.update", 1);
+    assert_eq!(handle.hash.as_deref(), Some("h1"));
+}
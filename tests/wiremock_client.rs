@@ -0,0 +1,160 @@
+#![cfg(feature = "wiremock")]
+
+use async_trait::async_trait;
+use serde_json::json;
+use slack_serverless::client::{Bucket, RateLimiter};
+use slack_serverless::testing::wiremock::SlackApiServer;
+use slack_serverless::{Result, SlackApi, SlackError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+async fn mount(server: &SlackApiServer, api_method: &str, response: ResponseTemplate, times: u64) {
+    Mock::given(method("POST"))
+        .and(path(format!("/api/{}", api_method)))
+        .respond_with(response)
+        .up_to_n_times(times)
+        .mount(server.server())
+        .await;
+}
+
+fn ratelimited() -> ResponseTemplate {
+    ResponseTemplate::new(429)
+        .insert_header("Retry-After", "0")
+        .set_body_json(json!({ "ok": false, "error": "ratelimited" }))
+}
+
+#[tokio::test]
+async fn ratelimited_post_is_retried() {
+    let server = SlackApiServer::start().await;
+    mount(&server, "chat.postMessage", ratelimited(), 1).await;
+    server.mock_post_message().await;
+
+    let client = server.client().with_max_retries(2);
+    let response = client.call("chat.postMessage", json!({ "channel": "C1", "text": "hi" })).await;
+
+    assert!(response.is_ok());
+    assert_eq!(server.received("chat.postMessage").await.len(), 2);
+}
+
+#[tokio::test]
+async fn post_that_may_have_landed_is_not_retried() {
+    let server = SlackApiServer::start().await;
+    mount(&server, "chat.postMessage", ResponseTemplate::new(502).set_body_string("Bad Gateway"), 1).await;
+    server.mock_post_message().await;
+
+    let client = server.client().with_max_retries(2);
+    let response = client.call("chat.postMessage", json!({ "channel": "C1", "text": "hi" })).await;
+
+    assert!(response.is_err());
+    assert_eq!(server.received("chat.postMessage").await.len(), 1);
+}
+
+#[tokio::test]
+async fn post_failing_with_internal_error_is_not_retried() {
+    let server = SlackApiServer::start().await;
+    mount(
+        &server,
+        "chat.postMessage",
+        ResponseTemplate::new(200).set_body_json(json!({ "ok": false, "error": "internal_error" })),
+        1,
+    )
+    .await;
+    server.mock_post_message().await;
+
+    let client = server.client().with_max_retries(2);
+    let error = client.call("chat.postMessage", json!({ "channel": "C1", "text": "hi" })).await.unwrap_err();
+
+    assert!(matches!(error, SlackError::SlackApi { ref code, .. } if code == "internal_error"));
+    assert_eq!(server.received("chat.postMessage").await.len(), 1);
+}
+
+#[tokio::test]
+async fn post_failing_with_a_server_error_is_not_retried() {
+    let server = SlackApiServer::start().await;
+    mount(
+        &server,
+        "chat.postMessage",
+        ResponseTemplate::new(500).set_body_json(json!({ "ok": false, "error": "internal_error" })),
+        1,
+    )
+    .await;
+    server.mock_post_message().await;
+
+    let client = server.client().with_max_retries(2);
+    let error = client.call("chat.postMessage", json!({ "channel": "C1", "text": "hi" })).await.unwrap_err();
+
+    assert!(matches!(error, SlackError::SlackApi { ref code, .. } if code == "internal_error"));
+    assert_eq!(server.received("chat.postMessage").await.len(), 1);
+}
+
+#[tokio::test]
+async fn idempotent_read_is_retried_after_internal_error() {
+    let server = SlackApiServer::start().await;
+    mount(
+        &server,
+        "conversations.history",
+        ResponseTemplate::new(200).set_body_json(json!({ "ok": false, "error": "internal_error" })),
+        1,
+    )
+    .await;
+    server.mock_ok("conversations.history", json!({ "messages": [] })).await;
+
+    let client = server.client().with_max_retries(2);
+    let response = client.call("conversations.history", json!({ "channel": "C1" })).await;
+
+    assert!(response.is_ok());
+    assert_eq!(server.received("conversations.history").await.len(), 2);
+}
+
+#[derive(Debug, Default)]
+struct CountingLimiter {
+    taken: AtomicUsize,
+}
+
+#[async_trait]
+impl RateLimiter for CountingLimiter {
+    async fn try_take(&self, _bucket: &Bucket) -> Result<Option<Duration>> {
+        self.taken.fetch_add(1, Ordering::SeqCst);
+        Ok(None)
+    }
+}
+
+#[tokio::test]
+async fn every_attempt_takes_a_rate_limit_token() {
+    let server = SlackApiServer::start().await;
+    mount(&server, "chat.postMessage", ratelimited(), 2).await;
+    server.mock_post_message().await;
+
+    let limiter = Arc::new(CountingLimiter::default());
+    let client = server.client().with_max_retries(2).with_rate_limiter(limiter.clone());
+    client.call("chat.postMessage", json!({ "channel": "C1", "text": "hi" })).await.unwrap();
+
+    assert_eq!(limiter.taken.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn listener_posts_through_the_mock_server() {
+    let server = SlackApiServer::start().await;
+    server.mock_post_message().await;
+    let mut app = slack_serverless::App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(server.client())
+        .build()
+        .unwrap();
+    app.command("/deploy", |context| async move {
+        context.say.text("Deploying prod").await?;
+        context.ack.empty().await
+    });
+
+    app.dispatch(slack_serverless::testing::CommandRequestBuilder::new("/deploy").build())
+        .await
+        .unwrap();
+
+    let posted = server.received("chat.postMessage").await;
+    assert_eq!(posted.len(), 1);
+    assert_eq!(posted[0]["text"], "Deploying prod");
+}
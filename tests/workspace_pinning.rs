@@ -0,0 +1,53 @@
+use bytes::Bytes;
+use http::HeaderMap;
+use serde_json::json;
+use slack_serverless::adapter::ErrorPolicy;
+use slack_serverless::request::{SlackRequest, SlackRequestBody, UnknownRequest};
+use slack_serverless::response::SlackResponse;
+use slack_serverless::testing::MockSlackClient;
+use slack_serverless::{App, SlackError};
+use std::collections::HashMap;
+
+fn unknown_request(team_id: &str) -> SlackRequest {
+    SlackRequest {
+        method: "POST".to_string(),
+        path: "/slack/events".to_string(),
+        headers: HeaderMap::new(),
+        query_params: HashMap::new(),
+        multi_query_params: HashMap::new(),
+        body: SlackRequestBody::Unknown(UnknownRequest {
+            payload: json!({ "type": "something_new", "team_id": team_id }),
+        }),
+        raw_body: Bytes::new(),
+        gateway: None,
+        correlation_id: None,
+        deferred: false,
+    }
+}
+
+fn pinned_app() -> App {
+    let mut app = App::builder()
+        .token("xoxb-1")
+        .signing_secret("secret")
+        .client(MockSlackClient::new())
+        .allowed_team_ids(["T_ALLOWED"])
+        .build()
+        .unwrap();
+    app.any(|_context| async { Ok(SlackResponse::text("handled")) });
+    app
+}
+
+#[tokio::test]
+async fn unknown_payload_from_disallowed_team_is_forbidden() {
+    let error = pinned_app().dispatch(unknown_request("T_OTHER")).await.unwrap_err();
+
+    assert!(matches!(error, SlackError::WorkspaceNotAllowed { .. }));
+    assert_eq!(ErrorPolicy::default_status_code(&error), 403);
+}
+
+#[tokio::test]
+async fn unknown_payload_from_allowed_team_reaches_catch_all() {
+    let response = pinned_app().dispatch(unknown_request("T_ALLOWED")).await.unwrap();
+
+    assert_eq!(response.status_code, 200);
+}